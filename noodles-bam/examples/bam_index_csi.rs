@@ -0,0 +1,92 @@
+//! Builds and writes a coordinate-sorted index (CSI) from a BAM file.
+//!
+//! The input BAM must be coordinate-sorted, i.e., `SO:coordinate`. The minimum interval size
+//! (`min_shift`) and number of levels (`depth`) can optionally be given and default to 14 and 5,
+//! respectively.
+//!
+//! This writes the output to stdout rather than `<src>.csi`.
+//!
+//! The output is similar to the output of `samtools index -c -m <min-shift> <src>`.
+
+use std::{env, fs::File, io};
+
+use noodles_bam as bam;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_sam::{self as sam, header::header::SortOrder};
+
+fn is_coordinate_sorted(header: &sam::Header) -> bool {
+    if let Some(hdr) = header.header() {
+        if let Some(sort_order) = hdr.sort_order() {
+            return sort_order == SortOrder::Coordinate;
+        }
+    }
+
+    false
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args();
+
+    let src = args.nth(1).expect("missing src");
+    let min_shift = args.next().map(|s| s.parse()).transpose()?.unwrap_or(14);
+    let depth = args.next().map(|s| s.parse()).transpose()?.unwrap_or(5);
+
+    let mut reader = File::open(src).map(bam::Reader::new)?;
+    let header: sam::Header = reader.read_header()?.parse()?;
+    reader.read_reference_sequences()?;
+
+    if !is_coordinate_sorted(&header) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the input BAM must be coordinate-sorted to be indexed",
+        )
+        .into());
+    }
+
+    let mut record = bam::Record::default();
+
+    let mut indexer = csi::Index::indexer();
+    indexer.set_min_shift(min_shift);
+    indexer.set_depth(depth);
+
+    let mut start_position = reader.virtual_position();
+
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let end_position = reader.virtual_position();
+        let chunk = Chunk::new(start_position, end_position);
+
+        match record.position() {
+            Some(position) => {
+                let reference_sequence_id = i32::from(record.reference_sequence_id()) as usize;
+
+                let start = i32::from(position) as u64;
+                let reference_len = u64::from(record.cigar().reference_len()?).max(1);
+                let end = start + reference_len - 1;
+
+                let is_mapped = !record.flags().is_unmapped();
+
+                indexer.add_record(reference_sequence_id, start, end, is_mapped, chunk);
+            }
+            None => indexer.add_unplaced_unmapped_record(),
+        }
+
+        start_position = end_position;
+    }
+
+    let index = indexer.build(header.reference_sequences().len());
+
+    let stdout = io::stdout();
+    let handle = stdout.lock();
+    let mut writer = csi::Writer::new(handle);
+
+    writer.write_index(&index)?;
+    writer.try_finish()?;
+
+    Ok(())
+}
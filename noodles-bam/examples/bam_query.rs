@@ -1,6 +1,8 @@
 //! Querys a BAM file with a given region.
 //!
-//! The input BAM must have an index in the same directory.
+//! The input BAM must have an index in the same directory, either a BAM index (BAI), e.g.,
+//! `sample.bam.bai`, or a coordinate-sorted index (CSI), e.g., `sample.bam.csi`. A CSI is preferred
+//! if both exist.
 //!
 //! While the results are not formatted the same, the records printed match the output of `samtools
 //! view <src> <region>`.
@@ -9,6 +11,7 @@ use std::{env, ffi::CStr, fs::File, path::PathBuf};
 
 use noodles::Region;
 use noodles_bam::{self as bam, bai};
+use noodles_csi::{self as csi, BinningIndex};
 use noodles_sam as sam;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -21,10 +24,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let header: sam::Header = reader.read_header()?.parse()?;
     let reference_sequences = header.reference_sequences();
 
-    let index = bai::read(src.with_extension("bam.bai"))?;
+    let csi_src = src.with_extension("bam.csi");
+
+    let index: Box<dyn BinningIndex> = if csi_src.exists() {
+        csi::read(csi_src).map(Box::new)?
+    } else {
+        bai::read(src.with_extension("bam.bai")).map(Box::new)?
+    };
 
     let region = Region::from_str_reference_sequences(&raw_region, reference_sequences)?;
-    let query = reader.query(reference_sequences, &index, &region)?;
+    let query = reader.query(reference_sequences, index.as_ref(), &region)?;
 
     for result in query {
         let record = result?;
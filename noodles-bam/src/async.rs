@@ -0,0 +1,6 @@
+//! Async BAM reader and writer.
+
+mod reader;
+mod writer;
+
+pub use self::{reader::Reader, writer::Writer};
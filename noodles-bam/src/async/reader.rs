@@ -0,0 +1,342 @@
+use futures::{stream, Stream};
+use noodles::Region;
+use noodles_bgzf::{self as bgzf, VirtualPosition};
+use noodles_csi::BinningIndex;
+use noodles_sam::header::{ReferenceSequence, ReferenceSequences};
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncSeek};
+
+use crate::{
+    reader::{bytes_with_nul_to_string, intersects, parse_header_text, resolve_region},
+    Record, MAGIC_NUMBER,
+};
+
+/// An async BAM reader.
+///
+/// This is the async counterpart of [`bam::Reader`]. It wraps an async BGZF reader and reads the
+/// SAM header, binary reference sequences, and records.
+///
+/// [`bam::Reader`]: ../struct.Reader.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> io::Result<()> {
+/// use futures::TryStreamExt;
+/// use noodles_bam as bam;
+/// use tokio::fs::File;
+///
+/// let mut reader = File::open("sample.bam").await.map(bam::AsyncReader::new)?;
+/// reader.read_header().await?;
+/// reader.read_reference_sequences().await?;
+///
+/// let mut records = reader.records();
+///
+/// while let Some(record) = records.try_next().await? {
+///     println!("{:?}", record);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Reader<R> {
+    inner: bgzf::AsyncReader<R>,
+}
+
+impl<R> Reader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates an async BAM reader.
+    ///
+    /// The given reader must be a raw BGZF stream, as the underlying reader wraps it in a decoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let data = [];
+    /// let reader = bam::AsyncReader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner: bgzf::AsyncReader::new(inner),
+        }
+    }
+
+    /// Reads the raw SAM header.
+    ///
+    /// The BAM magic number is also checked.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.bam").await.map(bam::AsyncReader::new)?;
+    /// let header = reader.read_header().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_header(&mut self) -> io::Result<String> {
+        let mut magic = [0; 4];
+        self.inner.read_exact(&mut magic).await?;
+
+        if magic != MAGIC_NUMBER {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid BAM header",
+            ));
+        }
+
+        let l_text = self.inner.read_u32_le().await?;
+
+        let mut c_text = vec![0; l_text as usize];
+        self.inner.read_exact(&mut c_text).await?;
+
+        parse_header_text(c_text)
+    }
+
+    /// Reads the binary reference sequences after the SAM header.
+    ///
+    /// The position of the stream is expected to be directly after the header.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.bam").await.map(bam::AsyncReader::new)?;
+    /// reader.read_header().await?;
+    /// let reference_sequences = reader.read_reference_sequences().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_reference_sequences(&mut self) -> io::Result<Vec<ReferenceSequence>> {
+        let n_ref = self.inner.read_u32_le().await?;
+        let mut reference_sequences = Vec::with_capacity(n_ref as usize);
+
+        for _ in 0..n_ref {
+            let l_name = self.inner.read_u32_le().await?;
+
+            let mut c_name = vec![0; l_name as usize];
+            self.inner.read_exact(&mut c_name).await?;
+
+            let name = bytes_with_nul_to_string(&c_name)?;
+            let l_ref = self.inner.read_u32_le().await?;
+
+            reference_sequences.push(ReferenceSequence::new(name, l_ref as i32));
+        }
+
+        Ok(reference_sequences)
+    }
+
+    /// Reads a single record.
+    ///
+    /// The record block size (`bs`) is read from the underlying stream, and `bs` additional bytes
+    /// are read into the given record.
+    ///
+    /// If successful, the record block size is returned. If a block size of 0 is returned, the
+    /// stream reached EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.bam").await.map(bam::AsyncReader::new)?;
+    /// reader.read_header().await?;
+    /// reader.read_reference_sequences().await?;
+    ///
+    /// let mut record = bam::Record::default();
+    /// reader.read_record(&mut record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        let block_size = match self.inner.read_u32_le().await {
+            Ok(bs) => bs as usize,
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
+            Err(e) => return Err(e),
+        };
+
+        record.resize(block_size);
+        self.inner.read_exact(record).await?;
+
+        Ok(block_size)
+    }
+
+    /// Returns a stream over records starting from the current stream position.
+    ///
+    /// The stream is expected to be directly after the reference sequences or at the start of
+    /// another record.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use futures::TryStreamExt;
+    /// use noodles_bam as bam;
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.bam").await.map(bam::AsyncReader::new)?;
+    /// reader.read_header().await?;
+    /// reader.read_reference_sequences().await?;
+    ///
+    /// let mut records = reader.records();
+    ///
+    /// while let Some(record) = records.try_next().await? {
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn records(&mut self) -> impl Stream<Item = io::Result<Record>> + Unpin + '_ {
+        Box::pin(stream::try_unfold(self, |reader| async move {
+            let mut record = Record::default();
+
+            match reader.read_record(&mut record).await? {
+                0 => Ok(None),
+                _ => Ok(Some((record, reader))),
+            }
+        }))
+    }
+
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = [];
+    /// let reader = bam::AsyncReader::new(&data[..]);
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn virtual_position(&self) -> VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Seeks the underlying BGZF reader to the given virtual position.
+    ///
+    /// Virtual positions typically come from the associated BAM index file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.bam").await.map(bam::AsyncReader::new)?;
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::from(102334155);
+    /// reader.seek(virtual_position).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn seek(&mut self, pos: VirtualPosition) -> io::Result<VirtualPosition> {
+        self.inner.seek(pos).await
+    }
+
+    /// Returns a stream over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::TryStreamExt;
+    /// use noodles::Region;
+    /// use noodles_bam::{self as bam, bai};
+    /// use noodles_sam as sam;
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.bam").await.map(bam::AsyncReader::new)?;
+    /// let header: sam::Header = reader.read_header().await?.parse()?;
+    ///
+    /// let reference_sequences = header.reference_sequences();
+    /// let index = bai::read("sample.bam.bai")?;
+    /// let region = Region::mapped("sq0", 17711, 28657);
+    /// let mut query = reader.query(&reference_sequences, &index, &region)?;
+    ///
+    /// while let Some(record) = query.try_next().await? {
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query<I>(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+        index: &I,
+        region: &Region,
+    ) -> io::Result<impl Stream<Item = io::Result<Record>> + Unpin + '_>
+    where
+        I: BinningIndex + ?Sized,
+    {
+        let (i, start, end) = resolve_region(reference_sequences, region)?;
+        let chunks = index.query(i, start, end)?;
+
+        let state = (self, chunks.into_iter(), None);
+
+        Ok(Box::pin(stream::try_unfold(
+            state,
+            move |(reader, mut chunks, mut chunk_end)| async move {
+                loop {
+                    match chunk_end {
+                        Some(pos) if reader.virtual_position() < pos => {}
+                        _ => match chunks.next() {
+                            Some(chunk) => {
+                                reader.seek(chunk.start()).await?;
+                                chunk_end = Some(chunk.end());
+                            }
+                            None => return Ok(None),
+                        },
+                    }
+
+                    let mut record = Record::default();
+
+                    if reader.read_record(&mut record).await? == 0 {
+                        return Ok(None);
+                    }
+
+                    if intersects(&record, i, start, end)? {
+                        return Ok(Some((record, (reader, chunks, chunk_end))));
+                    }
+                }
+            },
+        )))
+    }
+}
+
+impl<R> From<bgzf::AsyncReader<R>> for Reader<R> {
+    fn from(inner: bgzf::AsyncReader<R>) -> Self {
+        Self { inner }
+    }
+}
@@ -0,0 +1,288 @@
+use noodles_bgzf as bgzf;
+use noodles_sam::{self as sam, header::ReferenceSequences};
+use tokio::io::{self, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    writer::{record::write_sam_record, write_reference},
+    Record, MAGIC_NUMBER,
+};
+
+/// An async BAM writer.
+///
+/// This is the async counterpart of [`bam::Writer`]. Unlike the synchronous writer, the output
+/// stream is not finished when the writer is dropped; [`shutdown`] must be called to write the
+/// final BGZF EOF block.
+///
+/// [`bam::Writer`]: ../struct.Writer.html
+/// [`shutdown`]: #method.shutdown
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> io::Result<()> {
+/// use noodles_bam as bam;
+/// use noodles_sam as sam;
+///
+/// let mut writer = bam::AsyncWriter::new(Vec::new());
+///
+/// let header = sam::Header::builder().add_comment("noodles-bam").build();
+/// writer.write_header(&header).await?;
+/// writer.write_reference_sequences(header.reference_sequences()).await?;
+///
+/// let record = sam::Record::default();
+/// writer.write_sam_record(header.reference_sequences(), &record).await?;
+///
+/// writer.shutdown().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Writer<W> {
+    inner: bgzf::AsyncWriter<W>,
+    buf: Vec<u8>,
+}
+
+impl<W> Writer<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async BAM writer with a default compression level.
+    ///
+    /// The given stream is wrapped in an async BGZF encoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::AsyncWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::from(bgzf::AsyncWriter::new(inner))
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::AsyncWriter::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner()
+    }
+
+    /// Shuts down the output stream.
+    ///
+    /// This flushes any buffered data and writes the final BGZF EOF block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// let mut writer = bam::AsyncWriter::new(Vec::new());
+    /// writer.shutdown().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+
+    /// Writes a SAM header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::AsyncWriter::new(Vec::new());
+    ///
+    /// let header = sam::Header::builder().add_comment("noodles-bam").build();
+    /// writer.write_header(&header).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.inner.write_all(MAGIC_NUMBER).await?;
+
+        let text = header.to_string();
+        let l_text = text.len() as i32;
+        self.inner.write_i32_le(l_text).await?;
+
+        self.inner.write_all(text.as_bytes()).await?;
+
+        Ok(())
+    }
+
+    /// Writes SAM reference sequences.
+    ///
+    /// The reference sequences here are typically the same as the reference sequences in the SAM
+    /// header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::AsyncWriter::new(Vec::new());
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(sam::header::ReferenceSequence::new(String::from("sq0"), 8))
+    ///     .add_comment("noodles-bam")
+    ///     .build();
+    ///
+    /// writer.write_header(&header).await?;
+    /// writer.write_reference_sequences(header.reference_sequences()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_reference_sequences(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+    ) -> io::Result<()> {
+        self.buf.clear();
+
+        let n_ref = reference_sequences.len() as i32;
+        self.buf.extend_from_slice(&n_ref.to_le_bytes());
+
+        for reference_sequence in reference_sequences.values() {
+            write_reference(&mut self.buf, reference_sequence)?;
+        }
+
+        self.inner.write_all(&self.buf).await
+    }
+
+    /// Writes a BAM record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// let mut writer = bam::AsyncWriter::new(Vec::new());
+    /// let record = bam::Record::default();
+    /// writer.write_record(&record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let block_size = record.len() as u32;
+        self.inner.write_u32_le(block_size).await?;
+        self.inner.write_all(record).await
+    }
+
+    /// Writes a SAM record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bam as bam;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::AsyncWriter::new(Vec::new());
+    ///
+    /// let reference_sequences = sam::header::ReferenceSequences::new();
+    /// let record = sam::Record::default();
+    /// writer.write_sam_record(&reference_sequences, &record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_sam_record(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+        record: &sam::Record,
+    ) -> io::Result<()> {
+        self.buf.clear();
+        write_sam_record(&mut self.buf, reference_sequences, record)?;
+        self.inner.write_all(&self.buf).await
+    }
+}
+
+impl<W> From<bgzf::AsyncWriter<W>> for Writer<W> {
+    fn from(inner: bgzf::AsyncWriter<W>) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use crate::AsyncReader;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_and_read() -> io::Result<()> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(sam::header::ReferenceSequence::new(String::from("sq0"), 8))
+            .add_comment("noodles-bam")
+            .build();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header).await?;
+        writer
+            .write_reference_sequences(header.reference_sequences())
+            .await?;
+
+        let sam_record = sam::Record::default();
+        writer
+            .write_sam_record(header.reference_sequences(), &sam_record)
+            .await?;
+        writer
+            .write_sam_record(header.reference_sequences(), &sam_record)
+            .await?;
+
+        writer.shutdown().await?;
+
+        let data = writer.into_inner();
+        let mut reader = AsyncReader::new(&data[..]);
+
+        assert_eq!(reader.read_header().await?, header.to_string());
+
+        let reference_sequences = reader.read_reference_sequences().await?;
+        assert_eq!(reference_sequences.len(), 1);
+        assert_eq!(reference_sequences[0].name(), "sq0");
+        assert_eq!(reference_sequences[0].len(), 8);
+
+        let records: Vec<_> = reader.records().try_collect().await?;
+        assert_eq!(records.len(), 2);
+
+        Ok(())
+    }
+}
@@ -5,6 +5,12 @@ pub mod reference_sequence;
 
 pub use self::{builder::Builder, reference_sequence::ReferenceSequence};
 
+use std::io;
+
+use noodles_csi::{self as csi, BinningIndex};
+
+use self::reference_sequence::bin::Chunk;
+
 /// A BAM index.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Index {
@@ -75,3 +81,32 @@ impl Index {
         self.n_no_coor
     }
 }
+
+impl BinningIndex for Index {
+    fn query(&self, reference_sequence_id: usize, start: u64, end: u64) -> io::Result<Vec<Chunk>> {
+        let reference_sequence = self
+            .reference_sequences()
+            .get(reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "could not find reference in index: {} >= {}",
+                        reference_sequence_id,
+                        self.reference_sequences().len()
+                    ),
+                )
+            })?;
+
+        let chunks: Vec<_> = reference_sequence
+            .query(start, end)
+            .iter()
+            .flat_map(|bin| bin.chunks())
+            .copied()
+            .collect();
+
+        let min_offset = reference_sequence.min_offset(start);
+
+        Ok(csi::optimize_chunks(&chunks, min_offset))
+    }
+}
@@ -48,6 +48,42 @@
 //! }
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! ## Convert to SAM
+//!
+//! BAM records can be written as SAM records using a [`sam::Writer`]. This is equivalent to
+//! `samtools view -h sample.bam`.
+//!
+//! [`sam::Writer`]: ../noodles_sam/struct.Writer.html
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_bam as bam;
+//! use noodles_sam as sam;
+//!
+//! let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+//! let header: sam::Header = reader.read_header()?.parse()?;
+//! reader.read_reference_sequences()?;
+//!
+//! let stdout = io::stdout();
+//! let mut writer = sam::Writer::new(stdout.lock());
+//! writer.write_header(&header)?;
+//!
+//! for result in reader.records() {
+//!     let record = result?;
+//!     writer.write_alignment_record(header.reference_sequences(), &record)?;
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Features
+//!
+//!   * `async`: Enables async I/O using [Tokio], i.e., `AsyncReader` and `AsyncWriter`.
+//!
+//! [Tokio]: https://tokio.rs/
+
+#[cfg(feature = "async")]
+mod r#async;
 
 pub mod bai;
 pub mod reader;
@@ -56,4 +92,7 @@ mod writer;
 
 pub use self::{reader::Reader, record::Record, writer::Writer};
 
+#[cfg(feature = "async")]
+pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
+
 static MAGIC_NUMBER: &[u8] = b"BAM\x01";
@@ -6,6 +6,9 @@ mod unmapped_records;
 
 pub use self::{query::Query, records::Records, unmapped_records::UnmappedRecords};
 
+#[cfg(feature = "async")]
+pub(crate) use self::query::intersects;
+
 use std::{
     ffi::CStr,
     io::{self, Read, Seek},
@@ -14,6 +17,7 @@ use std::{
 use byteorder::{LittleEndian, ReadBytesExt};
 use noodles::Region;
 use noodles_bgzf::{self as bgzf, VirtualPosition};
+use noodles_csi::BinningIndex;
 use noodles_sam::header::{ReferenceSequence, ReferenceSequences};
 
 use super::{bai, Record, MAGIC_NUMBER};
@@ -70,6 +74,23 @@ where
     /// let mut _reader = File::open("sample.bam").map(bam::Reader::new)?;
     /// # Ok::<(), io::Error>(())
     /// ```
+    ///
+    /// To decompress blocks using multiple threads, create the BGZF reader using
+    /// [`bgzf::Reader::with_worker_count`] and convert it to a BAM reader.
+    ///
+    /// [`bgzf::Reader::with_worker_count`]: ../../noodles_bgzf/struct.Reader.html#method.with_worker_count
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io, num::NonZeroUsize};
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let inner = File::open("sample.bam")
+    ///     .map(|f| bgzf::Reader::with_worker_count(worker_count, f))?;
+    /// let mut _reader = bam::Reader::from(inner);
+    /// # Ok::<(), io::Error>(())
+    /// ```
     pub fn new(reader: R) -> Self {
         Self {
             inner: bgzf::Reader::new(reader),
@@ -274,6 +295,9 @@ where
 
     /// Returns an iterator over records that intersect the given region.
     ///
+    /// The index can be any binning index, e.g., a BAM index (BAI) or a coordinate-sorted index
+    /// (CSI). The region is always resolved using the given reference sequences.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -296,35 +320,17 @@ where
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn query(
+    pub fn query<I>(
         &mut self,
         reference_sequences: &ReferenceSequences,
-        index: &bai::Index,
+        index: &I,
         region: &Region,
-    ) -> io::Result<Query<'_, R>> {
+    ) -> io::Result<Query<'_, R>>
+    where
+        I: BinningIndex + ?Sized,
+    {
         let (i, start, end) = resolve_region(reference_sequences, region)?;
-
-        let index_reference_sequence = index.reference_sequences().get(i).ok_or_else(|| {
-            io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "could not find reference in index: {} >= {}",
-                    i,
-                    reference_sequences.len()
-                ),
-            )
-        })?;
-
-        let query_bins = index_reference_sequence.query(start, end);
-
-        let chunks: Vec<_> = query_bins
-            .iter()
-            .flat_map(|bin| bin.chunks())
-            .cloned()
-            .collect();
-
-        let min_offset = index_reference_sequence.min_offset(start);
-        let merged_chunks = bai::optimize_chunks(&chunks, min_offset);
+        let merged_chunks = index.query(i, start, end)?;
 
         Ok(Query::new(self, merged_chunks, i, start, end))
     }
@@ -364,6 +370,15 @@ where
     }
 }
 
+impl<R> From<bgzf::Reader<R>> for Reader<R>
+where
+    R: Read,
+{
+    fn from(inner: bgzf::Reader<R>) -> Self {
+        Self { inner }
+    }
+}
+
 fn read_magic<R>(reader: &mut R) -> io::Result<[u8; 4]>
 where
     R: Read,
@@ -382,6 +397,10 @@ where
     let mut c_text = vec![0; l_text as usize];
     reader.read_exact(&mut c_text)?;
 
+    parse_header_text(c_text)
+}
+
+pub(crate) fn parse_header_text(c_text: Vec<u8>) -> io::Result<String> {
     // Headers are not necessarily NUL-terminated.
     bytes_with_nul_to_string(&c_text).or_else(|_| {
         String::from_utf8(c_text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
@@ -403,7 +422,7 @@ where
     Ok(ReferenceSequence::new(name, l_ref as i32))
 }
 
-fn bytes_with_nul_to_string(buf: &[u8]) -> io::Result<String> {
+pub(crate) fn bytes_with_nul_to_string(buf: &[u8]) -> io::Result<String> {
     CStr::from_bytes_with_nul(buf)
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
         .and_then(|c_str| {
@@ -414,7 +433,7 @@ fn bytes_with_nul_to_string(buf: &[u8]) -> io::Result<String> {
         })
 }
 
-fn resolve_region(
+pub(crate) fn resolve_region(
     reference_sequences: &ReferenceSequences,
     region: &Region,
 ) -> io::Result<(usize, u64, u64)> {
@@ -1,8 +1,9 @@
 use std::io::{self, Read, Seek};
 
 use noodles_bgzf::VirtualPosition;
+use noodles_csi::index::reference_sequence::bin::Chunk;
 
-use crate::{bai::index::reference_sequence::bin::Chunk, Record};
+use crate::Record;
 
 use super::Reader;
 
@@ -100,32 +101,15 @@ where
 
                         match result {
                             Ok(record) => {
-                                if let Some(reference_sequence_id) = *record.reference_sequence_id()
-                                {
-                                    let reference_sequence_id = reference_sequence_id as usize;
-
-                                    let record_start = record
-                                        .position()
-                                        .map(i32::from)
-                                        .map(|n| n as u64)
-                                        .expect("missing position");
-                                    let record_reference_len = match record.cigar().reference_len()
-                                    {
-                                        Ok(len) => u64::from(len),
-                                        Err(e) => return Some(Err(e)),
-                                    };
-                                    let record_end = record_start + record_reference_len - 1;
-
-                                    if reference_sequence_id == self.reference_sequence_id
-                                        && in_interval(
-                                            record_start,
-                                            record_end,
-                                            self.start,
-                                            self.end,
-                                        )
-                                    {
-                                        return Some(Ok(record));
-                                    }
+                                match intersects(
+                                    &record,
+                                    self.reference_sequence_id,
+                                    self.start,
+                                    self.end,
+                                ) {
+                                    Ok(true) => return Some(Ok(record)),
+                                    Ok(false) => {}
+                                    Err(e) => return Some(Err(e)),
                                 }
                             }
                             Err(e) => return Some(Err(e)),
@@ -141,6 +125,29 @@ where
     }
 }
 
+pub(crate) fn intersects(
+    record: &Record,
+    reference_sequence_id: usize,
+    start: u64,
+    end: u64,
+) -> io::Result<bool> {
+    let record_reference_sequence_id = match *record.reference_sequence_id() {
+        Some(id) => id as usize,
+        None => return Ok(false),
+    };
+
+    let record_start = record
+        .position()
+        .map(i32::from)
+        .map(|n| n as u64)
+        .expect("missing position");
+    let record_reference_len = u64::from(record.cigar().reference_len()?);
+    let record_end = record_start + record_reference_len - 1;
+
+    Ok(record_reference_sequence_id == reference_sequence_id
+        && in_interval(record_start, record_end, start, end))
+}
+
 fn in_interval(a_start: u64, a_end: u64, b_start: u64, b_end: u64) -> bool {
     a_start <= b_end && b_start <= a_end
 }
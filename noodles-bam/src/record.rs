@@ -76,6 +76,84 @@ impl Record {
         Ok(Self::from(buf[start..].to_vec()))
     }
 
+    /// Converts this BAM record to a fully decoded SAM record.
+    ///
+    /// The reference sequences are used to resolve the reference sequence and mate reference
+    /// sequence names from their IDs. The sequence, quality scores, and data fields are all
+    /// decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::{self as sam, header::ReferenceSequences};
+    ///
+    /// let reference_sequences = ReferenceSequences::default();
+    /// let record = bam::Record::default();
+    ///
+    /// let sam_record = record.try_into_sam_record(&reference_sequences)?;
+    /// assert_eq!(sam_record, sam::Record::default());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_into_sam_record(
+        &self,
+        reference_sequences: &sam::header::ReferenceSequences,
+    ) -> io::Result<sam::Record> {
+        let mut builder = sam::Record::builder();
+
+        let read_name = CStr::from_bytes_with_nul(self.read_name())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            .and_then(|c_str| {
+                c_str
+                    .to_str()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })?;
+
+        if read_name != "*" {
+            let read_name = read_name
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            builder = builder.set_read_name(read_name);
+        }
+
+        builder = builder.set_flags(self.flags());
+
+        if let Some(name) =
+            get_reference_sequence_name(reference_sequences, self.reference_sequence_id())?
+        {
+            builder = builder.set_reference_sequence_name(name);
+        }
+
+        if let Some(position) = self.position() {
+            builder = builder.set_position(position);
+        }
+
+        builder = builder
+            .set_mapping_quality(self.mapping_quality())
+            .set_cigar(sam::record::Cigar::try_from(self.cigar())?);
+
+        if let Some(name) =
+            get_reference_sequence_name(reference_sequences, self.mate_reference_sequence_id())?
+        {
+            builder = builder.set_mate_reference_sequence_name(name);
+        }
+
+        if let Some(mate_position) = self.mate_position() {
+            builder = builder.set_mate_position(mate_position);
+        }
+
+        let record = builder
+            .set_template_length(self.template_length())
+            .set_sequence(sam::record::Sequence::from(self.sequence()))
+            .set_quality_scores(sam::record::QualityScores::try_from(self.quality_scores())?)
+            .set_data(sam::record::Data::try_from(self.data())?)
+            .build();
+
+        Ok(record)
+    }
+
     pub(crate) fn resize(&mut self, new_len: usize) {
         self.0.resize(new_len, Default::default());
     }
@@ -336,6 +414,43 @@ impl Record {
     }
 }
 
+fn get_reference_sequence_name(
+    reference_sequences: &sam::header::ReferenceSequences,
+    reference_sequence_id: ReferenceSequenceId,
+) -> io::Result<Option<sam::record::ReferenceSequenceName>> {
+    let id = match *reference_sequence_id {
+        Some(id) => id as usize,
+        None => return Ok(None),
+    };
+
+    reference_sequences
+        .get_index(id)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid reference sequence ID: expected < {}, got {}",
+                    reference_sequences.len(),
+                    id
+                ),
+            )
+        })
+        .and_then(|(name, _)| {
+            name.parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+}
+
+impl sam::AlignmentRecord for Record {
+    fn try_into_sam_record(
+        &self,
+        reference_sequences: &sam::header::ReferenceSequences,
+    ) -> io::Result<sam::Record> {
+        Record::try_into_sam_record(self, reference_sequences)
+    }
+}
+
 impl Default for Record {
     fn default() -> Self {
         Self::from(vec![
@@ -589,4 +704,58 @@ mod tests {
         assert_eq!(*record.data(), expected);
         Ok(())
     }
+
+    #[test]
+    fn test_try_into_sam_record() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::{Flags, MappingQuality, Position};
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(sam::header::ReferenceSequence::new(String::from("sq0"), 8))
+            .add_reference_sequence(sam::header::ReferenceSequence::new(String::from("sq1"), 13))
+            .build();
+        let reference_sequences = header.reference_sequences();
+
+        let expected = sam::Record::builder()
+            .set_read_name("r0".parse()?)
+            .set_flags(Flags::PAIRED | Flags::READ_1)
+            .set_reference_sequence_name("sq1".parse()?)
+            .set_position(Position::try_from(2)?)
+            .set_mapping_quality(MappingQuality::from(37))
+            .set_cigar("4M".parse()?)
+            .set_mate_reference_sequence_name("sq0".parse()?)
+            .set_mate_position(Position::try_from(5)?)
+            .set_template_length(-3)
+            .set_sequence("ACGT".parse()?)
+            .set_quality_scores("NDLS".parse()?)
+            .set_data("NH:i:1\tRG:Z:rg0".parse()?)
+            .build();
+
+        let record = Record::try_from_sam_record(reference_sequences, &expected)?;
+        let actual = record.try_into_sam_record(reference_sequences)?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_sam_record_with_invalid_reference_sequence_id() -> io::Result<()> {
+        let record = build_record()?;
+        let reference_sequences = sam::header::ReferenceSequences::default();
+        assert!(record.try_into_sam_record(&reference_sequences).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record() -> io::Result<()> {
+        let reference_sequences = sam::header::ReferenceSequences::default();
+        let record = Record::default();
+
+        let mut writer = sam::Writer::new(Vec::new());
+        writer.write_alignment_record(&reference_sequences, &record)?;
+
+        assert_eq!(writer.get_ref(), b"*\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\n");
+
+        Ok(())
+    }
 }
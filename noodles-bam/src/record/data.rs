@@ -5,7 +5,9 @@ pub mod reader;
 
 pub use self::{field::Field, reader::Reader};
 
-use std::ops::Deref;
+use std::{convert::TryFrom, io, ops::Deref};
+
+use noodles_sam as sam;
 
 use self::reader::Fields;
 
@@ -74,3 +76,18 @@ impl<'a> Deref for Data<'a> {
         self.0
     }
 }
+
+impl<'a> TryFrom<Data<'a>> for sam::record::Data {
+    type Error = io::Error;
+
+    fn try_from(data: Data<'_>) -> Result<Self, Self::Error> {
+        let mut fields = Vec::new();
+
+        for result in data.fields() {
+            let field = result.map(sam::record::data::Field::from)?;
+            fields.push(field);
+        }
+
+        Ok(Self::from(fields))
+    }
+}
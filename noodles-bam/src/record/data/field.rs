@@ -4,7 +4,7 @@ pub mod value;
 
 pub use self::value::Value;
 
-use noodles_sam::record::data::field::Tag;
+use noodles_sam::{self as sam, record::data::field::Tag};
 
 /// A BAM record data field.
 #[derive(Clone, Debug, PartialEq)]
@@ -59,3 +59,10 @@ impl Field {
         &self.value
     }
 }
+
+impl From<Field> for sam::record::data::Field {
+    fn from(field: Field) -> Self {
+        let value = sam::record::data::field::Value::from(field.value);
+        Self::new(field.tag, value)
+    }
+}
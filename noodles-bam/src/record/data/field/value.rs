@@ -5,6 +5,10 @@ pub mod ty;
 
 pub use self::{subtype::Subtype, ty::Type};
 
+use std::convert::TryFrom;
+
+use noodles_sam as sam;
+
 /// A BAM record data field value.
 ///
 /// BAM record data field values support all the same types as a SAM record data field value:
@@ -622,6 +626,35 @@ impl Value {
     }
 }
 
+impl From<Value> for sam::record::data::field::Value {
+    /// Converts a BAM data field value to a SAM data field value.
+    ///
+    /// Single-value integer types are widened to a SAM 32-bit integer (`i`). A 32-bit unsigned
+    /// integer (`I`) that does not fit in a 32-bit integer is converted to a SAM 32-bit unsigned
+    /// integer.
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Char(c) => Self::Char(c),
+            Value::Int8(n) => Self::Int32(i32::from(n)),
+            Value::UInt8(n) => Self::Int32(i32::from(n)),
+            Value::Int16(n) => Self::Int32(i32::from(n)),
+            Value::UInt16(n) => Self::Int32(i32::from(n)),
+            Value::Int32(n) => Self::Int32(n),
+            Value::UInt32(n) => i32::try_from(n).map(Self::Int32).unwrap_or(Self::UInt32(n)),
+            Value::Float(n) => Self::Float(n),
+            Value::String(s) => Self::String(s),
+            Value::Hex(s) => Self::Hex(s),
+            Value::Int8Array(values) => Self::Int8Array(values),
+            Value::UInt8Array(values) => Self::UInt8Array(values),
+            Value::Int16Array(values) => Self::Int16Array(values),
+            Value::UInt16Array(values) => Self::UInt16Array(values),
+            Value::Int32Array(values) => Self::Int32Array(values),
+            Value::UInt32Array(values) => Self::UInt32Array(values),
+            Value::FloatArray(values) => Self::FloatArray(values),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -667,4 +700,38 @@ mod tests {
         assert_eq!(Value::UInt32Array(vec![0]).subtype(), Some(Subtype::UInt32));
         assert_eq!(Value::FloatArray(vec![0.0]).subtype(), Some(Subtype::Float));
     }
+
+    #[test]
+    fn test_from_value_for_sam_record_data_field_value() {
+        use sam::record::data::field::Value as SamValue;
+
+        assert_eq!(SamValue::from(Value::Char('m')), SamValue::Char('m'));
+        assert_eq!(SamValue::from(Value::Int8(-1)), SamValue::Int32(-1));
+        assert_eq!(SamValue::from(Value::UInt8(1)), SamValue::Int32(1));
+        assert_eq!(SamValue::from(Value::Int16(-1)), SamValue::Int32(-1));
+        assert_eq!(SamValue::from(Value::UInt16(1)), SamValue::Int32(1));
+        assert_eq!(SamValue::from(Value::Int32(-1)), SamValue::Int32(-1));
+        assert_eq!(SamValue::from(Value::UInt32(1)), SamValue::Int32(1));
+        assert_eq!(
+            SamValue::from(Value::UInt32(u32::MAX)),
+            SamValue::UInt32(u32::MAX)
+        );
+        assert_eq!(SamValue::from(Value::Float(0.0)), SamValue::Float(0.0));
+        assert_eq!(
+            SamValue::from(Value::String(String::from("noodles"))),
+            SamValue::String(String::from("noodles"))
+        );
+        assert_eq!(
+            SamValue::from(Value::Hex(String::from("cafe"))),
+            SamValue::Hex(String::from("cafe"))
+        );
+        assert_eq!(
+            SamValue::from(Value::UInt8Array(vec![0])),
+            SamValue::UInt8Array(vec![0])
+        );
+        assert_eq!(
+            SamValue::from(Value::FloatArray(vec![0.0])),
+            SamValue::FloatArray(vec![0.0])
+        );
+    }
 }
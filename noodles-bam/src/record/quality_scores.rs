@@ -1,7 +1,12 @@
-use std::{ops::Deref, slice};
+use std::{convert::TryFrom, io, ops::Deref, slice};
+
+use noodles_sam as sam;
 
 const QUALITY_OFFSET: u8 = b'!';
 
+// § 4.2.3 SEQ and QUAL encoding (2020-04-30)
+const NULL_QUALITY_SCORE: u8 = 255;
+
 /// BAM record quality scores.
 #[derive(Debug)]
 pub struct QualityScores<'a> {
@@ -95,6 +100,26 @@ fn byte_to_char(b: u8) -> char {
     (b + QUALITY_OFFSET) as char
 }
 
+impl<'a> TryFrom<QualityScores<'a>> for sam::record::QualityScores {
+    type Error = io::Error;
+
+    fn try_from(quality_scores: QualityScores<'_>) -> Result<Self, Self::Error> {
+        use sam::record::quality_scores::Score;
+
+        // A missing list of quality scores is filled with 0xff.
+        if quality_scores.first() == Some(&NULL_QUALITY_SCORE) {
+            return Ok(Self::default());
+        }
+
+        let scores = quality_scores
+            .iter()
+            .map(|&n| Score::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::from(scores))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,4 +131,20 @@ mod tests {
         let actual: Vec<char> = quality.chars().collect();
         assert_eq!(actual, vec!['>', '<', '>', '=', '@', '>', ';']);
     }
+
+    #[test]
+    fn test_try_from_quality_scores_for_sam_record_quality_scores() -> io::Result<()> {
+        let data = [45, 35, 43, 50];
+        let actual = sam::record::QualityScores::try_from(QualityScores::new(&data))?;
+        assert_eq!(actual.to_string(), "NDLS");
+
+        let data = [255, 255, 255, 255];
+        let actual = sam::record::QualityScores::try_from(QualityScores::new(&data))?;
+        assert!(actual.is_empty());
+
+        let data = [45, 94];
+        assert!(sam::record::QualityScores::try_from(QualityScores::new(&data)).is_err());
+
+        Ok(())
+    }
 }
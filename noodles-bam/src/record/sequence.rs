@@ -10,6 +10,8 @@ use std::{
     ops::{Deref, Index},
 };
 
+use noodles_sam as sam;
+
 static BASES: &[Base] = &[
     Base::Eq,
     Base::A,
@@ -159,6 +161,17 @@ impl<'a> Index<usize> for Sequence<'a> {
     }
 }
 
+impl<'a> From<Sequence<'a>> for sam::record::Sequence {
+    fn from(sequence: Sequence<'_>) -> Self {
+        let bases: Vec<_> = sequence
+            .bases()
+            .map(sam::record::sequence::Base::from)
+            .collect();
+
+        Self::from(bases)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +211,23 @@ mod tests {
         let sequence = Sequence::new(&data, 4);
         assert_eq!(sequence.to_string(), "ATGC");
     }
+
+    #[test]
+    fn test_from_sequence_for_sam_record_sequence() {
+        use sam::record::sequence::Base as SamBase;
+
+        let data = [0x18, 0x42, 0xf0];
+        let sequence = Sequence::new(&data, 5);
+
+        let actual = sam::record::Sequence::from(sequence);
+        let expected = sam::record::Sequence::from(vec![
+            SamBase::A,
+            SamBase::T,
+            SamBase::G,
+            SamBase::C,
+            SamBase::N,
+        ]);
+
+        assert_eq!(actual, expected);
+    }
 }
@@ -107,6 +107,29 @@ impl From<sam::record::sequence::Base> for Base {
     }
 }
 
+impl From<Base> for sam::record::sequence::Base {
+    fn from(base: Base) -> Self {
+        match base {
+            Base::Eq => Self::Eq,
+            Base::A => Self::A,
+            Base::C => Self::C,
+            Base::M => Self::M,
+            Base::G => Self::G,
+            Base::R => Self::R,
+            Base::S => Self::S,
+            Base::V => Self::V,
+            Base::T => Self::T,
+            Base::W => Self::W,
+            Base::Y => Self::Y,
+            Base::H => Self::H,
+            Base::K => Self::K,
+            Base::D => Self::D,
+            Base::B => Self::B,
+            Base::N => Self::N,
+        }
+    }
+}
+
 impl From<Base> for char {
     fn from(base: Base) -> Self {
         match base {
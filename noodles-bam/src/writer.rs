@@ -57,6 +57,24 @@ where
     /// use noodles_bam as bam;
     /// let writer = bam::Writer::new(Vec::new());
     /// ```
+    ///
+    /// To set the compression level or compress blocks using multiple threads, build the BGZF
+    /// writer using a [`bgzf::writer::Builder`] and convert it to a BAM writer.
+    ///
+    /// [`bgzf::writer::Builder`]: ../noodles_bgzf/writer/struct.Builder.html
+    ///
+    /// ```
+    /// # use std::num::NonZeroUsize;
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let inner = bgzf::writer::Builder::default()
+    ///     .set_compression_level(CompressionLevel::fast())
+    ///     .set_worker_count(NonZeroUsize::new(4).unwrap())
+    ///     .build_with_writer(Vec::new());
+    ///
+    /// let writer = bam::Writer::from(inner);
+    /// ```
     pub fn new(writer: W) -> Self {
         Self {
             inner: bgzf::Writer::new(writer),
@@ -210,7 +228,19 @@ where
     }
 }
 
-fn write_reference<W>(writer: &mut W, reference_sequence: &ReferenceSequence) -> io::Result<()>
+impl<W> From<bgzf::Writer<W>> for Writer<W>
+where
+    W: Write,
+{
+    fn from(inner: bgzf::Writer<W>) -> Self {
+        Self { inner }
+    }
+}
+
+pub(crate) fn write_reference<W>(
+    writer: &mut W,
+    reference_sequence: &ReferenceSequence,
+) -> io::Result<()>
 where
     W: Write,
 {
@@ -425,6 +455,7 @@ mod tests {
             .set_data(Data::from(vec![
                 SamField::new(SamTag::ReadGroup, SamValue::String(String::from("rg0"))),
                 SamField::new(SamTag::AlignmentHitCount, SamValue::Int32(1)),
+                SamField::new(SamTag::AlignmentScore, SamValue::UInt32(u32::MAX)),
             ]))
             .build();
 
@@ -452,6 +483,11 @@ mod tests {
             Some(Field::new(SamTag::AlignmentHitCount, Value::Int32(1)))
         );
 
+        assert_eq!(
+            fields.next().transpose()?,
+            Some(Field::new(SamTag::AlignmentScore, Value::UInt32(u32::MAX)))
+        );
+
         assert!(fields.next().is_none());
 
         Ok(())
@@ -232,6 +232,9 @@ fn calculate_data_len(data: &Data) -> usize {
             Value::Int32(_) => {
                 len += mem::size_of::<i32>();
             }
+            Value::UInt32(_) => {
+                len += mem::size_of::<u32>();
+            }
             Value::Float(_) => {
                 len += mem::size_of::<f32>();
             }
@@ -275,7 +278,14 @@ where
         writer.write_all(field.tag().as_ref().as_bytes())?;
 
         let value = field.value();
-        writer.write_u8(char::from(value.ty()) as u8)?;
+
+        // A SAM 32-bit unsigned integer is written as a BAM 32-bit unsigned integer (`I`).
+        let ty = match value {
+            Value::UInt32(_) => b'I',
+            _ => char::from(value.ty()) as u8,
+        };
+
+        writer.write_u8(ty)?;
 
         if let Some(subtype) = value.subtype() {
             writer.write_u8(char::from(subtype) as u8)?;
@@ -288,6 +298,9 @@ where
             Value::Int32(n) => {
                 writer.write_i32::<LittleEndian>(*n)?;
             }
+            Value::UInt32(n) => {
+                writer.write_u32::<LittleEndian>(*n)?;
+            }
             Value::Float(n) => {
                 writer.write_f32::<LittleEndian>(*n)?;
             }
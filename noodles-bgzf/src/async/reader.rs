@@ -0,0 +1,315 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{future, ready};
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, ReadBuf};
+
+use crate::{
+    reader::{inflate_frame, read_block_size},
+    Block, VirtualPosition, BGZF_HEADER_SIZE,
+};
+
+enum State {
+    Header(usize),
+    Body(usize, usize),
+    Eof,
+}
+
+/// An async BGZF reader.
+///
+/// This implements [`tokio::io::AsyncRead`], consuming compressed data and emitting uncompressed
+/// data. Like [`bgzf::Reader`], it tracks the virtual position of the stream and can seek to
+/// virtual positions when the underlying stream is seekable.
+///
+/// [`tokio::io::AsyncRead`]: https://docs.rs/tokio/latest/tokio/io/trait.AsyncRead.html
+/// [`bgzf::Reader`]: ../struct.Reader.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> io::Result<()> {
+/// use noodles_bgzf as bgzf;
+/// use tokio::{fs::File, io::AsyncReadExt};
+///
+/// let mut reader = File::open("data.gz").await.map(bgzf::AsyncReader::new)?;
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Reader<R> {
+    inner: R,
+    position: u64,
+    frame: Vec<u8>,
+    state: State,
+    block: Block,
+}
+
+impl<R> Reader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates an async BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            position: 0,
+            frame: Vec::new(),
+            state: State::Header(0),
+            block: Block::default(),
+        }
+    }
+
+    /// Returns the current position of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// assert_eq!(reader.position(), 0);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the current virtual position of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::AsyncReader::new(&data[..]);
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn virtual_position(&self) -> VirtualPosition {
+        self.block.virtual_position()
+    }
+
+    // Reads and decompresses the next block, returning its compressed size. A size of 0 means the
+    // underlying stream reached EOF.
+    fn poll_read_block(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        loop {
+            match self.state {
+                State::Header(filled) => {
+                    self.frame.resize(BGZF_HEADER_SIZE, Default::default());
+
+                    let n = ready!(poll_read_into(
+                        &mut self.inner,
+                        cx,
+                        &mut self.frame[filled..]
+                    ))?;
+
+                    if n == 0 {
+                        if filled == 0 {
+                            self.state = State::Eof;
+                            continue;
+                        }
+
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "unexpected EOF in block header",
+                        )));
+                    }
+
+                    let filled = filled + n;
+
+                    self.state = if filled < BGZF_HEADER_SIZE {
+                        State::Header(filled)
+                    } else {
+                        let block_size = read_block_size(&self.frame)?;
+                        self.frame.resize(block_size, Default::default());
+                        State::Body(block_size, filled)
+                    };
+                }
+                State::Body(block_size, filled) => {
+                    if filled < block_size {
+                        let n = ready!(poll_read_into(
+                            &mut self.inner,
+                            cx,
+                            &mut self.frame[filled..]
+                        ))?;
+
+                        if n == 0 {
+                            return Poll::Ready(Err(io::Error::from(io::ErrorKind::UnexpectedEof)));
+                        }
+
+                        self.state = State::Body(block_size, filled + n);
+                        continue;
+                    }
+
+                    self.block.set_position(self.position);
+                    self.block.set_len(block_size as u64);
+
+                    let udata = self.block.data_mut();
+                    let udata_buf = udata.get_mut();
+                    udata_buf.clear();
+
+                    inflate_frame(&self.frame, udata_buf, false)?;
+
+                    udata.set_position(0);
+
+                    self.position += block_size as u64;
+                    self.state = State::Header(0);
+
+                    return Poll::Ready(Ok(block_size));
+                }
+                State::Eof => return Poll::Ready(Ok(0)),
+            }
+        }
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Seeks the stream to the given virtual position.
+    ///
+    /// The underlying stream's cursor is first moved the the compressed position. A block is read,
+    /// decompressed, and has its own cursor moved to the uncompressed position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bgzf as bgzf;
+    /// let mut reader = bgzf::AsyncReader::new(Cursor::new(Vec::new()));
+    /// reader.seek(bgzf::VirtualPosition::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn seek(&mut self, pos: VirtualPosition) -> io::Result<VirtualPosition> {
+        let (compressed_pos, uncompressed_pos) = pos.into();
+
+        self.inner.seek(io::SeekFrom::Start(compressed_pos)).await?;
+        self.position = compressed_pos;
+        self.state = State::Header(0);
+
+        future::poll_fn(|cx| self.poll_read_block(cx)).await?;
+
+        self.block
+            .data_mut()
+            .set_position(u64::from(uncompressed_pos));
+
+        Ok(pos)
+    }
+}
+
+impl<R> AsyncRead for Reader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            let data = this.block.data_mut();
+            let pos = data.position() as usize;
+            let src = &data.get_ref()[pos.min(data.get_ref().len())..];
+
+            if !src.is_empty() {
+                let n = src.len().min(buf.remaining());
+                buf.put_slice(&src[..n]);
+                data.set_position((pos + n) as u64);
+                return Poll::Ready(Ok(()));
+            }
+
+            if ready!(this.poll_read_block(cx))? == 0 {
+                return Poll::Ready(Ok(()));
+            }
+        }
+    }
+}
+
+fn poll_read_into<R>(
+    reader: &mut R,
+    cx: &mut Context<'_>,
+    buf: &mut [u8],
+) -> Poll<io::Result<usize>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buf = ReadBuf::new(buf);
+    ready!(Pin::new(reader).poll_read(cx, &mut buf))?;
+    Poll::Ready(Ok(buf.filled().len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    fn build_data() -> io::Result<Vec<u8>> {
+        let mut writer = crate::Writer::new(Vec::new());
+
+        for i in 0..4 {
+            writer.write_all(format!("noodles-bgzf-{}", i).as_bytes())?;
+            writer.flush()?;
+        }
+
+        writer.finish()
+    }
+
+    #[tokio::test]
+    async fn test_read_to_end() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+
+        assert_eq!(
+            buf,
+            b"noodles-bgzf-0noodles-bgzf-1noodles-bgzf-2noodles-bgzf-3"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seek() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = crate::Reader::new(&data[..]);
+        let mut buf = [0; 17];
+        std::io::Read::read_exact(&mut reader, &mut buf)?;
+        let virtual_position = reader.virtual_position();
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.read_to_end(&mut Vec::new()).await?;
+
+        reader.seek(virtual_position).await?;
+        assert_eq!(reader.virtual_position(), virtual_position);
+
+        let mut buf = [0; 11];
+        reader.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"dles-bgzf-1");
+
+        Ok(())
+    }
+}
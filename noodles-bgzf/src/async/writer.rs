@@ -0,0 +1,220 @@
+use std::{
+    cmp, io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use flate2::Compression;
+use futures::ready;
+use tokio::io::AsyncWrite;
+
+use crate::{
+    writer::{deflate_block, CompressionLevel, MAX_BGZF_BLOCK_SIZE},
+    BGZF_EOF,
+};
+
+/// An async BGZF writer.
+///
+/// This implements [`tokio::io::AsyncWrite`], consuming uncompressed data and emitting compressed
+/// data.
+///
+/// Shutting down the writer (e.g., [`tokio::io::AsyncWriteExt::shutdown`]) flushes any remaining
+/// data and appends the final BGZF EOF block.
+///
+/// [`tokio::io::AsyncWrite`]: https://docs.rs/tokio/latest/tokio/io/trait.AsyncWrite.html
+/// [`tokio::io::AsyncWriteExt::shutdown`]: https://docs.rs/tokio/latest/tokio/io/trait.AsyncWriteExt.html#method.shutdown
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> io::Result<()> {
+/// use noodles_bgzf as bgzf;
+/// use tokio::io::AsyncWriteExt;
+///
+/// let mut writer = bgzf::AsyncWriter::new(Vec::new());
+/// writer.write_all(b"noodles-bgzf").await?;
+/// writer.shutdown().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Writer<W> {
+    inner: W,
+    buf: Vec<u8>,
+    frame: Vec<u8>,
+    frame_position: usize,
+    compression_level: Compression,
+    is_eof_written: bool,
+}
+
+impl<W> Writer<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async BGZF writer with a default compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::AsyncWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::with_compression_level(inner, CompressionLevel::default())
+    }
+
+    /// Creates an async BGZF writer with the given compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    /// let writer = bgzf::AsyncWriter::with_compression_level(Vec::new(), CompressionLevel::best());
+    /// ```
+    pub fn with_compression_level(inner: W, compression_level: CompressionLevel) -> Self {
+        Self {
+            inner,
+            buf: Vec::with_capacity(MAX_BGZF_BLOCK_SIZE),
+            frame: Vec::new(),
+            frame_position: 0,
+            compression_level: Compression::from(compression_level),
+            is_eof_written: false,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// The writer is expected to be shut down before it is unwrapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::AsyncWriter::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    // Writes the remaining bytes of the current frame to the underlying writer.
+    fn poll_write_frame(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.frame_position < self.frame.len() {
+            let buf = &self.frame[self.frame_position..];
+
+            match ready!(Pin::new(&mut self.inner).poll_write(cx, buf))? {
+                0 => return Poll::Ready(Err(io::Error::from(io::ErrorKind::WriteZero))),
+                n => self.frame_position += n,
+            }
+        }
+
+        self.frame.clear();
+        self.frame_position = 0;
+
+        Poll::Ready(Ok(()))
+    }
+
+    // Compresses the buffered data as a block and writes it to the underlying writer.
+    fn poll_flush_block(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // A previously compressed block is written first.
+        ready!(self.poll_write_frame(cx))?;
+
+        if !self.buf.is_empty() {
+            deflate_block(&self.buf, self.compression_level, &mut self.frame)?;
+            self.buf.clear();
+        }
+
+        self.poll_write_frame(cx)
+    }
+}
+
+impl<W> AsyncWrite for Writer<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if this.buf.len() >= MAX_BGZF_BLOCK_SIZE {
+            ready!(this.poll_flush_block(cx))?;
+        }
+
+        let n = cmp::min(MAX_BGZF_BLOCK_SIZE - this.buf.len(), buf.len());
+        this.buf.extend_from_slice(&buf[..n]);
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_flush_block(cx))?;
+
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_flush_block(cx))?;
+
+        if !this.is_eof_written {
+            this.frame.extend_from_slice(BGZF_EOF);
+            this.is_eof_written = true;
+        }
+
+        ready!(this.poll_write_frame(cx))?;
+
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write() -> io::Result<()> {
+        let data: Vec<_> = (0..2 * MAX_BGZF_BLOCK_SIZE + 8)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data).await?;
+        writer.shutdown().await?;
+
+        let compressed_data = writer.into_inner();
+        let eof_start = compressed_data.len() - BGZF_EOF.len();
+        assert_eq!(&compressed_data[eof_start..], BGZF_EOF);
+
+        let mut reader = crate::Reader::new(&compressed_data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        assert_eq!(buf, data);
+
+        Ok(())
+    }
+}
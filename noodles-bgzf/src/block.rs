@@ -44,7 +44,7 @@ impl Block {
     }
 
     /// Returns whether the cursor is at the end of the uncompressed data.
-    fn is_eof(&self) -> bool {
+    pub fn is_eof(&self) -> bool {
         let len = self.data.get_ref().len() as u64;
         self.data.position() >= len
     }
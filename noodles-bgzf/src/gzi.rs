@@ -0,0 +1,69 @@
+//! gzip index (GZI) and fields.
+//!
+//! A gzip index (GZI) is a list of block offsets in a BGZF file. Each entry pairs the position of
+//! a block in the compressed stream with its position in the uncompressed stream, which allows
+//! seeking to an uncompressed position, e.g., random access into a bgzipped FASTA.
+//!
+//! The first block always starts at (0, 0) and is not listed in the index.
+//!
+//! # Examples
+//!
+//! ## Reading a gzip index
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_bgzf::gzi;
+//! let index = gzi::read("data.gz.gzi")?;
+//! # Ok::<(), io::Error>(())
+//! ```
+
+mod index;
+mod reader;
+mod writer;
+
+pub use self::{index::Index, reader::Reader, writer::Writer};
+
+use std::{fs::File, io, path::Path};
+
+/// Reads the entire contents of a gzip index.
+///
+/// This is a convenience function and is equivalent to opening the file at the given path and
+/// reading the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::read("data.gz.gzi")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn read<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(Reader::new)?;
+    reader.read_index()
+}
+
+/// Writes a gzip index to a file.
+///
+/// This is a convenience function and is equivalent to creating a file at the given path and
+/// writing the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::Index::default();
+/// gzi::write("data.gz.gzi", &index)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn write<P>(dst: P, index: &Index) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut writer = File::create(dst).map(Writer::new)?;
+    writer.write_index(index)
+}
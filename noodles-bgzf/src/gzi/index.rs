@@ -0,0 +1,82 @@
+/// A gzip index.
+///
+/// The index is a list of (compressed position, uncompressed position) pairs, one for the start of
+/// each block after the first, sorted by position.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Index(Vec<(u64, u64)>);
+
+impl Index {
+    /// Creates a gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let index = gzi::Index::new(vec![(4668, 21294)]);
+    /// ```
+    pub fn new(entries: Vec<(u64, u64)>) -> Self {
+        Self(entries)
+    }
+
+    /// Returns the list of (compressed position, uncompressed position) pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let index = gzi::Index::new(vec![(4668, 21294)]);
+    /// assert_eq!(index.entries(), [(4668, 21294)]);
+    /// ```
+    pub fn entries(&self) -> &[(u64, u64)] {
+        &self.0
+    }
+
+    /// Finds the block that contains the given uncompressed position.
+    ///
+    /// This returns the (compressed position, uncompressed position) pair of the start of the
+    /// block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let index = gzi::Index::new(vec![(4668, 21294), (9444, 42588)]);
+    ///
+    /// assert_eq!(index.query(0), (0, 0));
+    /// assert_eq!(index.query(21293), (0, 0));
+    /// assert_eq!(index.query(21294), (4668, 21294));
+    /// assert_eq!(index.query(50000), (9444, 42588));
+    /// ```
+    pub fn query(&self, pos: u64) -> (u64, u64) {
+        let i = self.0.partition_point(|&(_, u)| u <= pos);
+
+        if i == 0 {
+            (0, 0)
+        } else {
+            self.0[i - 1]
+        }
+    }
+
+    pub(crate) fn push(&mut self, entry: (u64, u64)) {
+        self.0.push(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let index = Index::default();
+        assert_eq!(index.query(0), (0, 0));
+        assert_eq!(index.query(8), (0, 0));
+
+        let index = Index::new(vec![(4668, 21294), (9444, 42588), (14217, 63882)]);
+        assert_eq!(index.query(42587), (4668, 21294));
+        assert_eq!(index.query(42588), (9444, 42588));
+        assert_eq!(index.query(63882), (14217, 63882));
+        assert_eq!(index.query(u64::MAX), (14217, 63882));
+    }
+}
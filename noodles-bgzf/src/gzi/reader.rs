@@ -0,0 +1,93 @@
+use std::io::{self, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::Index;
+
+/// A gzip index (GZI) reader.
+///
+/// A gzip index is a little-endian 64-bit count of entries, followed by that many pairs of
+/// little-endian 64-bit compressed and uncompressed positions.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bgzf::gzi;
+/// let mut reader = File::open("data.gz.gzi").map(gzi::Reader::new)?;
+/// let index = reader.read_index()?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a gzip index reader.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf::gzi;
+    /// let reader = File::open("data.gz.gzi").map(gzi::Reader::new)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Reads a gzip index.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf::gzi;
+    /// let mut reader = File::open("data.gz.gzi").map(gzi::Reader::new)?;
+    /// let index = reader.read_index()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_index(&mut self) -> io::Result<Index> {
+        let len = self.inner.read_u64::<LittleEndian>()?;
+        let mut entries = Vec::new();
+
+        for _ in 0..len {
+            let compressed_pos = self.inner.read_u64::<LittleEndian>()?;
+            let uncompressed_pos = self.inner.read_u64::<LittleEndian>()?;
+            entries.push((compressed_pos, uncompressed_pos));
+        }
+
+        Ok(Index::new(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_index() -> io::Result<()> {
+        let data = [
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // number_entries = 2
+            0x3c, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 4668
+            0x2e, 0x53, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 21294
+            0xe4, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 9444
+            0x5c, 0xa6, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 42588
+        ];
+
+        let mut reader = Reader::new(&data[..]);
+        let actual = reader.read_index()?;
+
+        let expected = Index::new(vec![(4668, 21294), (9444, 42588)]);
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
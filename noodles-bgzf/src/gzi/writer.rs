@@ -0,0 +1,108 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::Index;
+
+/// A gzip index (GZI) writer.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bgzf::gzi;
+///
+/// let index = gzi::Index::default();
+///
+/// let mut writer = File::create("data.gz.gzi").map(gzi::Writer::new)?;
+/// writer.write_index(&index)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a gzip index writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes a gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let mut writer = gzi::Writer::new(Vec::new());
+    ///
+    /// let index = gzi::Index::default();
+    /// writer.write_index(&index)?;
+    ///
+    /// assert_eq!(writer.get_ref(), &[0, 0, 0, 0, 0, 0, 0, 0]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_index(&mut self, index: &Index) -> io::Result<()> {
+        let entries = index.entries();
+
+        let len = entries.len() as u64;
+        self.inner.write_u64::<LittleEndian>(len)?;
+
+        for &(compressed_pos, uncompressed_pos) in entries {
+            self.inner.write_u64::<LittleEndian>(compressed_pos)?;
+            self.inner.write_u64::<LittleEndian>(uncompressed_pos)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_index() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+
+        let index = Index::new(vec![(4668, 21294), (9444, 42588)]);
+        writer.write_index(&index)?;
+
+        let expected = [
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // number_entries = 2
+            0x3c, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 4668
+            0x2e, 0x53, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 21294
+            0xe4, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 9444
+            0x5c, 0xa6, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 42588
+        ];
+
+        assert_eq!(writer.get_ref(), &expected);
+
+        Ok(())
+    }
+}
@@ -35,15 +35,46 @@
 //! writer.write_all(b"noodles-bgzf")?;
 //! # Ok::<(), io::Error>(())
 //! ```
+//!
+//! ## Write a BGZF file with a compression level
+//!
+//! ```no_run
+//! # use std::{fs::File, io::{self, Write}};
+//! use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+//!
+//! let mut writer = File::create("data.gz").map(|f| {
+//!     bgzf::writer::Builder::default()
+//!         .set_compression_level(CompressionLevel::best())
+//!         .build_with_writer(f)
+//! })?;
+//!
+//! writer.write_all(b"noodles-bgzf")?;
+//! # Ok::<(), io::Error>(())
+//! ```
+//!
+//! # Features
+//!
+//!   * `async`: Enables async I/O using [Tokio], i.e., `AsyncReader` and `AsyncWriter`.
+//!   * `zlib`: Compresses and decompresses DEFLATE streams using zlib instead of the pure Rust
+//!     backend of `flate2`.
+//!
+//! [Tokio]: https://tokio.rs/
+
+#[cfg(feature = "async")]
+mod r#async;
 
 mod block;
 mod gz;
-mod reader;
+pub mod gzi;
+pub mod reader;
 pub mod virtual_position;
-mod writer;
+pub mod writer;
 
 pub use self::{reader::Reader, virtual_position::VirtualPosition, writer::Writer};
 
+#[cfg(feature = "async")]
+pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
+
 use self::block::Block;
 
 // XLEN (2)
@@ -54,6 +85,12 @@ const BGZF_XLEN: usize = 6;
 
 pub(crate) const BGZF_HEADER_SIZE: usize = gz::HEADER_SIZE + GZIP_XLEN_SIZE + BGZF_XLEN;
 
+// Sequence Alignment/Map Format Specification § 4.1.2 (accessed 2020-04-15)
+pub(crate) static BGZF_EOF: &[u8] = &[
+    0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
+    0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
 #[cfg(test)]
 mod tests {
     use std::io::{self, Read, Write};
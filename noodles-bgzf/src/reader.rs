@@ -1,18 +1,41 @@
-use std::io::{self, Read, Seek, SeekFrom};
+//! BGZF reader.
+
+mod builder;
+mod inflater;
+
+pub use self::builder::Builder;
+
+use std::{
+    convert::TryFrom,
+    io::{self, BufRead, Read, Seek, SeekFrom},
+    num::NonZeroUsize,
+};
 
 use byteorder::{ByteOrder, LittleEndian};
-use flate2::read::DeflateDecoder;
+use flate2::{read::DeflateDecoder, Crc};
 
-use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
+use self::inflater::Inflater;
+use super::{gz, gzi, Block, VirtualPosition, BGZF_EOF, BGZF_HEADER_SIZE};
 
 /// A BGZF reader.
 ///
 /// Due to the static structure of a BGZF block, gzip headers are mostly discarded. CRC32
-/// validation is also disabled when decompressing data.
+/// validation is disabled by default when decompressing data but can be enabled using
+/// [`bgzf::reader::Builder::set_verify_checksums`].
+///
+/// [`bgzf::reader::Builder::set_verify_checksums`]: reader/struct.Builder.html#method.set_verify_checksums
 ///
 /// This implements [`std::io::Read`], consuming compressed data and emitting uncompressed data.
+/// It also implements [`std::io::BufRead`] using the uncompressed data of the current block as its
+/// buffer, which allows line-based formats to be read while tracking exact virtual positions.
+///
+/// By default, blocks are decompressed serially on the calling thread. A reader created with
+/// [`with_worker_count`] instead reads ahead compressed blocks and decompresses them on a pool of
+/// worker threads.
 ///
 /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+/// [`std::io::BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+/// [`with_worker_count`]: #method.with_worker_count
 ///
 /// # Examples
 ///
@@ -27,8 +50,11 @@ use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
 pub struct Reader<R> {
     inner: R,
     position: u64,
-    cdata: Vec<u8>,
+    buf: Vec<u8>,
     block: Block,
+    inflater: Option<Inflater>,
+    is_inner_eof: bool,
+    verify_checksums: bool,
 }
 
 impl<R> Reader<R>
@@ -45,14 +71,46 @@ where
     /// let reader = bgzf::Reader::new(&data[..]);
     /// ```
     pub fn new(inner: R) -> Self {
+        Builder::default().build_with_reader(inner)
+    }
+
+    fn with_options(inner: R, worker_count: Option<NonZeroUsize>, verify_checksums: bool) -> Self {
         Self {
             inner,
             position: 0,
-            cdata: Vec::new(),
+            buf: Vec::new(),
             block: Block::default(),
+            inflater: worker_count.map(|n| Inflater::new(n, verify_checksums)),
+            is_inner_eof: false,
+            verify_checksums,
         }
     }
 
+    /// Creates a BGZF reader that decompresses blocks using a pool of worker threads.
+    ///
+    /// Up to twice the number of workers of compressed blocks are read ahead from the underlying
+    /// stream. The uncompressed data is still emitted in stream order, and both
+    /// [`virtual_position`] and [`seek`] behave the same as with a reader created by [`new`].
+    ///
+    /// [`virtual_position`]: #method.virtual_position
+    /// [`seek`]: #method.seek
+    /// [`new`]: #method.new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let reader = bgzf::Reader::with_worker_count(worker_count, &data[..]);
+    /// ```
+    pub fn with_worker_count(worker_count: NonZeroUsize, inner: R) -> Self {
+        Builder::default()
+            .set_worker_count(worker_count)
+            .build_with_reader(inner)
+    }
+
     /// Returns the current position of the stream.
     ///
     /// # Examples
@@ -80,6 +138,61 @@ where
     pub fn virtual_position(&self) -> VirtualPosition {
         self.block.virtual_position()
     }
+
+    fn read_block(&mut self) -> io::Result<usize> {
+        let inflater = match self.inflater.as_mut() {
+            Some(inflater) => inflater,
+            None => {
+                let block_size = read_block(
+                    &mut self.inner,
+                    &mut self.buf,
+                    &mut self.block,
+                    self.verify_checksums,
+                )?;
+
+                if block_size > 0 {
+                    self.block.set_position(self.position);
+                    self.position += block_size as u64;
+                }
+
+                return Ok(block_size);
+            }
+        };
+
+        let mut next_position = inflater.end_position().unwrap_or(self.position);
+
+        while !self.is_inner_eof && !inflater.is_full() {
+            let mut frame = Vec::new();
+
+            match read_frame(&mut self.inner, &mut frame)? {
+                0 => self.is_inner_eof = true,
+                n => {
+                    inflater.submit(next_position, frame)?;
+                    next_position += n as u64;
+                }
+            }
+        }
+
+        let pending_block = match inflater.next_block() {
+            Some(pending_block) => pending_block,
+            None => return Ok(0),
+        };
+
+        let position = pending_block.position();
+        let block_size = pending_block.len();
+        let data = pending_block.wait()?;
+
+        self.block.set_position(position);
+        self.block.set_len(block_size);
+
+        let udata = self.block.data_mut();
+        *udata.get_mut() = data;
+        udata.set_position(0);
+
+        self.position = position + block_size;
+
+        Ok(block_size as usize)
+    }
 }
 
 impl<R> Reader<R>
@@ -104,10 +217,15 @@ where
     pub fn seek(&mut self, pos: VirtualPosition) -> io::Result<VirtualPosition> {
         let (compressed_pos, uncompressed_pos) = pos.into();
 
+        if let Some(inflater) = self.inflater.as_mut() {
+            inflater.clear();
+        }
+
         self.inner.seek(SeekFrom::Start(compressed_pos))?;
         self.position = compressed_pos;
+        self.is_inner_eof = false;
 
-        read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+        self.read_block()?;
 
         self.block
             .data_mut()
@@ -115,6 +233,78 @@ where
 
         Ok(pos)
     }
+
+    /// Seeks the stream to the given uncompressed position.
+    ///
+    /// The gzip index is used to find the block that contains the uncompressed position, which is
+    /// then converted to a virtual position and seeked to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let mut reader = File::open("data.gz").map(bgzf::Reader::new)?;
+    /// let index = gzi::read("data.gz.gzi")?;
+    ///
+    /// reader.seek_uncompressed(&index, 21)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek_uncompressed(&mut self, index: &gzi::Index, pos: u64) -> io::Result<u64> {
+        let (compressed_pos, block_uncompressed_pos) = index.query(pos);
+
+        let uncompressed_pos = u16::try_from(pos - block_uncompressed_pos)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let virtual_position = VirtualPosition::try_from((compressed_pos, uncompressed_pos))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.seek(virtual_position)?;
+
+        Ok(pos)
+    }
+
+    /// Returns whether the stream ends with a BGZF EOF marker.
+    ///
+    /// The EOF marker is an empty block written at the end of a complete BGZF stream. Its absence
+    /// typically means the stream was truncated. This is similar to `samtools quickcheck`.
+    ///
+    /// The position of the stream is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles-bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bgzf::Reader::new(Cursor::new(&data));
+    /// assert!(reader.has_eof_marker()?);
+    ///
+    /// let mut reader = bgzf::Reader::new(Cursor::new(&data[..data.len() - 1]));
+    /// assert!(!reader.has_eof_marker()?);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn has_eof_marker(&mut self) -> io::Result<bool> {
+        let pos = self.inner.stream_position()?;
+        let len = self.inner.seek(SeekFrom::End(0))?;
+
+        let result = if len < BGZF_EOF.len() as u64 {
+            Ok(false)
+        } else {
+            let mut buf = vec![0; BGZF_EOF.len()];
+            self.inner.seek(SeekFrom::Start(len - buf.len() as u64))?;
+            self.inner.read_exact(&mut buf).map(|_| buf == BGZF_EOF)
+        };
+
+        self.inner.seek(SeekFrom::Start(pos))?;
+
+        result
+    }
 }
 
 impl<R> Read for Reader<R>
@@ -123,13 +313,9 @@ where
 {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         match self.block.data_mut().read(buf) {
-            Ok(0) => match read_block(&mut self.inner, &mut self.cdata, &mut self.block) {
+            Ok(0) => match self.read_block() {
                 Ok(0) => Ok(0),
-                Ok(bs) => {
-                    self.block.set_position(self.position);
-                    self.position += bs as u64;
-                    Err(io::Error::from(io::ErrorKind::Interrupted))
-                }
+                Ok(_) => Err(io::Error::from(io::ErrorKind::Interrupted)),
                 Err(e) => Err(e),
             },
             Ok(n) => Ok(n),
@@ -138,28 +324,131 @@ where
     }
 }
 
-fn read_block_size<R>(reader: &mut R) -> io::Result<u16>
+impl<R> BufRead for Reader<R>
 where
     R: Read,
 {
-    let mut header = [0; BGZF_HEADER_SIZE];
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Blocks with no uncompressed data, e.g., the EOF marker, are skipped.
+        while self.block.is_eof() {
+            if self.read_block()? == 0 {
+                break;
+            }
+        }
+
+        self.block.data_mut().fill_buf()
+    }
 
-    if reader.read_exact(&mut header).is_err() {
-        return Ok(0);
+    fn consume(&mut self, amt: usize) {
+        self.block.data_mut().consume(amt);
     }
+}
+
+fn read_frame<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<usize>
+where
+    R: Read,
+{
+    buf.resize(BGZF_HEADER_SIZE, Default::default());
 
-    let bsize = &header[16..18];
+    match read_exact_or_eof(reader, buf)? {
+        0 => return Ok(0),
+        BGZF_HEADER_SIZE => {}
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected EOF in block header",
+            ))
+        }
+    }
 
+    let block_size = read_block_size(buf)?;
+
+    buf.resize(block_size, Default::default());
+    reader.read_exact(&mut buf[BGZF_HEADER_SIZE..])?;
+
+    Ok(block_size)
+}
+
+pub(crate) fn read_block_size(header: &[u8]) -> io::Result<usize> {
     // Add 1 because BSIZE is "total Block SIZE minus 1".
-    Ok(LittleEndian::read_u16(bsize) + 1)
+    let block_size = usize::from(LittleEndian::read_u16(&header[16..18])) + 1;
+
+    if block_size < BGZF_HEADER_SIZE + gz::TRAILER_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid block size",
+        ));
+    }
+
+    Ok(block_size)
 }
 
-fn read_trailer<R>(reader: &mut R) -> io::Result<()>
+// Reads until the buffer is full or the stream reaches EOF, returning the number of bytes read.
+fn read_exact_or_eof<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize>
 where
     R: Read,
 {
-    let mut trailer = [0; gz::TRAILER_SIZE];
-    reader.read_exact(&mut trailer)
+    let mut len = 0;
+
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(len)
+}
+
+pub(crate) fn inflate_frame(
+    frame: &[u8],
+    udata: &mut Vec<u8>,
+    verify_checksums: bool,
+) -> io::Result<usize> {
+    let cdata_end = frame.len() - gz::TRAILER_SIZE;
+    let cdata = &frame[BGZF_HEADER_SIZE..cdata_end];
+
+    let n = inflate_data(cdata, udata)?;
+
+    if verify_checksums {
+        verify_trailer(&frame[cdata_end..], udata)?;
+    }
+
+    Ok(n)
+}
+
+fn verify_trailer(trailer: &[u8], udata: &[u8]) -> io::Result<()> {
+    let expected_checksum = LittleEndian::read_u32(&trailer[0..4]);
+    let expected_len = LittleEndian::read_u32(&trailer[4..8]);
+
+    if udata.len() as u64 != u64::from(expected_len) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "block uncompressed size mismatch: expected {}, got {}",
+                expected_len,
+                udata.len()
+            ),
+        ));
+    }
+
+    let mut crc = Crc::new();
+    crc.update(udata);
+    let actual_checksum = crc.sum();
+
+    if actual_checksum != expected_checksum {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "block checksum mismatch: expected {:08x}, got {:08x}",
+                expected_checksum, actual_checksum
+            ),
+        ));
+    }
+
+    Ok(())
 }
 
 fn inflate_data<R>(reader: R, writer: &mut Vec<u8>) -> io::Result<usize>
@@ -170,31 +459,196 @@ where
     decoder.read_to_end(writer)
 }
 
-fn read_block<R>(reader: &mut R, cdata: &mut Vec<u8>, block: &mut Block) -> io::Result<usize>
+fn read_block<R>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    block: &mut Block,
+    verify_checksums: bool,
+) -> io::Result<usize>
 where
     R: Read,
 {
-    let block_size = match read_block_size(reader).map(usize::from) {
-        Ok(0) => return Ok(0),
-        Ok(bs) => bs,
-        Err(e) => return Err(e),
+    let block_size = match read_frame(reader, buf)? {
+        0 => return Ok(0),
+        n => n,
     };
 
-    let cdata_len = block_size - BGZF_HEADER_SIZE - gz::TRAILER_SIZE;
-    cdata.resize(cdata_len, Default::default());
-    reader.read_exact(cdata)?;
-
-    read_trailer(reader)?;
-
     block.set_len(block_size as u64);
 
     let udata = block.data_mut();
     let udata_buf = udata.get_mut();
     udata_buf.clear();
 
-    inflate_data(&cdata[..], udata_buf)?;
+    inflate_frame(buf, udata_buf, verify_checksums)?;
 
     udata.set_position(0);
 
     Ok(block_size)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use crate::Writer;
+
+    use super::*;
+
+    fn build_data() -> io::Result<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+
+        for i in 0..8 {
+            writer.write_all(format!("noodles-bgzf-{}", i).as_bytes())?;
+            writer.flush()?;
+        }
+
+        writer.finish()
+    }
+
+    #[test]
+    fn test_read_with_worker_count() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = Reader::new(&data[..]);
+        let mut expected = Vec::new();
+        reader.read_to_end(&mut expected)?;
+
+        let worker_count = NonZeroUsize::new(2).unwrap();
+        let mut reader = Reader::with_worker_count(worker_count, &data[..]);
+        let mut actual = Vec::new();
+        reader.read_to_end(&mut actual)?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_with_worker_count() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let mut buf = [0; 14];
+        reader.read_exact(&mut buf)?;
+        reader.read_exact(&mut buf[..3])?;
+        let virtual_position = reader.virtual_position();
+
+        let worker_count = NonZeroUsize::new(2).unwrap();
+        let mut reader = Reader::with_worker_count(worker_count, Cursor::new(&data));
+        reader.read_to_end(&mut Vec::new())?;
+
+        reader.seek(virtual_position)?;
+        assert_eq!(reader.virtual_position(), virtual_position);
+
+        let mut buf = [0; 11];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"dles-bgzf-1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_line() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles\nbg")?;
+        writer.flush()?;
+        writer.write_all(b"zf\n")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(&data[..]);
+        let mut buf = String::new();
+
+        reader.read_line(&mut buf)?;
+        assert_eq!(buf, "noodles\n");
+        assert_eq!(reader.virtual_position(), VirtualPosition::from(8));
+
+        buf.clear();
+        reader.read_line(&mut buf)?;
+        assert_eq!(buf, "bgzf\n");
+
+        buf.clear();
+        assert_eq!(reader.read_line(&mut buf)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_checksum_verification() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = Builder::default()
+            .set_verify_checksums(true)
+            .build_with_reader(&data[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf.len(), 112);
+
+        // Corrupts the CRC32 of the first block.
+        let mut data = data;
+        let block_size = usize::from(LittleEndian::read_u16(&data[16..18])) + 1;
+        data[block_size - gz::TRAILER_SIZE] ^= 0xff;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_to_end(&mut Vec::new())?;
+
+        let mut reader = Builder::default()
+            .set_verify_checksums(true)
+            .build_with_reader(&data[..]);
+        let result = reader.read_to_end(&mut Vec::new());
+        assert!(matches!(result, Err(ref e) if e.kind() == io::ErrorKind::InvalidData));
+
+        let worker_count = NonZeroUsize::new(2).unwrap();
+        let mut reader = Builder::default()
+            .set_worker_count(worker_count)
+            .set_verify_checksums(true)
+            .build_with_reader(&data[..]);
+        let result = reader.read_to_end(&mut Vec::new());
+        assert!(matches!(result, Err(ref e) if e.kind() == io::ErrorKind::InvalidData));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_truncated_block_header() -> io::Result<()> {
+        let data = build_data()?;
+
+        let mut reader = Reader::new(&data[..BGZF_HEADER_SIZE - 1]);
+        let result = reader.read_to_end(&mut Vec::new());
+        assert!(matches!(result, Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_uncompressed() -> io::Result<()> {
+        let mut writer = crate::writer::Builder::default()
+            .set_build_gzi_index(true)
+            .build_with_writer(Vec::new());
+
+        for i in 0..8 {
+            writer.write_all(format!("noodles-bgzf-{}", i).as_bytes())?;
+            writer.flush()?;
+        }
+
+        writer.try_finish()?;
+
+        let index = writer.gzi_index().cloned().expect("missing gzi index");
+        let data = writer.get_ref();
+
+        let mut reader = Reader::new(Cursor::new(data));
+        let mut buf = [0; 9];
+
+        reader.seek_uncompressed(&index, 3)?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"dles-bgzf");
+
+        reader.seek_uncompressed(&index, 33)?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"es-bgzf-2");
+
+        reader.seek_uncompressed(&index, 112)?;
+        assert_eq!(reader.read(&mut buf)?, 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use std::{io::Read, num::NonZeroUsize};
+
+use super::Reader;
+
+/// A BGZF reader builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    worker_count: Option<NonZeroUsize>,
+    verify_checksums: bool,
+}
+
+impl Builder {
+    /// Creates a BGZF reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::reader::Builder::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of worker threads used to decompress blocks.
+    ///
+    /// By default, blocks are decompressed on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let builder = bgzf::reader::Builder::default().set_worker_count(worker_count);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Sets whether to verify the CRC32 and uncompressed size (ISIZE) of each block.
+    ///
+    /// By default, checksums are not verified. When enabled, a block whose decompressed data does
+    /// not match its gzip trailer results in an [`std::io::ErrorKind::InvalidData`] error.
+    ///
+    /// [`std::io::ErrorKind::InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::reader::Builder::default().set_verify_checksums(true);
+    /// ```
+    pub fn set_verify_checksums(mut self, verify_checksums: bool) -> Self {
+        self.verify_checksums = verify_checksums;
+        self
+    }
+
+    /// Builds a BGZF reader that reads from the given reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = [];
+    /// let reader = bgzf::reader::Builder::default()
+    ///     .set_verify_checksums(true)
+    ///     .build_with_reader(&data[..]);
+    /// ```
+    pub fn build_with_reader<R>(self, inner: R) -> Reader<R>
+    where
+        R: Read,
+    {
+        Reader::with_options(inner, self.worker_count, self.verify_checksums)
+    }
+}
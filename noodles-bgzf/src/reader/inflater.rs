@@ -0,0 +1,142 @@
+use std::{
+    collections::VecDeque,
+    io,
+    num::NonZeroUsize,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+use super::inflate_frame;
+
+type InflateResult = io::Result<Vec<u8>>;
+
+struct Request {
+    frame: Vec<u8>,
+    tx: Sender<InflateResult>,
+}
+
+/// A block that was read from the compressed stream and is being inflated by a worker.
+pub(super) struct PendingBlock {
+    position: u64,
+    len: u64,
+    rx: Receiver<InflateResult>,
+}
+
+impl PendingBlock {
+    /// Returns the position of this block in the compressed stream.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the compressed size of this block.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Blocks until the uncompressed data of this block is available.
+    pub fn wait(self) -> InflateResult {
+        self.rx.recv().unwrap_or_else(|_| {
+            Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "inflater worker disconnected",
+            ))
+        })
+    }
+}
+
+/// A pool of workers that inflate BGZF blocks.
+///
+/// Blocks are submitted in stream order, and their results are queued in the same order,
+/// regardless of which worker finishes first.
+pub(super) struct Inflater {
+    request_tx: Option<Sender<Request>>,
+    handles: Vec<JoinHandle<()>>,
+    queue: VecDeque<PendingBlock>,
+    capacity: usize,
+}
+
+impl Inflater {
+    /// Creates a pool of `worker_count` inflater workers.
+    ///
+    /// Up to twice the number of workers are read ahead.
+    pub fn new(worker_count: NonZeroUsize, verify_checksums: bool) -> Self {
+        let worker_count = worker_count.get();
+        let (request_tx, request_rx) = crossbeam_channel::bounded(worker_count);
+
+        let handles = (0..worker_count)
+            .map(|_| spawn_worker(request_rx.clone(), verify_checksums))
+            .collect();
+
+        Self {
+            request_tx: Some(request_tx),
+            handles,
+            queue: VecDeque::with_capacity(worker_count * 2),
+            capacity: worker_count * 2,
+        }
+    }
+
+    /// Returns whether the read-ahead queue is full.
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= self.capacity
+    }
+
+    /// Returns the compressed position after the last block that was read ahead.
+    pub fn end_position(&self) -> Option<u64> {
+        self.queue
+            .back()
+            .map(|pending_block| pending_block.position + pending_block.len)
+    }
+
+    /// Submits a raw BGZF block frame, starting at the given compressed position, to be inflated.
+    pub fn submit(&mut self, position: u64, frame: Vec<u8>) -> io::Result<()> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let len = frame.len() as u64;
+
+        self.request_tx
+            .as_ref()
+            .expect("missing request sender")
+            .send(Request { frame, tx })
+            .map_err(|_| {
+                io::Error::new(io::ErrorKind::BrokenPipe, "inflater workers disconnected")
+            })?;
+
+        self.queue.push_back(PendingBlock { position, len, rx });
+
+        Ok(())
+    }
+
+    /// Removes the next block in stream order.
+    pub fn next_block(&mut self) -> Option<PendingBlock> {
+        self.queue.pop_front()
+    }
+
+    /// Discards all blocks that have been read ahead.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+}
+
+impl Drop for Inflater {
+    fn drop(&mut self) {
+        self.queue.clear();
+
+        // Disconnecting the request channel stops the workers.
+        self.request_tx.take();
+
+        for handle in self.handles.drain(..) {
+            handle.join().ok();
+        }
+    }
+}
+
+fn spawn_worker(request_rx: Receiver<Request>, verify_checksums: bool) -> JoinHandle<()> {
+    thread::spawn(move || {
+        while let Ok(Request { frame, tx }) = request_rx.recv() {
+            let mut data = Vec::new();
+            let result = inflate_frame(&frame, &mut data, verify_checksums).map(|_| data);
+            // The receiver is dropped when a block is discarded, e.g., after a seek.
+            tx.send(result).ok();
+        }
+    })
+}
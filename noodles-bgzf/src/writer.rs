@@ -1,14 +1,27 @@
+//! BGZF writer.
+
+mod builder;
+pub mod compression_level;
+mod deflater;
+
+pub use self::{builder::Builder, compression_level::CompressionLevel};
+
 use std::{
     cmp,
     io::{self, Write},
+    mem,
+    num::NonZeroUsize,
 };
 
-use byteorder::{LittleEndian, WriteBytesExt};
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
 use flate2::{write::DeflateEncoder, Compression, Crc};
 
-use super::{gz, BGZF_HEADER_SIZE};
+use self::deflater::Deflater;
+use super::{gz, gzi, BGZF_EOF, BGZF_HEADER_SIZE};
 
-const MAX_BGZF_BLOCK_SIZE: u32 = 65536; // bytes
+// The maximum uncompressed block size leaves room for the deflate stream to be larger than its
+// input, e.g., when data is stored without compression, while still fitting in a block.
+pub(crate) const MAX_BGZF_BLOCK_SIZE: usize = 0xff00; // bytes
 
 const BGZF_FLG: u8 = 0x04; // FEXTRA
 const BGZF_XFL: u8 = 0x00; // none
@@ -18,17 +31,16 @@ const BGZF_SI1: u8 = 0x42;
 const BGZF_SI2: u8 = 0x43;
 const BGZF_SLEN: u16 = 2;
 
-// Sequence Alignment/Map Format Specification § 4.1.2 (accessed 2020-04-15)
-static BGZF_EOF: &[u8] = &[
-    0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
-    0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-];
-
 /// A BZGF writer.
 ///
 /// This implements [`std::io::Write`], consuming uncompressed data and emitting compressed data.
 ///
+/// By default, blocks are compressed serially on the calling thread. A writer created with
+/// [`with_worker_count`] instead hands completed blocks to a pool of worker threads and writes the
+/// compressed blocks to the underlying stream in order.
+///
 /// [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+/// [`with_worker_count`]: #method.with_worker_count
 ///
 /// # Examples
 ///
@@ -48,8 +60,13 @@ where
     W: Write,
 {
     inner: W,
-    encoder: DeflateEncoder<Vec<u8>>,
-    crc: Crc,
+    buf: Vec<u8>,
+    frame: Vec<u8>,
+    compression_level: Compression,
+    deflater: Option<Deflater>,
+    position: u64,
+    uncompressed_position: u64,
+    gzi_index: Option<gzi::Index>,
 }
 
 impl<W> Writer<W>
@@ -58,6 +75,10 @@ where
 {
     /// Creates a writer with a default compression level.
     ///
+    /// Use a [`bgzf::writer::Builder`] to set a different compression level.
+    ///
+    /// [`bgzf::writer::Builder`]: writer/struct.Builder.html
+    ///
     /// # Examples
     ///
     /// ```
@@ -65,15 +86,64 @@ where
     /// let writer = bgzf::Writer::new(Vec::new());
     /// ```
     pub fn new(inner: W) -> Self {
+        Builder::default().build_with_writer(inner)
+    }
+
+    fn with_options(
+        inner: W,
+        compression_level: CompressionLevel,
+        worker_count: Option<NonZeroUsize>,
+        build_gzi_index: bool,
+    ) -> Self {
+        let compression_level = Compression::from(compression_level);
+
         Self {
             inner,
-            encoder: DeflateEncoder::new(Vec::new(), Compression::default()),
-            crc: Crc::new(),
+            buf: Vec::with_capacity(MAX_BGZF_BLOCK_SIZE),
+            frame: Vec::new(),
+            compression_level,
+            deflater: worker_count.map(|n| Deflater::new(n, compression_level)),
+            position: 0,
+            uncompressed_position: 0,
+            gzi_index: if build_gzi_index {
+                Some(gzi::Index::default())
+            } else {
+                None
+            },
         }
     }
 
+    /// Creates a writer that compresses blocks using a pool of worker threads.
+    ///
+    /// Compressed blocks are written to the underlying stream in the same order the uncompressed
+    /// data was written. Up to twice the number of workers of blocks can be pending at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::{io::{self, Write}, num::NonZeroUsize};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let mut writer = bgzf::Writer::with_worker_count(worker_count, Vec::new());
+    /// writer.write_all(b"noodles-bgzf")?;
+    ///
+    /// let data = writer.finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn with_worker_count(worker_count: NonZeroUsize, inner: W) -> Self {
+        Builder::default()
+            .set_worker_count(worker_count)
+            .build_with_writer(inner)
+    }
+
     /// Returns a reference to the underlying writer.
     ///
+    /// When compressing blocks using worker threads, blocks that are still pending are not yet
+    /// written to the underlying writer. Call [`flush`] to write all pending blocks.
+    ///
+    /// [`flush`]: https://doc.rust-lang.org/std/io/trait.Write.html#tymethod.flush
+    ///
     /// # Examples
     ///
     /// ```
@@ -85,16 +155,83 @@ where
         &self.inner
     }
 
+    /// Returns the gzip index built from the blocks written so far.
+    ///
+    /// This is only available if the writer was built with
+    /// [`bgzf::writer::Builder::set_build_gzi_index`]. Blocks that are still buffered or pending
+    /// are not yet indexed, so the index is typically read after calling [`try_finish`].
+    ///
+    /// [`bgzf::writer::Builder::set_build_gzi_index`]: writer/struct.Builder.html#method.set_build_gzi_index
+    /// [`try_finish`]: #method.try_finish
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::writer::Builder::default()
+    ///     .set_build_gzi_index(true)
+    ///     .build_with_writer(Vec::new());
+    ///
+    /// writer.write_all(b"noodles-bgzf")?;
+    /// writer.try_finish()?;
+    ///
+    /// let index = writer.gzi_index().expect("missing gzi index");
+    /// assert_eq!(index.entries().len(), 1);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> Option<&gzi::Index> {
+        self.gzi_index.as_ref()
+    }
+
     fn flush_block(&mut self) -> io::Result<()> {
-        self.encoder.try_finish()?;
-        let data = self.encoder.get_ref();
+        if let Some(deflater) = self.deflater.as_mut() {
+            let frame = if deflater.is_full() {
+                deflater.next_frame().transpose()?
+            } else {
+                None
+            };
+
+            let data = mem::replace(&mut self.buf, Vec::with_capacity(MAX_BGZF_BLOCK_SIZE));
+            deflater.submit(data)?;
+
+            if let Some(frame) = frame {
+                self.write_frame(&frame)?;
+            }
+        } else {
+            let mut frame = mem::take(&mut self.frame);
+            deflate_block(&self.buf, self.compression_level, &mut frame)?;
+            self.write_frame(&frame)?;
+            self.frame = frame;
+            self.buf.clear();
+        }
+
+        Ok(())
+    }
 
-        write_header(&mut self.inner, data.len())?;
-        self.inner.write_all(&data[..])?;
-        write_trailer(&mut self.inner, self.crc.sum(), self.crc.amount())?;
+    fn write_pending_blocks(&mut self) -> io::Result<()> {
+        while let Some(result) = self.deflater.as_mut().and_then(|d| d.next_frame()) {
+            let frame = result?;
+            self.write_frame(&frame)?;
+        }
+
+        Ok(())
+    }
 
-        self.encoder.reset(Vec::new())?;
-        self.crc.reset();
+    fn write_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.inner.write_all(frame)?;
+
+        // ISIZE is the last field of the gzip trailer.
+        let isize_start = frame.len() - 4;
+        let uncompressed_size = LittleEndian::read_u32(&frame[isize_start..]);
+
+        self.position += frame.len() as u64;
+        self.uncompressed_position += u64::from(uncompressed_size);
+
+        if let Some(index) = self.gzi_index.as_mut() {
+            index.push((self.position, self.uncompressed_position));
+        }
 
         Ok(())
     }
@@ -145,32 +282,49 @@ where
     W: Write,
 {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let total_uncompressed_bytes_written = self.crc.amount();
-
-        if total_uncompressed_bytes_written >= MAX_BGZF_BLOCK_SIZE {
-            self.flush()?;
-            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        if self.buf.len() >= MAX_BGZF_BLOCK_SIZE {
+            self.flush_block()?;
         }
 
-        let bytes_to_be_written = cmp::min(
-            (MAX_BGZF_BLOCK_SIZE - total_uncompressed_bytes_written) as usize,
-            buf.len(),
-        );
-        let bytes_written = self.encoder.write(&buf[..bytes_to_be_written])?;
-        self.crc.update(&buf[..bytes_written]);
+        let bytes_written = cmp::min(MAX_BGZF_BLOCK_SIZE - self.buf.len(), buf.len());
+        self.buf.extend_from_slice(&buf[..bytes_written]);
 
         Ok(bytes_written)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        if self.crc.amount() > 0 {
-            self.flush_block()
-        } else {
-            Ok(())
+        if !self.buf.is_empty() {
+            self.flush_block()?;
         }
+
+        self.write_pending_blocks()
     }
 }
 
+pub(crate) fn deflate_block(
+    data: &[u8],
+    compression_level: Compression,
+    frame: &mut Vec<u8>,
+) -> io::Result<()> {
+    frame.clear();
+
+    // The header is written after the compressed data size is known.
+    frame.resize(BGZF_HEADER_SIZE, Default::default());
+
+    let mut encoder = DeflateEncoder::new(frame, compression_level);
+    encoder.write_all(data)?;
+    let frame = encoder.finish()?;
+
+    let cdata_len = frame.len() - BGZF_HEADER_SIZE;
+    write_header(&mut &mut frame[..BGZF_HEADER_SIZE], cdata_len)?;
+
+    let mut crc = Crc::new();
+    crc.update(data);
+    write_trailer(frame, crc.sum(), crc.amount())?;
+
+    Ok(())
+}
+
 fn write_header<W>(writer: &mut W, cdata_len: usize) -> io::Result<()>
 where
     W: Write,
@@ -204,6 +358,10 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::{convert::TryFrom, io::Read};
+
+    use crate::Reader;
+
     use super::*;
 
     #[test]
@@ -218,4 +376,80 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_with_compression_level() -> io::Result<()> {
+        let data: Vec<_> = (0..2 * MAX_BGZF_BLOCK_SIZE)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        for n in 0..=9 {
+            let compression_level = CompressionLevel::try_from(n)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            let mut writer = Builder::default()
+                .set_compression_level(compression_level)
+                .build_with_writer(Vec::new());
+            writer.write_all(&data)?;
+            let compressed_data = writer.finish()?;
+
+            let mut reader = Reader::new(&compressed_data[..]);
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf)?;
+
+            assert_eq!(buf, data);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gzi_index() -> io::Result<()> {
+        let data: Vec<_> = (0..2 * MAX_BGZF_BLOCK_SIZE + 8)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let worker_count = NonZeroUsize::new(2).unwrap();
+        let mut writer = Builder::default()
+            .set_worker_count(worker_count)
+            .set_build_gzi_index(true)
+            .build_with_writer(Vec::new());
+        writer.write_all(&data)?;
+        writer.try_finish()?;
+
+        let index = writer.gzi_index().expect("missing gzi index");
+        let compressed_data = writer.get_ref();
+
+        let entries = index.entries();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].1, MAX_BGZF_BLOCK_SIZE as u64);
+        assert_eq!(entries[1].1, 2 * MAX_BGZF_BLOCK_SIZE as u64);
+        assert_eq!(entries[2].1, data.len() as u64);
+        assert_eq!(
+            entries[2].0,
+            (compressed_data.len() - BGZF_EOF.len()) as u64
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_worker_count() -> io::Result<()> {
+        let data: Vec<_> = (0..4 * MAX_BGZF_BLOCK_SIZE)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data)?;
+        let expected = writer.finish()?;
+
+        let worker_count = NonZeroUsize::new(2).unwrap();
+        let mut writer = Writer::with_worker_count(worker_count, Vec::new());
+        writer.write_all(&data)?;
+        let actual = writer.finish()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,98 @@
+use std::{io::Write, num::NonZeroUsize};
+
+use super::{CompressionLevel, Writer};
+
+/// A BGZF writer builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    compression_level: CompressionLevel,
+    worker_count: Option<NonZeroUsize>,
+    build_gzi_index: bool,
+}
+
+impl Builder {
+    /// Creates a BGZF writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::writer::Builder::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compression level.
+    ///
+    /// By default, the compression level is 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    /// let builder = bgzf::writer::Builder::default().set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Sets the number of worker threads used to compress blocks.
+    ///
+    /// By default, blocks are compressed on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bgzf as bgzf;
+    /// let worker_count = NonZeroUsize::new(4).unwrap();
+    /// let builder = bgzf::writer::Builder::default().set_worker_count(worker_count);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: NonZeroUsize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// By default, no index is built. The index can be retrieved using
+    /// [`bgzf::Writer::gzi_index`].
+    ///
+    /// [`bgzf::Writer::gzi_index`]: ../struct.Writer.html#method.gzi_index
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::writer::Builder::default().set_build_gzi_index(true);
+    /// ```
+    pub fn set_build_gzi_index(mut self, build_gzi_index: bool) -> Self {
+        self.build_gzi_index = build_gzi_index;
+        self
+    }
+
+    /// Builds a BGZF writer that writes to the given writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let writer = bgzf::writer::Builder::default()
+    ///     .set_compression_level(CompressionLevel::fast())
+    ///     .build_with_writer(Vec::new());
+    /// ```
+    pub fn build_with_writer<W>(self, inner: W) -> Writer<W>
+    where
+        W: Write,
+    {
+        Writer::with_options(
+            inner,
+            self.compression_level,
+            self.worker_count,
+            self.build_gzi_index,
+        )
+    }
+}
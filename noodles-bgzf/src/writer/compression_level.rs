@@ -0,0 +1,126 @@
+//! BGZF writer compression level.
+
+use std::{convert::TryFrom, error, fmt};
+
+use flate2::Compression;
+
+const MAX_COMPRESSION_LEVEL: u8 = 9;
+
+/// A BGZF writer compression level.
+///
+/// A compression level ranges from 0 to 9 (inclusive), where 0 stores the data without
+/// compression, 1 is the fastest, and 9 is the best (i.e., smallest output).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CompressionLevel(u8);
+
+impl CompressionLevel {
+    /// Creates a compression level that stores data without compression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// let compression_level = CompressionLevel::none();
+    /// assert_eq!(u8::from(compression_level), 0);
+    /// ```
+    pub fn none() -> Self {
+        Self(0)
+    }
+
+    /// Creates a compression level optimized for speed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// let compression_level = CompressionLevel::fast();
+    /// assert_eq!(u8::from(compression_level), 1);
+    /// ```
+    pub fn fast() -> Self {
+        Self(1)
+    }
+
+    /// Creates a compression level optimized for size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::writer::CompressionLevel;
+    /// let compression_level = CompressionLevel::best();
+    /// assert_eq!(u8::from(compression_level), 9);
+    /// ```
+    pub fn best() -> Self {
+        Self(MAX_COMPRESSION_LEVEL)
+    }
+}
+
+impl Default for CompressionLevel {
+    fn default() -> Self {
+        Self(6)
+    }
+}
+
+/// An error returned when a raw compression level fails to convert.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TryFromU8Error(u8);
+
+impl error::Error for TryFromU8Error {}
+
+impl fmt::Display for TryFromU8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid compression level: expected {{0..={}}}, got {}",
+            MAX_COMPRESSION_LEVEL, self.0
+        )
+    }
+}
+
+impl TryFrom<u8> for CompressionLevel {
+    type Error = TryFromU8Error;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        if n <= MAX_COMPRESSION_LEVEL {
+            Ok(Self(n))
+        } else {
+            Err(TryFromU8Error(n))
+        }
+    }
+}
+
+impl From<CompressionLevel> for u8 {
+    fn from(compression_level: CompressionLevel) -> Self {
+        compression_level.0
+    }
+}
+
+impl From<CompressionLevel> for Compression {
+    fn from(compression_level: CompressionLevel) -> Self {
+        Self::new(u32::from(compression_level.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(CompressionLevel::default(), CompressionLevel(6));
+    }
+
+    #[test]
+    fn test_try_from_u8_for_compression_level() {
+        assert_eq!(CompressionLevel::try_from(0), Ok(CompressionLevel(0)));
+        assert_eq!(CompressionLevel::try_from(5), Ok(CompressionLevel(5)));
+        assert_eq!(CompressionLevel::try_from(9), Ok(CompressionLevel(9)));
+        assert_eq!(CompressionLevel::try_from(10), Err(TryFromU8Error(10)));
+    }
+
+    #[test]
+    fn test_from_compression_level_for_u8() {
+        assert_eq!(u8::from(CompressionLevel::none()), 0);
+        assert_eq!(u8::from(CompressionLevel::fast()), 1);
+        assert_eq!(u8::from(CompressionLevel::best()), 9);
+    }
+}
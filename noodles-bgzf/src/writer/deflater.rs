@@ -0,0 +1,111 @@
+use std::{
+    collections::VecDeque,
+    io,
+    num::NonZeroUsize,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use flate2::Compression;
+
+use super::deflate_block;
+
+type DeflateResult = io::Result<Vec<u8>>;
+
+#[derive(Debug)]
+struct Request {
+    data: Vec<u8>,
+    tx: Sender<DeflateResult>,
+}
+
+/// A pool of workers that deflate BGZF blocks.
+///
+/// Blocks are submitted in stream order, and their compressed frames are returned in the same
+/// order, regardless of which worker finishes first.
+#[derive(Debug)]
+pub(super) struct Deflater {
+    request_tx: Option<Sender<Request>>,
+    handles: Vec<JoinHandle<()>>,
+    queue: VecDeque<Receiver<DeflateResult>>,
+    capacity: usize,
+}
+
+impl Deflater {
+    /// Creates a pool of `worker_count` deflater workers.
+    ///
+    /// Up to twice the number of workers of blocks are queued before they are written.
+    pub fn new(worker_count: NonZeroUsize, compression_level: Compression) -> Self {
+        let worker_count = worker_count.get();
+        let (request_tx, request_rx) = crossbeam_channel::bounded(worker_count);
+
+        let handles = (0..worker_count)
+            .map(|_| spawn_worker(request_rx.clone(), compression_level))
+            .collect();
+
+        Self {
+            request_tx: Some(request_tx),
+            handles,
+            queue: VecDeque::with_capacity(worker_count * 2),
+            capacity: worker_count * 2,
+        }
+    }
+
+    /// Returns whether the queue of blocks being deflated is full.
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= self.capacity
+    }
+
+    /// Submits uncompressed data to be deflated as a single block.
+    pub fn submit(&mut self, data: Vec<u8>) -> io::Result<()> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        self.request_tx
+            .as_ref()
+            .expect("missing request sender")
+            .send(Request { data, tx })
+            .map_err(|_| {
+                io::Error::new(io::ErrorKind::BrokenPipe, "deflater workers disconnected")
+            })?;
+
+        self.queue.push_back(rx);
+
+        Ok(())
+    }
+
+    /// Blocks until the next block in stream order is deflated and returns its frame.
+    ///
+    /// This returns `None` if there are no queued blocks.
+    pub fn next_frame(&mut self) -> Option<DeflateResult> {
+        self.queue.pop_front().map(|rx| {
+            rx.recv().unwrap_or_else(|_| {
+                Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "deflater worker disconnected",
+                ))
+            })
+        })
+    }
+}
+
+impl Drop for Deflater {
+    fn drop(&mut self) {
+        self.queue.clear();
+
+        // Disconnecting the request channel stops the workers.
+        self.request_tx.take();
+
+        for handle in self.handles.drain(..) {
+            handle.join().ok();
+        }
+    }
+}
+
+fn spawn_worker(request_rx: Receiver<Request>, compression_level: Compression) -> JoinHandle<()> {
+    thread::spawn(move || {
+        while let Ok(Request { data, tx }) = request_rx.recv() {
+            let mut frame = Vec::new();
+            let result = deflate_block(&data, compression_level, &mut frame).map(|_| frame);
+            tx.send(result).ok();
+        }
+    })
+}
@@ -0,0 +1,56 @@
+//! Queries a CRAM file with a given region.
+//!
+//! The input CRAM must have an index in the same directory, e.g., `sample.cram.crai`. The
+//! reference sequences are read from the given indexed FASTA, which is used to restore the bases
+//! of each record.
+//!
+//! While the results are not formatted the same, the records printed match the output of `samtools
+//! view <src> <region>`.
+
+use std::{env, fs::File, path::PathBuf, str};
+
+use noodles::Region;
+use noodles_cram::{self as cram, crai, reference_sequence_repository::IndexedFasta};
+use noodles_sam as sam;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args();
+
+    let src = args.nth(1).map(PathBuf::from).expect("missing src");
+    let reference_src = args.next().expect("missing reference src");
+    let raw_region = args.next().expect("missing region");
+
+    let mut reader = File::open(&src).map(cram::Reader::new)?;
+    reader.read_file_definition()?;
+
+    let header: sam::Header = reader.read_file_header()?.parse()?;
+    let reference_sequences = header.reference_sequences();
+
+    let mut reference_sequence_repository = IndexedFasta::open(reference_src)?;
+
+    let index = crai::read(src.with_extension("cram.crai"))?;
+
+    let region = Region::from_str_reference_sequences(&raw_region, reference_sequences)?;
+    let query = reader.query(&header, &mut reference_sequence_repository, &index, &region)?;
+
+    for result in query {
+        let record = result?;
+
+        let read_name = str::from_utf8(record.read_name())?;
+
+        let reference_sequence_id = i32::from(record.reference_sequence_id()) as usize;
+        let (_, reference_sequence) = reference_sequences
+            .get_index(reference_sequence_id)
+            .ok_or("invalid reference sequence id")?;
+
+        println!(
+            "{} ({}:{}-{})",
+            read_name,
+            reference_sequence.name(),
+            record.alignment_start(),
+            record.alignment_end()
+        );
+    }
+
+    Ok(())
+}
@@ -9,7 +9,7 @@ use std::io;
 
 use md5::{Digest, Md5};
 
-use noodles_cram as cram;
+use noodles_cram::{self as cram, reference_sequence_repository::InMemory};
 use noodles_fasta as fasta;
 use noodles_sam::{
     self as sam,
@@ -60,7 +60,7 @@ fn main() -> io::Result<()> {
 
     let header = build_header(&reference_sequence_records);
 
-    let mut writer = cram::Writer::new(handle, reference_sequence_records);
+    let mut writer = cram::Writer::new(handle, InMemory::new(reference_sequence_records));
     writer.write_file_definition()?;
     writer.write_file_header(&header)?;
 
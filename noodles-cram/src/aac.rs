@@ -0,0 +1,582 @@
+//! Adaptive arithmetic coding codec.
+//!
+//! This is the CRAM 3.1 range coder with adaptive order-0 or order-1 models and optional stripe,
+//! pack, and run-length transforms.
+
+mod flags;
+mod model;
+mod range_coder;
+
+pub use self::{
+    flags::Flags,
+    model::Model,
+    range_coder::{RangeDecoder, RangeEncoder},
+};
+
+use std::{
+    borrow::Cow,
+    io::{self, Read, Write},
+};
+
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use bzip2::{read::BzDecoder, write::BzEncoder};
+
+use crate::rans_nx16::{
+    decode_pack, decode_stripe, encode_pack, encode_stripe, read_len, read_pack_meta, write_len,
+};
+
+const RUN_MODEL_COUNT: usize = 258;
+const RUN_SYMBOL_COUNT: usize = 4;
+
+/// Decodes adaptive arithmetic-coded data.
+///
+/// `len` is the uncompressed size and is only used when the stream does not store it.
+pub fn decode<R>(reader: &mut R, mut len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let flags = reader.read_u8().and_then(|b| {
+        Flags::from_bits(b).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid adaptive arithmetic coding flags",
+            )
+        })
+    })?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        len = read_len(reader)?;
+    }
+
+    if flags.contains(Flags::STRIPE) {
+        return decode_stripe(reader, len, |src, len| decode(src, len));
+    }
+
+    let pack_meta = if flags.contains(Flags::PACK) {
+        let (symbols, packed_len) = read_pack_meta(reader)?;
+        let meta = (symbols, len);
+        len = packed_len;
+        Some(meta)
+    } else {
+        None
+    };
+
+    let mut dst = if flags.contains(Flags::CAT) {
+        let mut buf = vec![0; len];
+        reader.read_exact(&mut buf)?;
+        buf
+    } else if flags.contains(Flags::EXT) {
+        let mut decoder = BzDecoder::new(reader);
+        let mut buf = Vec::with_capacity(len);
+        decoder.read_to_end(&mut buf)?;
+        buf
+    } else if flags.contains(Flags::RLE) {
+        if flags.contains(Flags::ORDER) {
+            decode_rle_1(reader, len)?
+        } else {
+            decode_rle_0(reader, len)?
+        }
+    } else if flags.contains(Flags::ORDER) {
+        decode_order_1(reader, len)?
+    } else {
+        decode_order_0(reader, len)?
+    };
+
+    if let Some((symbols, len)) = pack_meta {
+        dst = decode_pack(&dst, &symbols, len)?;
+    }
+
+    Ok(dst)
+}
+
+/// Encodes data using adaptive arithmetic coding.
+///
+/// The pack transform is skipped when it does not apply to the given data, and empty data is
+/// always stored uncompressed.
+pub fn encode(mut flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dst = Vec::new();
+
+    if flags.contains(Flags::STRIPE) {
+        let stream_flags = (flags - Flags::STRIPE) | Flags::NO_SIZE;
+        dst.write_u8((flags & (Flags::STRIPE | Flags::NO_SIZE)).bits())?;
+
+        if !flags.contains(Flags::NO_SIZE) {
+            write_len(&mut dst, src.len())?;
+        }
+
+        encode_stripe(&mut dst, src, |buf| encode(stream_flags, buf))?;
+
+        return Ok(dst);
+    }
+
+    let mut meta = Vec::new();
+    let mut data = Cow::from(src);
+
+    if flags.contains(Flags::PACK) {
+        match encode_pack(&data)? {
+            Some((pack_meta, packed_data)) => {
+                meta.extend(pack_meta);
+                data = Cow::from(packed_data);
+            }
+            None => flags.remove(Flags::PACK),
+        }
+    }
+
+    if data.is_empty() {
+        flags.insert(Flags::CAT);
+    }
+
+    dst.write_u8(flags.bits())?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        write_len(&mut dst, src.len())?;
+    }
+
+    dst.extend(meta);
+
+    if flags.contains(Flags::CAT) {
+        dst.extend(data.iter());
+    } else if flags.contains(Flags::EXT) {
+        let mut encoder = BzEncoder::new(dst, bzip2::Compression::default());
+        encoder.write_all(&data)?;
+        dst = encoder.finish()?;
+    } else if flags.contains(Flags::RLE) {
+        if flags.contains(Flags::ORDER) {
+            encode_rle_1(&mut dst, &data)?;
+        } else {
+            encode_rle_0(&mut dst, &data)?;
+        }
+    } else if flags.contains(Flags::ORDER) {
+        encode_order_1(&mut dst, &data)?;
+    } else {
+        encode_order_0(&mut dst, &data)?;
+    }
+
+    Ok(dst)
+}
+
+fn read_symbol_count<R>(reader: &mut R) -> io::Result<usize>
+where
+    R: Read,
+{
+    match reader.read_u8()? {
+        0 => Ok(256),
+        n => Ok(usize::from(n)),
+    }
+}
+
+fn write_symbol_count(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<usize> {
+    let symbol_count = src.iter().max().map(|&n| usize::from(n) + 1).unwrap_or(1);
+    // 256 symbols overflows to 0.
+    dst.write_u8(symbol_count as u8)?;
+    Ok(symbol_count)
+}
+
+fn decode_order_0<R>(reader: &mut R, len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let symbol_count = read_symbol_count(reader)?;
+
+    let mut model = Model::new(symbol_count);
+    let mut range_decoder = RangeDecoder::new(reader)?;
+
+    let mut dst = vec![0; len];
+
+    for d in &mut dst {
+        *d = model.decode(reader, &mut range_decoder)?;
+    }
+
+    Ok(dst)
+}
+
+fn encode_order_0(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    let symbol_count = write_symbol_count(dst, src)?;
+
+    let mut model = Model::new(symbol_count);
+    let mut range_encoder = RangeEncoder::default();
+
+    for &sym in src {
+        model.encode(dst, &mut range_encoder, sym)?;
+    }
+
+    range_encoder.finish(dst);
+
+    Ok(())
+}
+
+fn decode_order_1<R>(reader: &mut R, len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let symbol_count = read_symbol_count(reader)?;
+
+    let mut models = vec![Model::new(symbol_count); 256];
+    let mut range_decoder = RangeDecoder::new(reader)?;
+
+    let mut dst = vec![0; len];
+    let mut last_sym = 0;
+
+    for d in &mut dst {
+        *d = models[usize::from(last_sym)].decode(reader, &mut range_decoder)?;
+        last_sym = *d;
+    }
+
+    Ok(dst)
+}
+
+fn encode_order_1(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    let symbol_count = write_symbol_count(dst, src)?;
+
+    let mut models = vec![Model::new(symbol_count); 256];
+    let mut range_encoder = RangeEncoder::default();
+
+    let mut last_sym = 0;
+
+    for &sym in src {
+        models[usize::from(last_sym)].encode(dst, &mut range_encoder, sym)?;
+        last_sym = sym;
+    }
+
+    range_encoder.finish(dst);
+
+    Ok(())
+}
+
+fn decode_rle<R>(reader: &mut R, len: usize, order: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let symbol_count = read_symbol_count(reader)?;
+
+    let mut models = vec![Model::new(symbol_count); if order == 0 { 1 } else { 256 }];
+    let mut run_models = vec![Model::new(RUN_SYMBOL_COUNT); RUN_MODEL_COUNT];
+    let mut range_decoder = RangeDecoder::new(reader)?;
+
+    let mut dst = Vec::with_capacity(len);
+    let mut last_sym = 0;
+
+    while dst.len() < len {
+        let ctx = if order == 0 { 0 } else { usize::from(last_sym) };
+        let sym = models[ctx].decode(reader, &mut range_decoder)?;
+        dst.push(sym);
+
+        // A run length is encoded as a sequence of parts, each at most 3. The first part uses
+        // the symbol as its context, the second uses 256, and the rest use 257.
+        let mut run_len = 0;
+        let mut run_ctx = usize::from(sym);
+
+        loop {
+            let part = run_models[run_ctx].decode(reader, &mut range_decoder)?;
+            run_ctx = if run_ctx == usize::from(sym) {
+                256
+            } else {
+                257
+            };
+            run_len += usize::from(part);
+
+            if part != 3 || run_len > len - dst.len() {
+                break;
+            }
+        }
+
+        if run_len > len - dst.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "RLE run exceeds data length",
+            ));
+        }
+
+        dst.resize(dst.len() + run_len, sym);
+        last_sym = sym;
+    }
+
+    Ok(dst)
+}
+
+fn decode_rle_0<R>(reader: &mut R, len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    decode_rle(reader, len, 0)
+}
+
+fn decode_rle_1<R>(reader: &mut R, len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    decode_rle(reader, len, 1)
+}
+
+fn encode_rle(dst: &mut Vec<u8>, src: &[u8], order: usize) -> io::Result<()> {
+    let symbol_count = write_symbol_count(dst, src)?;
+
+    let mut models = vec![Model::new(symbol_count); if order == 0 { 1 } else { 256 }];
+    let mut run_models = vec![Model::new(RUN_SYMBOL_COUNT); RUN_MODEL_COUNT];
+    let mut range_encoder = RangeEncoder::default();
+
+    let mut i = 0;
+    let mut last_sym = 0;
+
+    while i < src.len() {
+        let sym = src[i];
+        let ctx = if order == 0 { 0 } else { usize::from(last_sym) };
+        models[ctx].encode(dst, &mut range_encoder, sym)?;
+
+        let run_len = src[i + 1..].iter().take_while(|&&s| s == sym).count();
+
+        let mut remaining = run_len;
+        let mut run_ctx = usize::from(sym);
+
+        loop {
+            let part = remaining.min(3);
+            run_models[run_ctx].encode(dst, &mut range_encoder, part as u8)?;
+            run_ctx = if run_ctx == usize::from(sym) {
+                256
+            } else {
+                257
+            };
+            remaining -= part;
+
+            if part != 3 {
+                break;
+            }
+        }
+
+        i += run_len + 1;
+        last_sym = sym;
+    }
+
+    range_encoder.finish(dst);
+
+    Ok(())
+}
+
+fn encode_rle_0(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    encode_rle(dst, src, 0)
+}
+
+fn encode_rle_1(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    encode_rle(dst, src, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_data() -> Vec<u8> {
+        (0..1031u32)
+            .map(|i| match i % 11 {
+                0..=4 => b'A',
+                5..=7 => b'C',
+                8 => (i % 256) as u8,
+                _ => b'T',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_with_cat() -> io::Result<()> {
+        let data = [0x20, 0x03, b'n', b'd', b'l'];
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"ndl");
+        Ok(())
+    }
+
+    // The following streams are built independently of `encode`, directly from the format
+    // described in the CRAM codecs specification.
+    #[test]
+    fn test_decode_with_order_0() -> io::Result<()> {
+        // flags, len, max symbol, range coder stream
+        let data = [
+            0x00, 0x0b, 0x73, 0x00, 0xd9, 0xe2, 0x74, 0x43, 0x14, 0xfb, 0x98, 0x56, 0xe4, 0xb3,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_order_1() -> io::Result<()> {
+        // flags (ORDER), len, max symbol, range coder stream
+        let data = [
+            0x01, 0x17, 0x73, 0x00, 0xd9, 0xd8, 0xc6, 0x3b, 0x98, 0x81, 0xbe, 0xd5, 0xda, 0x80,
+            0x1f, 0xa9, 0xbd, 0x77, 0x90, 0xa9, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"abracadabra abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_rle() -> io::Result<()> {
+        // flags (RLE), len, max symbol, range coder stream
+        let data = [
+            0x40, 0x14, 0x55, 0x00, 0xc6, 0xac, 0x8f, 0xc5, 0x9b, 0x84, 0x35, 0xce, 0x27, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"AAAAAAAACGTTTTTTAAAC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_rle_and_order_1() -> io::Result<()> {
+        // flags (RLE | ORDER), len, max symbol, range coder stream
+        let data = [
+            0x41, 0x14, 0x55, 0x00, 0xc6, 0xac, 0x29, 0x2d, 0xbc, 0x47, 0xac, 0xed, 0x5b, 0x92,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"AAAAAAAACGTTTTTTAAAC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_pack() -> io::Result<()> {
+        // flags (PACK), len, symbol count, symbols, packed len, max symbol, range coder stream
+        let data = [
+            0x80, 0x0c, 0x04, 0x41, 0x43, 0x47, 0x54, 0x03, 0xe5, 0x00, 0xff, 0x02, 0x70, 0xac,
+            0x17, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"ACGTTGCAACGT");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_pack_and_rle() -> io::Result<()> {
+        // flags (PACK | RLE), len, symbol count, symbols, packed len, max symbol, range coder stream
+        let data = [
+            0xc0, 0x14, 0x04, 0x41, 0x43, 0x47, 0x54, 0x05, 0x00, 0x00, 0x00, 0x7e, 0x69, 0x5c,
+            0x69, 0xb9, 0x20, 0x00, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"AAAAAAAACGTTTTTTAAAC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_ext() -> io::Result<()> {
+        // flags (EXT), len, bzip2 stream
+        let data = [
+            0x04, 0x0b, 0x42, 0x5a, 0x68, 0x39, 0x31, 0x41, 0x59, 0x26, 0x53, 0x59, 0x23, 0x6d,
+            0x4b, 0xd8, 0x00, 0x00, 0x01, 0x01, 0x80, 0x3c, 0x00, 0x10, 0x00, 0x20, 0x00, 0x30,
+            0xcd, 0x00, 0x94, 0x7a, 0x9e, 0xa5, 0x2c, 0x84, 0x78, 0xbb, 0x92, 0x29, 0xc2, 0x84,
+            0x81, 0x1b, 0x6a, 0x5e, 0xc0,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_stripe() -> io::Result<()> {
+        // flags (STRIPE), len, stream count, compressed lens, 4 order-0 streams (NO_SIZE)
+        let data = [
+            0x08, 0x0b, 0x04, 0x09, 0x09, 0x09, 0x08, 0x10, 0x64, 0x00, 0xfa, 0xe1, 0x3b, 0xfe,
+            0xe2, 0x00, 0x10, 0x73, 0x00, 0xdc, 0x1c, 0x4f, 0xfb, 0x06, 0x00, 0x10, 0x73, 0x00,
+            0xff, 0x7c, 0x02, 0xf1, 0x8b, 0x00, 0x10, 0x62, 0x00, 0xff, 0x96, 0x68, 0x94, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_stripe_and_no_size() -> io::Result<()> {
+        // flags (STRIPE | NO_SIZE), stream count, compressed lens, 4 order-0 streams (NO_SIZE)
+        let data = [
+            0x18, 0x04, 0x09, 0x09, 0x09, 0x08, 0x10, 0x64, 0x00, 0xfa, 0xe1, 0x3b, 0xfe, 0xe2,
+            0x00, 0x10, 0x73, 0x00, 0xdc, 0x1c, 0x4f, 0xfb, 0x06, 0x00, 0x10, 0x73, 0x00, 0xff,
+            0x7c, 0x02, 0xf1, 0x8b, 0x00, 0x10, 0x62, 0x00, 0xff, 0x96, 0x68, 0x94, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 11)?, b"abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_order_1_and_no_size() -> io::Result<()> {
+        // flags (ORDER | NO_SIZE), max symbol, range coder stream
+        let data = [
+            0x11, 0x73, 0x00, 0xd9, 0xd8, 0xc6, 0x3b, 0x98, 0x81, 0xbe, 0xd5, 0xb8, 0x53, 0xe8,
+            0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 11)?, b"abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_invalid_flags() {
+        let data = [0x02, 0x00];
+        let mut reader = &data[..];
+
+        assert!(matches!(
+            decode(&mut reader, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_encode_round_trip() -> io::Result<()> {
+        let mut inputs: Vec<Vec<u8>> = (0..=9).map(|n| b"ACGTNacgt"[..n].to_vec()).collect();
+        inputs.push((0..=255).collect());
+        inputs.push(build_data());
+        inputs.push(b"AAAAAAAAAAAAAAAAACCCCCCCCCGTTTTTTTTTTTTTTA".to_vec());
+
+        let flag_sets = [
+            Flags::empty(),
+            Flags::ORDER,
+            Flags::CAT,
+            Flags::EXT,
+            Flags::PACK,
+            Flags::PACK | Flags::ORDER,
+            Flags::RLE,
+            Flags::RLE | Flags::ORDER,
+            Flags::PACK | Flags::RLE,
+            Flags::STRIPE,
+            Flags::STRIPE | Flags::ORDER | Flags::RLE,
+        ];
+
+        for data in &inputs {
+            for &flags in flag_sets.iter() {
+                let encoded_data = encode(flags, data)?;
+                let mut reader = &encoded_data[..];
+                assert_eq!(&decode(&mut reader, 0)?, data, "flags = {:?}", flags);
+            }
+
+            for &flags in &[Flags::NO_SIZE, Flags::STRIPE | Flags::NO_SIZE] {
+                let encoded_data = encode(flags, data)?;
+                let mut reader = &encoded_data[..];
+                assert_eq!(
+                    &decode(&mut reader, data.len())?,
+                    data,
+                    "flags = {:?}",
+                    flags
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
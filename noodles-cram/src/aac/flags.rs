@@ -0,0 +1,20 @@
+bitflags::bitflags! {
+    /// Adaptive arithmetic coding format flags.
+    #[derive(Default)]
+    pub struct Flags: u8 {
+        /// Use an order-1 context.
+        const ORDER = 0x01;
+        /// Compress the data using bzip2.
+        const EXT = 0x04;
+        /// Split the data into 4 byte-interleaved streams that are each encoded separately.
+        const STRIPE = 0x08;
+        /// The uncompressed size is not stored.
+        const NO_SIZE = 0x10;
+        /// Store the data uncompressed.
+        const CAT = 0x20;
+        /// Encode runs of symbols using separate run-length models.
+        const RLE = 0x40;
+        /// Bit-pack data with at most 16 distinct symbols.
+        const PACK = 0x80;
+    }
+}
@@ -0,0 +1,137 @@
+use std::io::{self, Read};
+
+use super::{RangeDecoder, RangeEncoder};
+
+const MAX_FREQ: u32 = (1 << 16) - 17;
+const STEP: u32 = 16;
+
+/// An adaptive frequency model.
+///
+/// Symbols are kept approximately sorted by frequency, and frequencies are halved when their
+/// total exceeds the maximum.
+#[derive(Clone, Debug)]
+pub struct Model {
+    symbols: Vec<u8>,
+    freqs: Vec<u32>,
+    total_freq: u32,
+}
+
+impl Model {
+    /// Creates a model with the given number of symbols, starting at 0.
+    pub fn new(symbol_count: usize) -> Self {
+        Self {
+            symbols: (0..symbol_count).map(|i| i as u8).collect(),
+            freqs: vec![1; symbol_count],
+            total_freq: symbol_count as u32,
+        }
+    }
+
+    pub fn decode<R>(&mut self, reader: &mut R, range_decoder: &mut RangeDecoder) -> io::Result<u8>
+    where
+        R: Read,
+    {
+        let freq = range_decoder.get_freq(self.total_freq)?;
+
+        let mut cumulative_freq = 0;
+        let mut i = 0;
+
+        while cumulative_freq + self.freqs[i] <= freq {
+            cumulative_freq += self.freqs[i];
+            i += 1;
+        }
+
+        range_decoder.decode(reader, cumulative_freq, self.freqs[i])?;
+
+        let sym = self.symbols[i];
+        self.update(i);
+
+        Ok(sym)
+    }
+
+    pub fn encode(
+        &mut self,
+        dst: &mut Vec<u8>,
+        range_encoder: &mut RangeEncoder,
+        sym: u8,
+    ) -> io::Result<()> {
+        let i =
+            self.symbols.iter().position(|&s| s == sym).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "symbol not in model")
+            })?;
+
+        let cumulative_freq = self.freqs[..i].iter().sum();
+        range_encoder.encode(dst, cumulative_freq, self.freqs[i], self.total_freq);
+
+        self.update(i);
+
+        Ok(())
+    }
+
+    fn update(&mut self, i: usize) {
+        self.freqs[i] += STEP;
+        self.total_freq += STEP;
+
+        if self.total_freq > MAX_FREQ {
+            self.renormalize();
+        }
+
+        if i > 0 && self.freqs[i] > self.freqs[i - 1] {
+            self.freqs.swap(i, i - 1);
+            self.symbols.swap(i, i - 1);
+        }
+    }
+
+    fn renormalize(&mut self) {
+        self.total_freq = 0;
+
+        for f in &mut self.freqs {
+            *f -= *f >> 1;
+            self.total_freq += *f;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> io::Result<()> {
+        let data: Vec<u8> = (0..4096u32)
+            .map(|i| (i % 3) as u8 * (i % 5) as u8)
+            .collect();
+
+        let mut model = Model::new(16);
+        let mut range_encoder = RangeEncoder::default();
+        let mut buf = Vec::new();
+
+        for &sym in &data {
+            model.encode(&mut buf, &mut range_encoder, sym)?;
+        }
+
+        range_encoder.finish(&mut buf);
+
+        let mut reader = &buf[..];
+        let mut model = Model::new(16);
+        let mut range_decoder = RangeDecoder::new(&mut reader)?;
+
+        for &expected in &data {
+            let actual = model.decode(&mut reader, &mut range_decoder)?;
+            assert_eq!(actual, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_unknown_symbol() {
+        let mut model = Model::new(2);
+        let mut range_encoder = RangeEncoder::default();
+        let mut buf = Vec::new();
+
+        assert!(matches!(
+            model.encode(&mut buf, &mut range_encoder, 2),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
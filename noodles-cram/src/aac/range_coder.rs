@@ -0,0 +1,120 @@
+use std::io::{self, Read};
+
+use byteorder::ReadBytesExt;
+
+const TOP: u32 = 1 << 24;
+const THRESHOLD: u64 = 0xff00_0000;
+
+/// A range decoder.
+#[derive(Debug)]
+pub struct RangeDecoder {
+    range: u32,
+    code: u32,
+}
+
+impl RangeDecoder {
+    /// Starts decoding by reading the initial code.
+    pub fn new<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: Read,
+    {
+        let mut code = 0;
+
+        // The first byte is always the carry of the encoder and is shifted out.
+        for _ in 0..5 {
+            code = (code << 8) | u32::from(reader.read_u8()?);
+        }
+
+        Ok(Self {
+            range: u32::MAX,
+            code,
+        })
+    }
+
+    pub fn get_freq(&mut self, total_freq: u32) -> io::Result<u32> {
+        self.range /= total_freq;
+        let freq = self.code / self.range;
+
+        if freq >= total_freq {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid range coder frequency",
+            ));
+        }
+
+        Ok(freq)
+    }
+
+    pub fn decode<R>(&mut self, reader: &mut R, cumulative_freq: u32, freq: u32) -> io::Result<()>
+    where
+        R: Read,
+    {
+        self.code -= cumulative_freq * self.range;
+        self.range *= freq;
+
+        while self.range < TOP {
+            self.code = (self.code << 8) | u32::from(reader.read_u8()?);
+            self.range <<= 8;
+        }
+
+        Ok(())
+    }
+}
+
+/// A range encoder.
+#[derive(Debug)]
+pub struct RangeEncoder {
+    low: u64,
+    range: u32,
+    cache: u8,
+    cache_size: u64,
+}
+
+impl Default for RangeEncoder {
+    fn default() -> Self {
+        Self {
+            low: 0,
+            range: u32::MAX,
+            cache: 0,
+            cache_size: 1,
+        }
+    }
+}
+
+impl RangeEncoder {
+    pub fn encode(&mut self, dst: &mut Vec<u8>, cumulative_freq: u32, freq: u32, total_freq: u32) {
+        self.range /= total_freq;
+        self.low += u64::from(cumulative_freq) * u64::from(self.range);
+        self.range *= freq;
+
+        while self.range < TOP {
+            self.range <<= 8;
+            self.shift_low(dst);
+        }
+    }
+
+    pub fn finish(mut self, dst: &mut Vec<u8>) {
+        for _ in 0..5 {
+            self.shift_low(dst);
+        }
+    }
+
+    fn shift_low(&mut self, dst: &mut Vec<u8>) {
+        let carry = (self.low >> 32) as u8;
+
+        if self.low < THRESHOLD || carry != 0 {
+            let mut b = self.cache;
+
+            for _ in 0..self.cache_size {
+                dst.push(b.wrapping_add(carry));
+                b = 0xff;
+            }
+
+            self.cache_size = 0;
+            self.cache = (self.low >> 24) as u8;
+        }
+
+        self.cache_size += 1;
+        self.low = (self.low & 0x00ff_ffff) << 8;
+    }
+}
@@ -42,6 +42,10 @@ impl Container {
             .set_data(buf)
             .build();
 
+        // Landmarks are the offsets of the first block of each slice relative to the start of the
+        // container data, i.e., directly after the compression header block.
+        let mut landmark = block.len() as Itf8;
+
         let mut blocks = vec![block];
         let mut landmarks = Vec::new();
 
@@ -79,6 +83,8 @@ impl Container {
 
             container_record_count += slice_header.record_count();
 
+            landmarks.push(landmark);
+
             let mut slice_len = 0;
 
             let mut slice_header_buf = Vec::new();
@@ -101,9 +107,7 @@ impl Container {
                 slice_len += external_block.len() as Itf8;
             }
 
-            let last_landmark = landmarks.last().unwrap_or(&0);
-            let landmark = last_landmark + slice_len;
-            landmarks.push(landmark);
+            landmark += slice_len;
         }
 
         let len = blocks.iter().map(|b| b.len() as i32).sum();
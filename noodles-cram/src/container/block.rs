@@ -17,8 +17,10 @@ use flate2::read::GzDecoder;
 use xz2::read::XzDecoder;
 
 use crate::{
+    aac, fqzcomp, name_tokenizer,
     num::{itf8, Itf8},
     rans::rans_decode,
+    rans_nx16,
 };
 
 // § 9 End of file container (2020-06-22)
@@ -96,6 +98,22 @@ impl Block {
                 let mut buf = self.data();
                 rans_decode(&mut buf).map(Cow::from)
             }
+            CompressionMethod::RansNx16 => {
+                let mut buf = self.data();
+                rans_nx16::decode(&mut buf, self.uncompressed_len as usize).map(Cow::from)
+            }
+            CompressionMethod::AdaptiveArithmeticCoding => {
+                let mut buf = self.data();
+                aac::decode(&mut buf, self.uncompressed_len as usize).map(Cow::from)
+            }
+            CompressionMethod::Fqzcomp => {
+                let mut buf = self.data();
+                fqzcomp::decode(&mut buf).map(Cow::from)
+            }
+            CompressionMethod::NameTokenizer => {
+                let mut buf = self.data();
+                name_tokenizer::decode(&mut buf).map(Cow::from)
+            }
         }
     }
 
@@ -133,4 +151,31 @@ mod tests {
 
         assert_eq!(block.len(), 16);
     }
+
+    #[test]
+    fn test_decompressed_data() -> io::Result<()> {
+        let data = b"r0\0r1\0r2\0".to_vec();
+
+        for &compression_method in &[
+            CompressionMethod::None,
+            CompressionMethod::Gzip,
+            CompressionMethod::Bzip2,
+            CompressionMethod::Lzma,
+            CompressionMethod::Rans,
+            CompressionMethod::RansNx16,
+            CompressionMethod::AdaptiveArithmeticCoding,
+            CompressionMethod::Fqzcomp,
+            CompressionMethod::NameTokenizer,
+        ] {
+            let block = Block::builder()
+                .set_content_type(ContentType::ExternalData)
+                .compress_and_set_data(data.clone(), compression_method)?
+                .build();
+
+            assert_eq!(block.compression_method(), compression_method);
+            assert_eq!(&block.decompressed_data()?[..], &data[..]);
+        }
+
+        Ok(())
+    }
 }
@@ -1,6 +1,11 @@
 use std::io::{self, Write};
 
-use crate::num::Itf8;
+use crate::{
+    aac, fqzcomp, name_tokenizer,
+    num::Itf8,
+    rans::{rans_encode, Context},
+    rans_nx16,
+};
 
 use super::{Block, CompressionMethod, ContentType};
 
@@ -55,6 +60,11 @@ impl Builder {
     ///
     /// This sets the compression method, the uncompressed size to the length of the given data,
     /// and the data to the compressed output of the given data.
+    ///
+    /// rANS, rANS Nx16, and adaptive arithmetic coding compression use whichever of the order-0 or
+    /// order-1 contexts gives the smaller output. fqzcomp treats the data as the quality scores
+    /// of a single record, and name tokenization expects the data to be a list of NUL-terminated
+    /// names.
     pub fn compress_and_set_data(
         mut self,
         data: Vec<u8>,
@@ -80,10 +90,24 @@ impl Builder {
                 encoder.write_all(&data)?;
                 encoder.finish()?
             }
-            _ => unimplemented!(
-                "compress_and_set_data: unhandled compression method: {:?}",
-                compression_method
-            ),
+            CompressionMethod::Rans => {
+                let order_0_data = rans_encode(Context::Order0, &data)?;
+                let order_1_data = rans_encode(Context::Order1, &data)?;
+
+                smaller(order_0_data, order_1_data)
+            }
+            CompressionMethod::RansNx16 => {
+                let order_0_data = rans_nx16::encode(rans_nx16::Flags::empty(), &data)?;
+                let order_1_data = rans_nx16::encode(rans_nx16::Flags::ORDER, &data)?;
+                smaller(order_0_data, order_1_data)
+            }
+            CompressionMethod::AdaptiveArithmeticCoding => {
+                let order_0_data = aac::encode(aac::Flags::empty(), &data)?;
+                let order_1_data = aac::encode(aac::Flags::ORDER, &data)?;
+                smaller(order_0_data, order_1_data)
+            }
+            CompressionMethod::Fqzcomp => fqzcomp::encode(&[data.len()], &data)?,
+            CompressionMethod::NameTokenizer => name_tokenizer::encode(&data)?,
         };
 
         Ok(self)
@@ -100,3 +124,11 @@ impl Builder {
         }
     }
 }
+
+fn smaller(a: Vec<u8>, b: Vec<u8>) -> Vec<u8> {
+    if b.len() < a.len() {
+        b
+    } else {
+        a
+    }
+}
@@ -1,14 +1,17 @@
-use crate::Record;
+use crate::{num::Itf8, Record};
 
 use super::{
     data_series_encoding_map::DataSeriesEncodingMap, preservation_map, tag_encoding_map,
-    CompressionHeader,
+    CompressionHeader, Encoding,
 };
 
 #[derive(Debug, Default)]
 pub struct Builder {
     preservation_map_builder: preservation_map::Builder,
     tag_encoding_map_builder: tag_encoding_map::Builder,
+    bam_bit_flags_range: ValueRange,
+    cram_bit_flags_range: ValueRange,
+    read_lengths_range: ValueRange,
 }
 
 impl Builder {
@@ -16,12 +19,100 @@ impl Builder {
         self.preservation_map_builder
             .update(reference_sequence, record);
         self.tag_encoding_map_builder.update(record);
+
+        self.bam_bit_flags_range
+            .update(i32::from(u16::from(record.bam_flags())));
+        self.cram_bit_flags_range
+            .update(i32::from(u8::from(record.flags())));
+        self.read_lengths_range.update(record.read_length());
     }
 
     pub fn build(self) -> CompressionHeader {
         let preservation_map = self.preservation_map_builder.build();
-        let data_series_encoding_map = DataSeriesEncodingMap::default();
+
+        let mut data_series_encoding_map_builder = DataSeriesEncodingMap::builder();
+
+        if let Some(encoding) = self.bam_bit_flags_range.encoding() {
+            data_series_encoding_map_builder =
+                data_series_encoding_map_builder.set_bam_bit_flags_encoding(encoding);
+        }
+
+        if let Some(encoding) = self.cram_bit_flags_range.encoding() {
+            data_series_encoding_map_builder =
+                data_series_encoding_map_builder.set_cram_bit_flags_encoding(encoding);
+        }
+
+        if let Some(encoding) = self.read_lengths_range.encoding() {
+            data_series_encoding_map_builder =
+                data_series_encoding_map_builder.set_read_lengths_encoding(encoding);
+        }
+
+        let data_series_encoding_map = data_series_encoding_map_builder.build();
         let tag_encoding_map = self.tag_encoding_map_builder.build();
+
         CompressionHeader::new(preservation_map, data_series_encoding_map, tag_encoding_map)
     }
 }
+
+// The range of values of an integer data series in the core data block.
+#[derive(Debug, Default)]
+struct ValueRange(Option<(Itf8, Itf8)>);
+
+impl ValueRange {
+    fn update(&mut self, value: Itf8) {
+        self.0 = match self.0 {
+            Some((min, max)) => Some((min.min(value), max.max(value))),
+            None => Some((value, value)),
+        };
+    }
+
+    // A data series with a single value is encoded with a single-symbol Huffman code, which writes
+    // no bits. Otherwise, values are beta-coded using the fewest bits that fit the range.
+    fn encoding(&self) -> Option<Encoding> {
+        let (min, max) = self.0?;
+
+        if min == max {
+            return Some(Encoding::Huffman(vec![min], vec![0]));
+        }
+
+        let offset = min.checked_neg()?;
+        let range = max.checked_add(offset)?;
+        let len = 32 - range.leading_zeros() as Itf8;
+
+        Some(Encoding::Beta(offset, len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_range_encoding() {
+        let mut range = ValueRange::default();
+        assert_eq!(range.encoding(), None);
+
+        range.update(151);
+        assert_eq!(
+            range.encoding(),
+            Some(Encoding::Huffman(vec![151], vec![0]))
+        );
+
+        range.update(151);
+        assert_eq!(
+            range.encoding(),
+            Some(Encoding::Huffman(vec![151], vec![0]))
+        );
+
+        range.update(101);
+        assert_eq!(range.encoding(), Some(Encoding::Beta(-101, 6)));
+
+        range.update(0);
+        assert_eq!(range.encoding(), Some(Encoding::Beta(0, 8)));
+
+        let mut range = ValueRange::default();
+        range.update(i32::MIN);
+        range.update(0);
+        assert_eq!(range.encoding(), None);
+    }
+}
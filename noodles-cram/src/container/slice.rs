@@ -9,11 +9,25 @@ use std::{
     io::{self, Cursor},
 };
 
-use noodles_sam as sam;
-
-use crate::{reader, BitReader, Record};
+use md5::{Digest, Md5};
+use noodles_bam::{
+    self as bam,
+    record::data::field::{value::Type, Value},
+};
+use noodles_sam::{self as sam, header::reference_sequence::Md5Checksum};
+
+use crate::{
+    reader,
+    record::{
+        resolve::{resolve_bases, resolve_quality_scores},
+        tag::Key,
+        Tag,
+    },
+    reference_sequence_repository::get_by_id,
+    BitReader, Record, ReferenceSequenceRepository,
+};
 
-use super::{Block, CompressionHeader};
+use super::{Block, CompressionHeader, ReferenceSequenceId};
 
 #[derive(Debug)]
 pub struct Slice {
@@ -47,6 +61,10 @@ impl Slice {
         &self.external_blocks
     }
 
+    /// Reads the records in this slice.
+    ///
+    /// Attached mates are linked, i.e., their mate reference sequence IDs, mate alignment starts,
+    /// mate flags, and template sizes are set.
     pub fn records(&self, compression_header: &CompressionHeader) -> io::Result<Vec<Record>> {
         let core_data_reader = self
             .core_data_block
@@ -80,62 +98,273 @@ impl Slice {
             records.push(record);
         }
 
+        resolve_mates(&mut records)?;
+
         Ok(records)
     }
 
-    pub fn resolve_mates(&self, records: Vec<Record>) -> Vec<Record> {
-        use std::cell::RefCell;
-
-        let mut mate_indices = vec![None; records.len()];
-
-        for (i, record) in records.iter().enumerate() {
-            let flags = record.flags();
-
-            if flags.has_mate_downstream() {
-                let distance_to_next_fragment = record.distance_to_next_fragment() as usize;
-                let mate_index = i + distance_to_next_fragment + 1;
-                mate_indices[i] = Some(mate_index);
+    /// Resolves the fields of records read from this slice that are not stored directly.
+    ///
+    /// This restores the bases of mapped records from the reference sequence, the quality scores
+    /// of records that store them as read features, missing read names, and read group (`RG`)
+    /// tags.
+    ///
+    /// The reference sequence of a single reference slice is validated against the slice
+    /// reference MD5 checksum. A mismatch, e.g., from using a different reference build than the
+    /// one used to write the slice, is an error. It is also an error if the reference sequence is
+    /// not in the repository, unless the compression header marks the reference as not required.
+    pub fn resolve_records(
+        &self,
+        header: &sam::Header,
+        reference_sequence_repository: &mut dyn ReferenceSequenceRepository,
+        compression_header: &CompressionHeader,
+        records: &mut [Record],
+    ) -> io::Result<()> {
+        let preservation_map = compression_header.preservation_map();
+        let substitution_matrix = preservation_map.substitution_matrix();
+
+        if let ReferenceSequenceId::Some(id) = self.header.reference_sequence_id() {
+            let reference_sequence = get_by_id(
+                reference_sequence_repository,
+                header.reference_sequences(),
+                id,
+            )?;
+
+            match reference_sequence {
+                Some(sequence) => self.validate_reference_md5(sequence)?,
+                None if preservation_map.reference_required() => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "missing reference sequence",
+                    ));
+                }
+                None => {}
             }
         }
 
-        let records: Vec<_> = records.into_iter().map(RefCell::new).collect();
-
-        for (i, record_cell) in records.iter().enumerate() {
-            if mate_indices[i].is_none() {
-                continue;
+        for record in records {
+            let read_len = record.read_length() as usize;
+
+            if !record.bam_flags().is_unmapped() && !record.flags().decode_sequence_as_unknown() {
+                let reference_sequence = match *record.reference_sequence_id() {
+                    Some(id) => get_by_id(
+                        reference_sequence_repository,
+                        header.reference_sequences(),
+                        id,
+                    )?,
+                    None => None,
+                };
+
+                let reference_sequence = match reference_sequence {
+                    Some(sequence) => sequence,
+                    None if preservation_map.reference_required() => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "missing reference sequence",
+                        ));
+                    }
+                    None => &[],
+                };
+
+                record.bases = resolve_bases(
+                    reference_sequence,
+                    substitution_matrix,
+                    record.features(),
+                    record.alignment_start(),
+                    read_len,
+                )?;
             }
 
-            let mut record = record_cell.borrow_mut();
+            if !record.flags().are_quality_scores_stored_as_array() {
+                record.quality_scores = resolve_quality_scores(record.features(), read_len)?;
+            }
 
             if record.read_name.is_empty() {
-                let read_name = record.id.to_string().into_bytes();
-                record.read_name.extend(read_name);
+                record.read_name = record.id.to_string().into_bytes();
             }
 
-            let mut j = i;
+            if let Some(id) = *record.read_group_id() {
+                let (read_group_id, _) =
+                    header.read_groups().get_index(id as usize).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("invalid read group ID: {}", id),
+                        )
+                    })?;
 
-            while let Some(mate_index) = mate_indices[j] {
-                let mut mate = records[mate_index].borrow_mut();
-                set_mate(&mut record, &mut mate);
-                record = mate;
-                j = mate_index;
+                let key = Key::new([b'R', b'G'], Type::String);
+
+                if !record.tags().iter().any(|tag| tag.key() == key) {
+                    record.add_tag(Tag::new(key, Value::String(read_group_id.clone())));
+                }
             }
+        }
 
-            let mut mate = record_cell.borrow_mut();
-            set_mate(&mut record, &mut mate);
+        Ok(())
+    }
 
-            let template_size = calculate_template_size(&record, &mate);
-            record.template_size = template_size;
-            mate.template_size = -template_size;
+    fn validate_reference_md5(&self, reference_sequence: &[u8]) -> io::Result<()> {
+        let expected = self.header.reference_md5();
+
+        // An unset checksum is not validated.
+        if expected.iter().all(|&b| b == 0) {
+            return Ok(());
         }
 
-        records.into_iter().map(|r| r.into_inner()).collect()
+        let actual = calculate_reference_md5(
+            reference_sequence,
+            self.header.alignment_start(),
+            self.header.alignment_span(),
+        )
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "slice alignment start is out of bounds of the reference sequence",
+            )
+        })?;
+
+        if actual[..] == *expected {
+            Ok(())
+        } else {
+            let mut expected_md5 = [0; 16];
+            expected_md5.copy_from_slice(expected);
+
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "slice reference MD5 mismatch: expected {}, got {}",
+                    Md5Checksum::from(expected_md5),
+                    Md5Checksum::from(actual)
+                ),
+            ))
+        }
     }
 }
 
-fn set_mate(mut record: &mut Record, mate: &mut Record) {
-    let mate_bam_flags = mate.bam_flags();
+// Calculates the MD5 checksum of the region of the reference sequence covered by a slice.
+//
+// Bases are uppercased before hashing. The end of the region is clamped to the end of the reference
+// sequence, e.g., for records that overhang the end of a circular reference sequence. This returns
+// `None` if the start of the region is out of bounds.
+pub(crate) fn calculate_reference_md5(
+    reference_sequence: &[u8],
+    alignment_start: i32,
+    alignment_span: i32,
+) -> Option<[u8; 16]> {
+    let start = usize::try_from(alignment_start - 1).ok()?;
+    let len = usize::try_from(alignment_span).ok()?;
+    let end = start.checked_add(len)?.min(reference_sequence.len());
+    let region = reference_sequence.get(start..end)?;
+
+    let mut hasher = Md5::new();
+    hasher.update(region.to_ascii_uppercase());
+
+    Some(<[u8; 16]>::from(hasher.finalize()))
+}
+
+// Links attached mates, i.e., records with a mate downstream in the same slice, and sets their mate
+// fields and template sizes.
+fn resolve_mates(records: &mut [Record]) -> io::Result<()> {
+    let mut mate_indices = vec![None; records.len()];
+    let mut has_upstream_mate = vec![false; records.len()];
+
+    for (i, record) in records.iter().enumerate() {
+        if !record.flags().has_mate_downstream() {
+            continue;
+        }
+
+        let mate_index = usize::try_from(record.distance_to_next_fragment())
+            .ok()
+            .map(|distance| i + distance + 1)
+            .filter(|&j| j < records.len())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid distance to next fragment: {}",
+                        record.distance_to_next_fragment()
+                    ),
+                )
+            })?;
+
+        if has_upstream_mate[mate_index] {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("record {} has multiple upstream mates", mate_index),
+            ));
+        }
+
+        mate_indices[i] = Some(mate_index);
+        has_upstream_mate[mate_index] = true;
+    }
+
+    for i in 0..records.len() {
+        if has_upstream_mate[i] || mate_indices[i].is_none() {
+            continue;
+        }
+
+        let mut segment_indices = vec![i];
+        let mut j = i;
+
+        while let Some(mate_index) = mate_indices[j] {
+            segment_indices.push(mate_index);
+            j = mate_index;
+        }
+
+        if records[i].read_name.is_empty() {
+            records[i].read_name = records[i].id.to_string().into_bytes();
+        }
+
+        let read_name = records[i].read_name.clone();
+
+        for &j in &segment_indices[1..] {
+            if records[j].read_name.is_empty() {
+                records[j].read_name = read_name.clone();
+            }
+        }
+
+        for (k, &j) in segment_indices.iter().enumerate() {
+            let mate_index = segment_indices[(k + 1) % segment_indices.len()];
+            let mate = &records[mate_index];
 
+            let mate_bam_flags = mate.bam_flags();
+            let mate_reference_sequence_id = mate.reference_sequence_id();
+            let mate_alignment_start = mate.alignment_start();
+
+            set_mate(
+                &mut records[j],
+                mate_bam_flags,
+                mate_reference_sequence_id,
+                mate_alignment_start,
+            );
+        }
+
+        let template_size = calculate_template_size(records, &segment_indices);
+
+        let leftmost_index = segment_indices
+            .iter()
+            .copied()
+            .min_by_key(|&j| records[j].alignment_start())
+            .unwrap_or(i);
+
+        for &j in &segment_indices {
+            records[j].template_size = if j == leftmost_index {
+                template_size
+            } else {
+                -template_size
+            };
+        }
+    }
+
+    Ok(())
+}
+
+fn set_mate(
+    record: &mut Record,
+    mate_bam_flags: sam::record::Flags,
+    mate_reference_sequence_id: bam::record::ReferenceSequenceId,
+    mate_alignment_start: i32,
+) {
     if mate_bam_flags.is_reverse_complemented() {
         record.bam_bit_flags |= sam::record::Flags::MATE_REVERSE_COMPLEMENTED;
     }
@@ -144,17 +373,38 @@ fn set_mate(mut record: &mut Record, mate: &mut Record) {
         record.bam_bit_flags |= sam::record::Flags::MATE_UNMAPPED;
     }
 
-    if mate.read_name().is_empty() {
-        mate.read_name.extend(record.read_name.iter());
+    record.next_fragment_reference_sequence_id = mate_reference_sequence_id;
+    record.next_mate_alignment_start = mate_alignment_start;
+}
+
+// The template size is the span from the leftmost start to the rightmost end of all the segments.
+// It is 0 if any segment is unmapped or the segments are mapped to different reference sequences.
+fn calculate_template_size(records: &[Record], segment_indices: &[usize]) -> i32 {
+    let first_record = &records[segment_indices[0]];
+    let reference_sequence_id = *first_record.reference_sequence_id();
+
+    let is_template_mapped = segment_indices.iter().all(|&i| {
+        let record = &records[i];
+        !record.bam_flags().is_unmapped()
+            && *record.reference_sequence_id() == reference_sequence_id
+    });
+
+    if !is_template_mapped {
+        return 0;
     }
 
-    record.next_fragment_reference_sequence_id = mate.reference_sequence_id();
-    record.next_mate_alignment_start = mate.alignment_start();
-}
+    let start = segment_indices
+        .iter()
+        .map(|&i| records[i].alignment_start())
+        .min()
+        .unwrap_or_default();
+
+    let end = segment_indices
+        .iter()
+        .map(|&i| records[i].alignment_end())
+        .max()
+        .unwrap_or_default();
 
-fn calculate_template_size(record: &Record, mate: &Record) -> i32 {
-    let start = record.alignment_start();
-    let end = mate.alignment_end();
     end - start + 1
 }
 
@@ -184,3 +434,144 @@ impl TryFrom<&[Block]> for Slice {
         Ok(Slice::new(header, core_data_block, external_blocks))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::record::{Feature, Flags};
+
+    use super::*;
+
+    fn build_record(
+        id: i64,
+        flags: Flags,
+        bam_flags: sam::record::Flags,
+        alignment_start: i32,
+        distance_to_next_fragment: i32,
+    ) -> Record {
+        Record::builder()
+            .set_id(id)
+            .set_flags(flags)
+            .set_bam_flags(bam_flags)
+            .set_reference_sequence_id(bam::record::ReferenceSequenceId::from(0))
+            .set_read_length(4)
+            .set_alignment_start(alignment_start)
+            .set_distance_to_next_fragment(distance_to_next_fragment)
+            .build()
+    }
+
+    #[test]
+    fn test_calculate_reference_md5() {
+        let expected = [
+            0x7d, 0x38, 0xbb, 0x5e, 0x5a, 0xcb, 0x31, 0x9c, 0x84, 0xe5, 0xd4, 0x1b, 0xea, 0x1f,
+            0xe6, 0x04,
+        ];
+
+        assert_eq!(calculate_reference_md5(b"ACGTACGT", 2, 4), Some(expected));
+        assert_eq!(calculate_reference_md5(b"acgtacgt", 2, 4), Some(expected));
+        assert!(calculate_reference_md5(b"ACGTACGT", 0, 4).is_none());
+        assert!(calculate_reference_md5(b"ACGTACGT", 10, 4).is_none());
+
+        // The region overhangs the end of the reference sequence and is clamped to "CGT".
+        let expected = [
+            0x13, 0x06, 0xc9, 0x84, 0x98, 0x36, 0x15, 0xe4, 0xb2, 0xc8, 0x63, 0x86, 0x1a, 0xb0,
+            0x57, 0x59,
+        ];
+
+        assert_eq!(calculate_reference_md5(b"ACGTACGT", 6, 4), Some(expected));
+    }
+
+    #[test]
+    fn test_resolve_mates() -> io::Result<()> {
+        let mut records = vec![
+            build_record(
+                1,
+                Flags::HAS_MATE_DOWNSTREAM,
+                sam::record::Flags::PAIRED,
+                5,
+                1,
+            ),
+            build_record(2, Flags::empty(), sam::record::Flags::empty(), 8, 0),
+            build_record(
+                3,
+                Flags::empty(),
+                sam::record::Flags::PAIRED | sam::record::Flags::REVERSE_COMPLEMENTED,
+                13,
+                0,
+            ),
+        ];
+
+        records[2].features = vec![Feature::Deletion(2, 3)];
+
+        resolve_mates(&mut records)?;
+
+        assert_eq!(records[0].read_name(), b"1");
+        assert_eq!(
+            records[0].bam_flags(),
+            sam::record::Flags::PAIRED | sam::record::Flags::MATE_REVERSE_COMPLEMENTED
+        );
+        assert_eq!(*records[0].next_fragment_reference_sequence_id(), Some(0));
+        assert_eq!(records[0].next_mate_alignment_start(), 13);
+        assert_eq!(records[0].template_size(), 15);
+
+        assert!(records[1].read_name().is_empty());
+        assert_eq!(records[1].template_size(), 0);
+
+        assert_eq!(records[2].read_name(), b"1");
+        assert_eq!(
+            records[2].bam_flags(),
+            sam::record::Flags::PAIRED | sam::record::Flags::REVERSE_COMPLEMENTED
+        );
+        assert_eq!(*records[2].next_fragment_reference_sequence_id(), Some(0));
+        assert_eq!(records[2].next_mate_alignment_start(), 5);
+        assert_eq!(records[2].template_size(), -15);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_mates_with_unmapped_mate() -> io::Result<()> {
+        let mut records = vec![
+            build_record(
+                1,
+                Flags::HAS_MATE_DOWNSTREAM,
+                sam::record::Flags::PAIRED,
+                5,
+                0,
+            ),
+            build_record(
+                2,
+                Flags::empty(),
+                sam::record::Flags::PAIRED | sam::record::Flags::UNMAPPED,
+                5,
+                0,
+            ),
+        ];
+
+        resolve_mates(&mut records)?;
+
+        assert_eq!(
+            records[0].bam_flags(),
+            sam::record::Flags::PAIRED | sam::record::Flags::MATE_UNMAPPED
+        );
+        assert_eq!(records[0].template_size(), 0);
+        assert_eq!(records[1].template_size(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_mates_with_invalid_distance_to_next_fragment() {
+        let mut records = vec![build_record(
+            1,
+            Flags::HAS_MATE_DOWNSTREAM,
+            sam::record::Flags::PAIRED,
+            5,
+            0,
+        )];
+
+        assert!(matches!(
+            resolve_mates(&mut records),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
@@ -1,7 +1,6 @@
 use std::{cmp, collections::HashMap, io};
 
-use md5::{Digest, Md5};
-use noodles_fasta as fasta;
+use noodles_sam as sam;
 
 use crate::{
     container::{
@@ -9,16 +8,20 @@ use crate::{
         compression_header::data_series_encoding_map::DataSeries,
         Block, CompressionHeader, ReferenceSequenceId,
     },
-    writer, BitWriter, Record,
+    reference_sequence_repository::get_by_id,
+    writer, BitWriter, Record, ReferenceSequenceRepository,
 };
 
-use super::{Header, Slice};
+use super::{calculate_reference_md5, Header, Slice};
 
 use noodles_bam as bam;
 
 const CORE_DATA_BLOCK_CONTENT_ID: i32 = 0;
 const MAX_RECORD_COUNT: usize = 2560;
 
+const READ_NAMES_BLOCK_CONTENT_ID: i32 = DataSeries::ReadNames as i32 + 1;
+const QUALITY_SCORES_BLOCK_CONTENT_ID: i32 = DataSeries::QualityScores as i32 + 1;
+
 #[derive(Debug, Default)]
 pub struct Builder {
     records: Vec<Record>,
@@ -71,7 +74,8 @@ impl Builder {
 
     pub fn build(
         self,
-        reference_sequences: &[fasta::Record],
+        reference_sequence_repository: &mut dyn ReferenceSequenceRepository,
+        reference_sequences: &sam::header::ReferenceSequences,
         compression_header: &CompressionHeader,
         record_counter: i64,
     ) -> io::Result<Slice> {
@@ -134,7 +138,7 @@ impl Builder {
                 Block::builder()
                     .set_content_type(block::ContentType::ExternalData)
                     .set_content_id(block_content_id)
-                    .compress_and_set_data(buf, CompressionMethod::Gzip)
+                    .compress_and_set_data(buf, external_block_compression_method(block_content_id))
                     .map(|builder| builder.build())
             })
             .collect::<Result<_, _>>()?;
@@ -143,26 +147,29 @@ impl Builder {
             block_content_ids.push(block.content_id());
         }
 
+        let slice_alignment_span = slice_alignment_end - slice_alignment_start + 1;
+
         let reference_md5 = if let ReferenceSequenceId::Some(id) = reference_sequence_id {
-            let reference_sequence = reference_sequences
-                .get(id as usize)
-                .map(|record| record.sequence())
-                .ok_or_else(|| {
-                    io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence")
-                })?;
-
-            let start = (slice_alignment_start - 1) as usize;
-            let end = (slice_alignment_end - 1) as usize;
-
-            let mut hasher = Md5::new();
-            hasher.update(&reference_sequence[start..=end]);
-            <[u8; 16]>::from(hasher.finalize())
+            let reference_sequence =
+                get_by_id(reference_sequence_repository, reference_sequences, id)?.ok_or_else(
+                    || io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence"),
+                )?;
+
+            calculate_reference_md5(
+                reference_sequence,
+                slice_alignment_start,
+                slice_alignment_span,
+            )
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "slice alignment start is out of bounds of the reference sequence",
+                )
+            })?
         } else {
             [0; 16]
         };
 
-        let slice_alignment_span = slice_alignment_end - slice_alignment_start + 1;
-
         let header = Header::builder()
             .set_reference_sequence_id(reference_sequence_id)
             .set_alignment_start(slice_alignment_start)
@@ -178,3 +185,12 @@ impl Builder {
         Ok(Slice::new(header, core_data_block, external_blocks))
     }
 }
+
+// Read names and quality scores are compressed with rANS, similar to htslib. All other external
+// blocks use gzip.
+fn external_block_compression_method(block_content_id: i32) -> CompressionMethod {
+    match block_content_id {
+        READ_NAMES_BLOCK_CONTENT_ID | QUALITY_SCORES_BLOCK_CONTENT_ID => CompressionMethod::Rans,
+        _ => CompressionMethod::Gzip,
+    }
+}
@@ -1,4 +1,69 @@
+//! CRAM index (CRAI) and fields.
+//!
+//! A CRAM index is a gzip-compressed list of tab-delimited records, one for each slice and
+//! reference sequence in the associated CRAM file.
+
 mod reader;
 mod record;
+mod writer;
+
+pub use self::{reader::Reader, record::Record, writer::Writer};
+
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+
+use flate2::read::GzDecoder;
+
+/// A CRAM index.
+pub type Index = Vec<Record>;
+
+/// Reads the entire contents of a CRAM index.
+///
+/// This is a convenience function and is equivalent to opening the file at the given path,
+/// decompressing the stream, and reading the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_cram::crai;
+/// let index = crai::read("sample.cram.crai")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn read<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src)
+        .map(GzDecoder::new)
+        .map(BufReader::new)
+        .map(Reader::new)?;
+
+    reader.read_index()
+}
 
-pub use self::{reader::Reader, record::Record};
+/// Writes a CRAM index to a file.
+///
+/// This is a convenience function and is equivalent to creating the file at the given path and
+/// writing the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_cram::crai;
+/// let index = crai::Index::default();
+/// crai::write("sample.cram.crai", &index)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn write<P>(dst: P, index: &[Record]) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut writer = File::create(dst).map(Writer::new)?;
+    writer.write_index(index)?;
+    writer.try_finish()
+}
@@ -1,6 +1,6 @@
 use std::io::{self, BufRead};
 
-use super::Record;
+use super::{Index, Record};
 
 pub struct Reader<R> {
     inner: R,
@@ -36,6 +36,34 @@ where
             Err(e) => Err(e),
         }
     }
+
+    /// Reads all records of a CRAM index.
+    ///
+    /// The stream is expected to be decompressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram::crai;
+    ///
+    /// let data = b"0\t10946\t6765\t17711\t233\t317811\n";
+    /// let mut reader = crai::Reader::new(&data[..]);
+    /// let index = reader.read_index()?;
+    ///
+    /// assert_eq!(index, [crai::Record::new(0, 10946, 6765, 17711, 233, 317811)]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_index(&mut self) -> io::Result<Index> {
+        let mut index = Vec::new();
+        let mut record = Record::default();
+
+        while self.read_record(&mut record)? != 0 {
+            index.push(record.clone());
+        }
+
+        Ok(index)
+    }
 }
 
 #[cfg(test)]
@@ -18,6 +18,32 @@ pub struct Record {
 }
 
 impl Record {
+    /// Creates a CRAM index record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::crai;
+    /// let record = crai::Record::new(0, 10946, 6765, 17711, 233, 317811);
+    /// ```
+    pub fn new(
+        reference_sequence_id: i32,
+        alignment_start: i32,
+        alignment_span: i32,
+        offset: u64,
+        landmark: u64,
+        slice_len: u64,
+    ) -> Self {
+        Self {
+            reference_sequence_id,
+            alignment_start,
+            alignment_span,
+            offset,
+            landmark,
+            slice_len,
+        }
+    }
+
     pub fn reference_sequence_id(&self) -> i32 {
         self.reference_sequence_id
     }
@@ -43,6 +69,21 @@ impl Record {
     }
 }
 
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.reference_sequence_id,
+            self.alignment_start,
+            self.alignment_span,
+            self.offset,
+            self.landmark,
+            self.slice_len
+        )
+    }
+}
+
 #[derive(Debug)]
 pub enum ParseError {
     Missing(Field),
@@ -90,7 +131,7 @@ where
 {
     fields
         .next()
-        .ok_or_else(|| ParseError::Missing(field))
+        .ok_or(ParseError::Missing(field))
         .and_then(|s| s.parse().map_err(|e| ParseError::Invalid(field, e)))
 }
 
@@ -100,7 +141,7 @@ where
 {
     fields
         .next()
-        .ok_or_else(|| ParseError::Missing(field))
+        .ok_or(ParseError::Missing(field))
         .and_then(|s| s.parse().map_err(|e| ParseError::Invalid(field, e)))
 }
 
@@ -108,6 +149,12 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fmt() {
+        let record = Record::new(0, 10946, 6765, 17711, 233, 317811);
+        assert_eq!(record.to_string(), "0\t10946\t6765\t17711\t233\t317811");
+    }
+
     #[test]
     fn test_from_str() -> Result<(), ParseError> {
         let actual: Record = "0\t10946\t6765\t17711\t233\t317811".parse()?;
@@ -0,0 +1,142 @@
+use std::io::{self, Write};
+
+use flate2::write::GzEncoder;
+
+use super::Record;
+
+/// A CRAM index writer.
+///
+/// The output is gzip-compressed.
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: GzEncoder<W>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a CRAM index writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::crai;
+    /// let writer = crai::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: GzEncoder::new(inner, Default::default()),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::crai;
+    /// let writer = crai::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Attempts to finish the output stream.
+    ///
+    /// This is typically only manually called if the underlying stream is needed before the writer
+    /// is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram::crai;
+    /// let mut writer = crai::Writer::new(Vec::new());
+    /// writer.try_finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        self.inner.try_finish()
+    }
+
+    /// Writes a CRAM index record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram::crai;
+    ///
+    /// let mut writer = crai::Writer::new(Vec::new());
+    ///
+    /// let record = crai::Record::new(0, 10946, 6765, 17711, 233, 317811);
+    /// writer.write_record(&record)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        writeln!(self.inner, "{}", record)
+    }
+
+    /// Writes a CRAM index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram::crai;
+    ///
+    /// let mut writer = crai::Writer::new(Vec::new());
+    ///
+    /// let index = vec![crai::Record::new(0, 10946, 6765, 17711, 233, 317811)];
+    /// writer.write_index(&index)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_index(&mut self, index: &[Record]) -> io::Result<()> {
+        for record in index {
+            self.write_record(record)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use crate::crai::Reader;
+
+    use super::*;
+
+    #[test]
+    fn test_write_index() -> io::Result<()> {
+        let index = vec![
+            Record::new(0, 10946, 6765, 17711, 233, 317811),
+            Record::new(-1, 0, 0, 335, 233, 144),
+        ];
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_index(&index)?;
+        writer.try_finish()?;
+
+        let mut decoder = GzDecoder::new(&writer.get_ref()[..]);
+        let mut buf = String::new();
+        decoder.read_to_string(&mut buf)?;
+
+        assert_eq!(
+            buf,
+            "0\t10946\t6765\t17711\t233\t317811\n-1\t0\t0\t335\t233\t144\n"
+        );
+
+        let mut reader = Reader::new(buf.as_bytes());
+        assert_eq!(reader.read_index()?, index);
+
+        Ok(())
+    }
+}
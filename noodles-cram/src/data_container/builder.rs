@@ -1,6 +1,6 @@
 use std::{io, mem};
 
-use noodles_fasta as fasta;
+use noodles_sam as sam;
 
 use crate::{
     container::{
@@ -8,7 +8,7 @@ use crate::{
         slice::{self, Slice},
         CompressionHeader,
     },
-    Record,
+    Record, ReferenceSequenceRepository,
 };
 
 use super::DataContainer;
@@ -81,7 +81,11 @@ impl Builder {
         }
     }
 
-    pub fn build(mut self, reference_sequences: &[fasta::Record]) -> io::Result<DataContainer> {
+    pub fn build(
+        mut self,
+        reference_sequence_repository: &mut dyn ReferenceSequenceRepository,
+        reference_sequences: &sam::header::ReferenceSequences,
+    ) -> io::Result<DataContainer> {
         if !self.slice_builder.is_empty() {
             self.slice_builders.push(self.slice_builder);
         }
@@ -92,7 +96,14 @@ impl Builder {
         let slices = self
             .slice_builders
             .into_iter()
-            .map(|builder| builder.build(reference_sequences, &compression_header, record_counter))
+            .map(|builder| {
+                builder.build(
+                    reference_sequence_repository,
+                    reference_sequences,
+                    &compression_header,
+                    record_counter,
+                )
+            })
             .collect::<Result<_, _>>()?;
 
         Ok(DataContainer {
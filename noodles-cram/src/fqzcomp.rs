@@ -0,0 +1,501 @@
+//! fqzcomp quality score codec.
+//!
+//! This is the CRAM 3.1 quality score codec. Each quality score is encoded with an adaptive
+//! arithmetic coder using a context built from the previous quality scores, the position in the
+//! record, and the number of changes in quality score seen so far.
+
+mod parameters;
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    io::{self, Read},
+};
+
+use crate::{
+    aac::{Model, RangeDecoder, RangeEncoder},
+    num::{read_uint7, write_uint7},
+};
+
+use self::parameters::{
+    read_parameters, write_parameters, Flags, Parameter, ParameterFlags, Parameters,
+};
+
+const QBITS: u8 = 10;
+const QSHIFT: u8 = 5;
+
+#[derive(Default)]
+struct State {
+    qctx: u32,
+    p: usize,
+    delta: u32,
+    prevq: u8,
+    s: u32,
+}
+
+struct Models {
+    qual: HashMap<u16, Model>,
+    qual_symbol_count: usize,
+    len: Vec<Model>,
+    rev: Model,
+    dup: Model,
+    sel: Model,
+}
+
+impl Models {
+    fn new(parameters: &Parameters) -> Self {
+        Self {
+            qual: HashMap::new(),
+            qual_symbol_count: parameters.symbol_count(),
+            len: vec![Model::new(256); 4],
+            rev: Model::new(2),
+            dup: Model::new(2),
+            sel: Model::new(usize::from(parameters.max_sel) + 1),
+        }
+    }
+
+    fn qual(&mut self, ctx: u16) -> &mut Model {
+        let symbol_count = self.qual_symbol_count;
+
+        self.qual
+            .entry(ctx)
+            .or_insert_with(|| Model::new(symbol_count))
+    }
+}
+
+/// Decodes fqzcomp-encoded quality scores.
+pub fn decode<R>(reader: &mut R) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let len = read_uint7(reader).and_then(|n| {
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })?;
+
+    let parameters = read_parameters(reader)?;
+
+    let mut models = Models::new(&parameters);
+    let mut range_decoder = RangeDecoder::new(reader)?;
+
+    let mut dst = vec![0; len];
+    let mut record_lens = Vec::new();
+    let mut is_reversed = Vec::new();
+    let mut last_len = None;
+
+    let mut i = 0;
+
+    while i < len {
+        let s = if parameters.max_sel > 0 {
+            models.sel.decode(reader, &mut range_decoder)?
+        } else {
+            0
+        };
+
+        let x = parameters.selector_table[usize::from(s)] as usize;
+        let param = &parameters.params[x];
+
+        // The last record length is shared by all parameter blocks.
+        let record_len = match last_len {
+            Some(n) if param.flags.contains(ParameterFlags::DO_LEN) => n,
+            _ => {
+                let mut n = 0;
+
+                for (j, model) in models.len.iter_mut().enumerate() {
+                    let b = model.decode(reader, &mut range_decoder)?;
+                    n |= usize::from(b) << (8 * j);
+                }
+
+                last_len = Some(n);
+                n
+            }
+        };
+
+        if record_len > len - i {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "fqzcomp record length exceeds data length",
+            ));
+        }
+
+        record_lens.push(record_len);
+
+        if parameters.flags.contains(Flags::DO_REV) {
+            let is_rev = models.rev.decode(reader, &mut range_decoder)?;
+            is_reversed.push(is_rev == 1);
+        }
+
+        if param.flags.contains(ParameterFlags::DO_DEDUP) {
+            let is_dup = models.dup.decode(reader, &mut range_decoder)?;
+
+            if is_dup == 1 {
+                if record_len > i {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "invalid fqzcomp duplicate record",
+                    ));
+                }
+
+                dst.copy_within(i - record_len..i, i);
+                i += record_len;
+
+                continue;
+            }
+        }
+
+        let mut state = State {
+            p: record_len,
+            s: u32::from(s),
+            ..Default::default()
+        };
+
+        let mut ctx = param.context;
+
+        for d in &mut dst[i..i + record_len] {
+            let q = models.qual(ctx).decode(reader, &mut range_decoder)?;
+            *d = param.qmap[usize::from(q)];
+            ctx = update_context(param, &mut state, q);
+        }
+
+        i += record_len;
+    }
+
+    if parameters.flags.contains(Flags::DO_REV) {
+        reverse_records(&mut dst, &record_lens, &is_reversed);
+    }
+
+    Ok(dst)
+}
+
+/// Encodes quality scores using fqzcomp.
+///
+/// `record_lens` are the number of quality scores in each record and must sum to the length of
+/// `src`.
+pub fn encode(record_lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
+    if record_lens.iter().sum::<usize>() != src.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "fqzcomp record lengths do not match data length",
+        ));
+    }
+
+    let parameters = build_parameters(record_lens, src);
+    let param = &parameters.params[0];
+
+    let mut qmap_inv = [0; 256];
+
+    for (i, &q) in param
+        .qmap
+        .iter()
+        .enumerate()
+        .take(usize::from(param.max_sym))
+    {
+        qmap_inv[usize::from(q)] = i as u8;
+    }
+
+    let mut dst = Vec::new();
+
+    let len =
+        u32::try_from(src.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    write_uint7(&mut dst, len)?;
+    write_parameters(&mut dst, &parameters)?;
+
+    let mut models = Models::new(&parameters);
+    let mut range_encoder = RangeEncoder::default();
+
+    let mut i = 0;
+    let mut last_record: Option<&[u8]> = None;
+
+    for (j, &record_len) in record_lens.iter().enumerate() {
+        if j == 0 || !param.flags.contains(ParameterFlags::DO_LEN) {
+            let n = u32::try_from(record_len)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            for (k, model) in models.len.iter_mut().enumerate() {
+                model.encode(&mut dst, &mut range_encoder, (n >> (8 * k)) as u8)?;
+            }
+        }
+
+        let record = &src[i..i + record_len];
+        i += record_len;
+
+        if param.flags.contains(ParameterFlags::DO_DEDUP) {
+            let is_dup = last_record == Some(record);
+            models
+                .dup
+                .encode(&mut dst, &mut range_encoder, u8::from(is_dup))?;
+
+            last_record = Some(record);
+
+            if is_dup {
+                continue;
+            }
+        }
+
+        let mut state = State {
+            p: record_len,
+            ..Default::default()
+        };
+
+        let mut ctx = param.context;
+
+        for &score in record {
+            let q = if param.flags.contains(ParameterFlags::HAVE_QMAP) {
+                qmap_inv[usize::from(score)]
+            } else {
+                score
+            };
+
+            models.qual(ctx).encode(&mut dst, &mut range_encoder, q)?;
+            ctx = update_context(param, &mut state, q);
+        }
+    }
+
+    range_encoder.finish(&mut dst);
+
+    Ok(dst)
+}
+
+fn build_parameters(record_lens: &[usize], src: &[u8]) -> Parameters {
+    let mut flags = ParameterFlags::empty();
+
+    if record_lens.windows(2).all(|w| w[0] == w[1]) {
+        flags.insert(ParameterFlags::DO_LEN);
+    }
+
+    let mut is_dup = false;
+    let mut i = 0;
+
+    for w in record_lens.windows(2) {
+        let (a, b) = (w[0], w[1]);
+
+        if a == b && src[i..i + a] == src[i + a..i + a + b] {
+            is_dup = true;
+            break;
+        }
+
+        i += a;
+    }
+
+    if is_dup {
+        flags.insert(ParameterFlags::DO_DEDUP);
+    }
+
+    let mut is_present = [false; 256];
+
+    for &score in src {
+        is_present[usize::from(score)] = true;
+    }
+
+    let qmap: Vec<u8> = (0..=255).filter(|&q| is_present[usize::from(q)]).collect();
+    let max_score = qmap.last().copied().unwrap_or(0);
+
+    let (max_sym, qmap) = if qmap.len() < usize::from(max_score) {
+        flags.insert(ParameterFlags::HAVE_QMAP);
+        let max_sym = qmap.len() as u8;
+        let qmap = qmap.into_iter().chain(max_sym..=255).collect();
+        (max_sym, qmap)
+    } else {
+        (max_score, (0..=255).collect())
+    };
+
+    let param = Parameter {
+        context: 0,
+        flags,
+        max_sym,
+        qbits: QBITS,
+        qshift: QSHIFT,
+        qloc: 0,
+        sloc: 0,
+        ploc: 0,
+        dloc: 0,
+        qmap,
+        qtab: (0..256).collect(),
+        ptab: vec![0; 1024],
+        dtab: vec![0; 256],
+    };
+
+    Parameters {
+        flags: Flags::empty(),
+        max_sel: 0,
+        selector_table: vec![0; 256],
+        params: vec![param],
+    }
+}
+
+fn update_context(param: &Parameter, state: &mut State, q: u8) -> u16 {
+    let mut ctx = u32::from(param.context);
+
+    state.qctx = (state.qctx << param.qshift).wrapping_add(param.qtab[usize::from(q)]);
+    ctx = ctx.wrapping_add((state.qctx & param.qmask()) << param.qloc);
+
+    ctx = ctx.wrapping_add(param.ptab[state.p.min(1023)] << param.ploc);
+    ctx = ctx.wrapping_add(param.dtab[state.delta.min(255) as usize] << param.dloc);
+
+    if param.flags.contains(ParameterFlags::DO_SEL) {
+        ctx = ctx.wrapping_add(state.s << param.sloc);
+    }
+
+    state.delta += u32::from(state.prevq != q);
+    state.prevq = q;
+    state.p = state.p.saturating_sub(1);
+
+    (ctx & 0xffff) as u16
+}
+
+fn reverse_records(dst: &mut [u8], record_lens: &[usize], is_reversed: &[bool]) {
+    let mut i = 0;
+
+    for (&len, &is_rev) in record_lens.iter().zip(is_reversed) {
+        if is_rev {
+            dst[i..i + len].reverse();
+        }
+
+        i += len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The following streams are built independently of `encode`, directly from the format
+    // described in the CRAM codecs specification.
+    #[test]
+    fn test_decode_with_single_parameter() -> io::Result<()> {
+        // no flags
+        let data = [
+            0x08, 0x05, 0x00, 0x00, 0x00, 0x00, 0x28, 0xa5, 0x00, 0x00, 0x00, 0x04, 0xff, 0xff,
+            0xfb, 0x41, 0x99, 0x5a, 0x18, 0x71, 0xb6, 0x3f, 0x54, 0x28, 0x82, 0xe8,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader)?, [10, 20, 30, 30, 25, 12, 12, 40]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_qmap_len_and_dedup() -> io::Result<()> {
+        // a quality map, fixed-length records, and a duplicate record
+        let data = [
+            0x0c, 0x05, 0x00, 0x00, 0x00, 0x16, 0x03, 0xa5, 0x00, 0x00, 0x02, 0x21, 0x29, 0x00,
+            0x03, 0xff, 0xff, 0xfc, 0x1b, 0xe5, 0xa8, 0xbd, 0x8b, 0x77, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(
+            decode(&mut reader)?,
+            [2, 33, 33, 41, 2, 33, 33, 41, 41, 41, 2, 33]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_lookup_tables() -> io::Result<()> {
+        // quality, position, and delta context tables
+        let data = [
+            0x0c, 0x05, 0x00, 0x00, 0x00, 0xe0, 0x28, 0x63, 0x00, 0x8b, 0x08, 0x08, 0x1e, 0x04,
+            0x04, 0x01, 0xff, 0xff, 0x01, 0xf7, 0x01, 0x01, 0x05, 0xf9, 0x00, 0x07, 0xff, 0xff,
+            0xf8, 0xc0, 0x29, 0x1f, 0x5b, 0x50, 0x62, 0xb0, 0xc9, 0x57, 0x05, 0x46, 0xec, 0x71,
+            0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(
+            decode(&mut reader)?,
+            [30, 31, 32, 40, 40, 40, 10, 12, 35, 35, 20, 21]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_reversed_records() -> io::Result<()> {
+        // DO_REV
+        let data = [
+            0x09, 0x05, 0x04, 0x00, 0x00, 0x00, 0x1f, 0xa5, 0x00, 0x00, 0x00, 0x02, 0xff, 0xff,
+            0xfd, 0x29, 0x6c, 0x12, 0x9f, 0x81, 0x1e, 0x0e, 0xcc, 0x13, 0x9e, 0x1d, 0xab, 0xe6,
+            0x50, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader)?, [10, 11, 12, 20, 21, 22, 23, 30, 31]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_selector_table() -> io::Result<()> {
+        // MULTI_PARAM | HAVE_STAB, with DO_SEL in the second block
+        let data = [
+            0x0a, 0x05, 0x03, 0x02, 0x02, 0x02, 0xfe, 0x00, 0x00, 0x00, 0x0e, 0xa5, 0x00, 0x00,
+            0x00, 0x80, 0x08, 0x29, 0xa5, 0x0f, 0x00, 0x00, 0x56, 0x55, 0x55, 0x54, 0x14, 0xdd,
+            0x4f, 0xda, 0x3f, 0xa4, 0x5d, 0xc6, 0x42, 0x2d, 0x10, 0x68, 0x87, 0x29, 0x35, 0x57,
+            0xd6, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(
+            decode(&mut reader)?,
+            [10, 11, 12, 40, 40, 13, 14, 41, 39, 38]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_multiple_parameters() -> io::Result<()> {
+        // MULTI_PARAM with an implicit selector table
+        let data = [
+            0x06, 0x05, 0x01, 0x02, 0x00, 0x00, 0x00, 0x08, 0xa5, 0x00, 0x00, 0x00, 0x40, 0x00,
+            0x08, 0xa5, 0x00, 0x00, 0x00, 0x00, 0xaa, 0xaa, 0xaa, 0x36, 0xac, 0xc4, 0x1e, 0x66,
+            0x0f, 0xf9, 0x49, 0x28, 0x6e, 0xad, 0x68,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader)?, [5, 6, 7, 7, 7, 8]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_round_trip() -> io::Result<()> {
+        let scores: Vec<u8> = (0..160).map(|i| ((i * 7) % 41) as u8).collect();
+
+        let cases: Vec<(Vec<usize>, Vec<u8>)> = vec![
+            (vec![], vec![]),
+            (vec![4], vec![45, 35, 43, 50]),
+            (vec![8, 8], vec![30; 16]),
+            (
+                vec![4, 4, 4],
+                [[20, 30, 40, 2], [20, 30, 40, 2], [2, 2, 2, 2]].concat(),
+            ),
+            (vec![100, 50, 10], scores),
+            (vec![3, 0, 2], vec![0, 1, 2, 93, 93]),
+        ];
+
+        for (record_lens, data) in cases {
+            let encoded_data = encode(&record_lens, &data)?;
+            let mut reader = &encoded_data[..];
+            assert_eq!(decode(&mut reader)?, data);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_invalid_record_lens() {
+        assert!(matches!(
+            encode(&[2], &[45, 35, 43]),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_reverse_records() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        reverse_records(&mut data, &[2, 4], &[false, true]);
+        assert_eq!(data, [1, 2, 6, 5, 4, 3]);
+    }
+}
@@ -0,0 +1,377 @@
+use std::io::{self, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+const VERSION: u8 = 5;
+
+const QUALITY_TABLE_SIZE: usize = 256;
+const POSITION_TABLE_SIZE: usize = 1024;
+const DELTA_TABLE_SIZE: usize = 256;
+const SELECTOR_TABLE_SIZE: usize = 256;
+
+bitflags::bitflags! {
+    /// fqzcomp global parameter flags.
+    #[derive(Default)]
+    pub struct Flags: u8 {
+        /// There are multiple parameter blocks.
+        const MULTI_PARAM = 0x01;
+        /// A selector table is present.
+        const HAVE_STAB = 0x02;
+        /// Records may be stored reversed.
+        const DO_REV = 0x04;
+    }
+}
+
+bitflags::bitflags! {
+    /// fqzcomp parameter block flags.
+    #[derive(Default)]
+    pub struct ParameterFlags: u8 {
+        /// Records may be duplicates of the previous record.
+        const DO_DEDUP = 0x02;
+        /// All records have the same length, which is only stored once.
+        const DO_LEN = 0x04;
+        /// The selector is added to the context.
+        const DO_SEL = 0x08;
+        /// A quality value map is present.
+        const HAVE_QMAP = 0x10;
+        /// A position table is present.
+        const HAVE_PTAB = 0x20;
+        /// A delta table is present.
+        const HAVE_DTAB = 0x40;
+        /// A quality context table is present.
+        const HAVE_QTAB = 0x80;
+    }
+}
+
+#[derive(Debug)]
+pub struct Parameters {
+    pub flags: Flags,
+    pub max_sel: u8,
+    pub selector_table: Vec<u32>,
+    pub params: Vec<Parameter>,
+}
+
+impl Parameters {
+    /// Returns the number of symbols used by the quality models.
+    pub fn symbol_count(&self) -> usize {
+        self.params
+            .iter()
+            .map(|param| usize::from(param.max_sym) + 1)
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+#[derive(Debug)]
+pub struct Parameter {
+    pub context: u16,
+    pub flags: ParameterFlags,
+    pub max_sym: u8,
+    pub qbits: u8,
+    pub qshift: u8,
+    pub qloc: u8,
+    pub sloc: u8,
+    pub ploc: u8,
+    pub dloc: u8,
+    pub qmap: Vec<u8>,
+    pub qtab: Vec<u32>,
+    pub ptab: Vec<u32>,
+    pub dtab: Vec<u32>,
+}
+
+impl Parameter {
+    pub fn qmask(&self) -> u32 {
+        (1 << self.qbits) - 1
+    }
+}
+
+pub fn read_parameters<R>(reader: &mut R) -> io::Result<Parameters>
+where
+    R: Read,
+{
+    let version = reader.read_u8()?;
+
+    if version != VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid fqzcomp version: expected {}, got {}",
+                VERSION, version
+            ),
+        ));
+    }
+
+    let flags = reader.read_u8().map(Flags::from_bits_truncate)?;
+
+    let param_count = if flags.contains(Flags::MULTI_PARAM) {
+        usize::from(reader.read_u8()?)
+    } else {
+        1
+    };
+
+    if param_count == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid fqzcomp parameter count",
+        ));
+    }
+
+    let (max_sel, selector_table) = if flags.contains(Flags::HAVE_STAB) {
+        let max_sel = reader.read_u8()?;
+        let selector_table = read_array(reader, SELECTOR_TABLE_SIZE)?;
+        (max_sel, selector_table)
+    } else {
+        let max_sel = if param_count > 1 {
+            param_count as u8
+        } else {
+            0
+        };
+        let last_param = (param_count - 1) as u32;
+        let selector_table = (0..SELECTOR_TABLE_SIZE as u32)
+            .map(|i| i.min(last_param))
+            .collect();
+        (max_sel, selector_table)
+    };
+
+    if selector_table.iter().any(|&i| i as usize >= param_count) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid fqzcomp selector table",
+        ));
+    }
+
+    let params = (0..param_count)
+        .map(|_| read_parameter(reader))
+        .collect::<io::Result<_>>()?;
+
+    Ok(Parameters {
+        flags,
+        max_sel,
+        selector_table,
+        params,
+    })
+}
+
+fn read_parameter<R>(reader: &mut R) -> io::Result<Parameter>
+where
+    R: Read,
+{
+    let context = reader.read_u16::<LittleEndian>()?;
+    let flags = reader.read_u8().map(ParameterFlags::from_bits_truncate)?;
+    let max_sym = reader.read_u8()?;
+
+    let (qbits, qshift) = read_nibbles(reader)?;
+    let (qloc, sloc) = read_nibbles(reader)?;
+    let (ploc, dloc) = read_nibbles(reader)?;
+
+    let mut qmap: Vec<_> = (0..=255).collect();
+
+    if flags.contains(ParameterFlags::HAVE_QMAP) {
+        reader.read_exact(&mut qmap[..usize::from(max_sym)])?;
+    }
+
+    let qtab = if qbits > 0 && flags.contains(ParameterFlags::HAVE_QTAB) {
+        read_array(reader, QUALITY_TABLE_SIZE)?
+    } else {
+        (0..QUALITY_TABLE_SIZE as u32).collect()
+    };
+
+    let ptab = if flags.contains(ParameterFlags::HAVE_PTAB) {
+        read_array(reader, POSITION_TABLE_SIZE)?
+    } else {
+        vec![0; POSITION_TABLE_SIZE]
+    };
+
+    let dtab = if flags.contains(ParameterFlags::HAVE_DTAB) {
+        read_array(reader, DELTA_TABLE_SIZE)?
+    } else {
+        vec![0; DELTA_TABLE_SIZE]
+    };
+
+    Ok(Parameter {
+        context,
+        flags,
+        max_sym,
+        qbits,
+        qshift,
+        qloc,
+        sloc,
+        ploc,
+        dloc,
+        qmap,
+        qtab,
+        ptab,
+        dtab,
+    })
+}
+
+fn read_nibbles<R>(reader: &mut R) -> io::Result<(u8, u8)>
+where
+    R: Read,
+{
+    let n = reader.read_u8()?;
+    Ok((n >> 4, n & 0x0f))
+}
+
+/// Writes parameters that do not use any lookup tables.
+pub fn write_parameters(dst: &mut Vec<u8>, parameters: &Parameters) -> io::Result<()> {
+    if parameters.params.len() != 1 || !parameters.flags.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "only a single fqzcomp parameter block is supported",
+        ));
+    }
+
+    dst.write_u8(VERSION)?;
+    dst.write_u8(parameters.flags.bits())?;
+
+    for param in &parameters.params {
+        if param.flags.intersects(
+            ParameterFlags::HAVE_QTAB | ParameterFlags::HAVE_PTAB | ParameterFlags::HAVE_DTAB,
+        ) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "fqzcomp lookup tables are not supported",
+            ));
+        }
+
+        dst.write_u16::<LittleEndian>(param.context)?;
+        dst.write_u8(param.flags.bits())?;
+        dst.write_u8(param.max_sym)?;
+        dst.write_u8(param.qbits << 4 | param.qshift)?;
+        dst.write_u8(param.qloc << 4 | param.sloc)?;
+        dst.write_u8(param.ploc << 4 | param.dloc)?;
+
+        if param.flags.contains(ParameterFlags::HAVE_QMAP) {
+            dst.extend(&param.qmap[..usize::from(param.max_sym)]);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a run-length encoded lookup table.
+///
+/// The table is stored as the run lengths of each successive value, starting at 0. Run lengths
+/// greater than 254 are split into parts of 255. These run lengths are then run-length encoded
+/// again: a byte equal to the previous byte is followed by the number of additional copies.
+fn read_array<R>(reader: &mut R, size: usize) -> io::Result<Vec<u32>>
+where
+    R: Read,
+{
+    let mut runs = Vec::new();
+    let mut total = 0;
+    let mut last = None;
+
+    while total < size {
+        let run = reader.read_u8()?;
+        runs.push(run);
+        total += usize::from(run);
+
+        if last == Some(run) {
+            let copies = reader.read_u8()?;
+            runs.resize(runs.len() + usize::from(copies), run);
+            total += usize::from(run) * usize::from(copies);
+        }
+
+        last = Some(run);
+    }
+
+    let mut dst = Vec::with_capacity(size);
+    let mut runs = runs.into_iter();
+    let mut value = 0;
+
+    while dst.len() < size {
+        let mut run_len = 0;
+
+        loop {
+            let part = runs.next().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid fqzcomp array")
+            })?;
+
+            run_len += usize::from(part);
+
+            if part != 255 {
+                break;
+            }
+        }
+
+        let run_len = run_len.min(size - dst.len());
+        dst.resize(dst.len() + run_len, value);
+        value += 1;
+    }
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_array() -> io::Result<()> {
+        let data = [0x02, 0x02, 0x00];
+        let mut reader = &data[..];
+        assert_eq!(read_array(&mut reader, 4)?, [0, 0, 1, 1]);
+
+        let data = [0x01, 0x01, 0x02];
+        let mut reader = &data[..];
+        assert_eq!(read_array(&mut reader, 4)?, [0, 1, 2, 3]);
+
+        let data = [0xff, 0x2d];
+        let mut reader = &data[..];
+        assert_eq!(read_array(&mut reader, 300)?, vec![0; 300]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_parameters() -> io::Result<()> {
+        let data = [
+            0x05, // version = 5
+            0x00, // flags = {}
+            0x00, 0x00, // context = 0
+            0x14, // parameter flags = {DO_LEN, HAVE_QMAP}
+            0x02, // max_sym = 2
+            0xa5, // qbits = 10, qshift = 5
+            0x00, // qloc = 0, sloc = 0
+            0x00, // ploc = 0, dloc = 0
+            0x0d, 0x1e, // qmap = [13, 30]
+        ];
+
+        let mut reader = &data[..];
+        let parameters = read_parameters(&mut reader)?;
+
+        assert!(parameters.flags.is_empty());
+        assert_eq!(parameters.max_sel, 0);
+        assert_eq!(parameters.params.len(), 1);
+        assert_eq!(parameters.symbol_count(), 3);
+
+        let param = &parameters.params[0];
+        assert_eq!(
+            param.flags,
+            ParameterFlags::DO_LEN | ParameterFlags::HAVE_QMAP
+        );
+        assert_eq!(param.qbits, 10);
+        assert_eq!(param.qshift, 5);
+        assert_eq!(param.qmask(), 0x03ff);
+        assert_eq!(&param.qmap[..3], [13, 30, 2]);
+
+        let mut buf = Vec::new();
+        write_parameters(&mut buf, &parameters)?;
+        assert_eq!(buf, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_parameters_with_invalid_version() {
+        let data = [0x04];
+        let mut reader = &data[..];
+
+        assert!(matches!(
+            read_parameters(&mut reader),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::{self, Read, Write};
+
+use crate::{
+    golomb::{offset_value, remove_offset},
+    num::Itf8,
+    BitReader, BitWriter,
+};
+
+/// Reads an Elias gamma-encoded integer.
+///
+/// The number of bits after the leading set bit is unary coded using unset bits.
+pub fn read<R>(reader: &mut BitReader<R>, offset: Itf8) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let mut n = 0;
+
+    while reader.read_u32(1)? == 0 {
+        n += 1;
+
+        if n >= 32 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid gamma-encoded value",
+            ));
+        }
+    }
+
+    let m = reader.read_u32(n)?;
+    let x = (1u32 << n) | m;
+
+    remove_offset(offset, x)
+}
+
+/// Writes an integer using Elias gamma encoding.
+///
+/// The value plus the offset must be positive.
+pub fn write<W>(writer: &mut BitWriter<W>, offset: Itf8, value: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let x = offset_value(offset, value)?;
+
+    if x == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "value cannot be gamma encoded with offset {}: {}",
+                offset, value
+            ),
+        ));
+    }
+
+    let n = (31 - x.leading_zeros()) as usize;
+
+    writer.write_u32(0, n)?;
+    writer.write_u32(x, n + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        // 1 => 1, 5 => 00101, 2 => 010
+        let data = [0b10010101, 0b00000000];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(read(&mut reader, 0)?, 1);
+        assert_eq!(read(&mut reader, 0)?, 5);
+        assert_eq!(read(&mut reader, 1)?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_overflowing_value() {
+        // x = 2^31
+        let data = [0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert!(matches!(
+            read(&mut reader, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        // 1 => 1
+        let data = [0b10000000];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert!(matches!(
+            read(&mut reader, i32::MIN),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_write() -> io::Result<()> {
+        let mut writer = BitWriter::new(Vec::new());
+
+        write(&mut writer, 0, 1)?;
+        write(&mut writer, 0, 5)?;
+        write(&mut writer, 1, 1)?;
+        writer.try_finish()?;
+
+        assert_eq!(writer.get_ref(), &[0b10010101, 0b00000000]);
+
+        let mut writer = BitWriter::new(Vec::new());
+        assert!(write(&mut writer, 0, 0).is_err());
+
+        Ok(())
+    }
+}
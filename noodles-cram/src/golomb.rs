@@ -0,0 +1,315 @@
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Write},
+};
+
+use crate::{num::Itf8, BitReader, BitWriter};
+
+/// Reads a Golomb-encoded integer.
+///
+/// The quotient is unary coded, and the remainder is truncated binary coded.
+pub fn read<R>(reader: &mut BitReader<R>, offset: Itf8, m: Itf8) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let m = parse_m(m)?;
+
+    let q = read_unary(reader)?;
+
+    let b = ceil_log2(m);
+    let cutoff = (1 << b) - m;
+
+    let r = if b == 0 {
+        0
+    } else {
+        let r = reader.read_u32(b - 1)?;
+
+        if r < cutoff {
+            r
+        } else {
+            ((r << 1) | reader.read_u32(1)?) - cutoff
+        }
+    };
+
+    let n = q
+        .checked_mul(m)
+        .and_then(|n| n.checked_add(r))
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "invalid Golomb-encoded value")
+        })?;
+
+    remove_offset(offset, n)
+}
+
+/// Writes an integer using Golomb encoding.
+pub fn write<W>(writer: &mut BitWriter<W>, offset: Itf8, m: Itf8, value: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let m = parse_m(m)?;
+    let n = offset_value(offset, value)?;
+
+    let q = n / m;
+    let r = n % m;
+
+    write_unary(writer, q)?;
+
+    let b = ceil_log2(m);
+    let cutoff = (1 << b) - m;
+
+    if b == 0 {
+        Ok(())
+    } else if r < cutoff {
+        writer.write_u32(r, b - 1)
+    } else {
+        writer.write_u32(r + cutoff, b)
+    }
+}
+
+/// Reads a Golomb-Rice-encoded integer, i.e., Golomb encoding with `m` = 2^`log2_m`.
+pub fn read_rice<R>(reader: &mut BitReader<R>, offset: Itf8, log2_m: Itf8) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let log2_m = parse_log2_m(log2_m)?;
+
+    let q = read_unary(reader)?;
+    let r = reader.read_u32(log2_m)?;
+
+    let n = q.checked_mul(1 << log2_m).map(|n| n | r).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid Golomb-Rice-encoded value",
+        )
+    })?;
+
+    remove_offset(offset, n)
+}
+
+/// Writes an integer using Golomb-Rice encoding.
+pub fn write_rice<W>(
+    writer: &mut BitWriter<W>,
+    offset: Itf8,
+    log2_m: Itf8,
+    value: Itf8,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let log2_m = parse_log2_m(log2_m)?;
+    let n = offset_value(offset, value)?;
+
+    let q = n >> log2_m;
+    let r = n & ((1 << log2_m) - 1);
+
+    write_unary(writer, q)?;
+    writer.write_u32(r, log2_m)
+}
+
+/// Reads a unary-coded integer, i.e., the number of set bits before an unset bit.
+pub(crate) fn read_unary<R>(reader: &mut BitReader<R>) -> io::Result<u32>
+where
+    R: Read,
+{
+    let mut n = 0;
+
+    while reader.read_u32(1)? == 1 {
+        n += 1;
+
+        if n == u32::MAX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid unary-coded value",
+            ));
+        }
+    }
+
+    Ok(n)
+}
+
+/// Writes an integer using unary coding.
+pub(crate) fn write_unary<W>(writer: &mut BitWriter<W>, n: u32) -> io::Result<()>
+where
+    W: Write,
+{
+    for _ in 0..n {
+        writer.write_u32(1, 1)?;
+    }
+
+    writer.write_u32(0, 1)
+}
+
+/// Adds the offset to a value to be encoded, which must result in a non-negative integer.
+pub(crate) fn offset_value(offset: Itf8, value: Itf8) -> io::Result<u32> {
+    value
+        .checked_add(offset)
+        .filter(|&n| n >= 0)
+        .map(|n| n as u32)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("value cannot be encoded with offset {}: {}", offset, value),
+            )
+        })
+}
+
+/// Subtracts the offset from a decoded value, which must result in an `Itf8`.
+pub(crate) fn remove_offset(offset: Itf8, n: u32) -> io::Result<Itf8> {
+    Itf8::try_from(n)
+        .ok()
+        .and_then(|n| n.checked_sub(offset))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid decoded value with offset {}: {}", offset, n),
+            )
+        })
+}
+
+fn parse_m(m: Itf8) -> io::Result<u32> {
+    if m > 0 {
+        Ok(m as u32)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid Golomb parameter: {}", m),
+        ))
+    }
+}
+
+fn parse_log2_m(log2_m: Itf8) -> io::Result<usize> {
+    if (0..32).contains(&log2_m) {
+        Ok(log2_m as usize)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid Golomb-Rice parameter: {}", log2_m),
+        ))
+    }
+}
+
+fn ceil_log2(n: u32) -> usize {
+    (32 - (n - 1).leading_zeros()) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        // m = 5, b = 3, cutoff = 3
+        // 0 => 0 00, 2 => 0 10, 3 => 0 110, 9 => 10 111
+        let data = [0b00001001, 0b10101110];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(read(&mut reader, 0, 5)?, 0);
+        assert_eq!(read(&mut reader, 0, 5)?, 2);
+        assert_eq!(read(&mut reader, 0, 5)?, 3);
+        assert_eq!(read(&mut reader, 0, 5)?, 9);
+
+        let mut reader = BitReader::new(&data[..]);
+        assert!(read(&mut reader, 0, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_overflowing_value() {
+        // m = 2^30, q = 4
+        let data = [0b11110000, 0, 0, 0, 0];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert!(matches!(
+            read(&mut reader, 0, 1 << 30),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        // m = 1, q = 3, offset = i32::MIN
+        let data = [0b11100000];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert!(matches!(
+            read(&mut reader, i32::MIN, 1),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_write() -> io::Result<()> {
+        let mut writer = BitWriter::new(Vec::new());
+
+        write(&mut writer, 0, 5, 0)?;
+        write(&mut writer, 0, 5, 2)?;
+        write(&mut writer, 0, 5, 3)?;
+        write(&mut writer, 0, 5, 9)?;
+        writer.try_finish()?;
+
+        assert_eq!(writer.get_ref(), &[0b00001001, 0b10101110]);
+
+        let mut writer = BitWriter::new(Vec::new());
+        assert!(write(&mut writer, 0, 5, -1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> io::Result<()> {
+        let values = [-3, 0, 1, 7, 8, 21, 144];
+
+        for &m in &[1, 2, 3, 8, 13] {
+            let mut writer = BitWriter::new(Vec::new());
+
+            for &value in &values {
+                write(&mut writer, 3, m, value)?;
+            }
+
+            let data = writer.finish()?;
+            let mut reader = BitReader::new(&data[..]);
+
+            for &value in &values {
+                assert_eq!(read(&mut reader, 3, m)?, value);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_rice() -> io::Result<()> {
+        // log2_m = 2
+        // 1 => 0 01, 6 => 10 10
+        let data = [0b00110100];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(read_rice(&mut reader, 0, 2)?, 1);
+        assert_eq!(read_rice(&mut reader, 0, 2)?, 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_rice_with_overflowing_value() {
+        // log2_m = 31, q = 2
+        let data = [0b11000000, 0, 0, 0, 0];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert!(matches!(
+            read_rice(&mut reader, 0, 31),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_write_rice() -> io::Result<()> {
+        let mut writer = BitWriter::new(Vec::new());
+
+        write_rice(&mut writer, 0, 2, 1)?;
+        write_rice(&mut writer, 0, 2, 6)?;
+        writer.try_finish()?;
+
+        assert_eq!(writer.get_ref(), &[0b00110100]);
+
+        Ok(())
+    }
+}
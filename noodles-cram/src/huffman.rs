@@ -1,9 +1,9 @@
 use std::{
     collections::HashMap,
-    io::{self, Read},
+    io::{self, Read, Write},
 };
 
-use crate::{num::Itf8, BitReader};
+use crate::{num::Itf8, BitReader, BitWriter};
 
 type CodeBook = HashMap<Itf8, (Itf8, usize)>;
 
@@ -40,7 +40,7 @@ impl CanonicalHuffmanDecoder {
         for &len in sorted_lens {
             input_code <<= len - prev_len;
 
-            let b = reader.read_u32(len)? as i32;
+            let b = reader.read_u32(len - prev_len)? as i32;
             input_code |= b;
 
             let entry = code_book_by_len[&len]
@@ -61,6 +61,31 @@ impl CanonicalHuffmanDecoder {
     }
 }
 
+pub struct CanonicalHuffmanEncoder {
+    code_book: CodeBook,
+}
+
+impl CanonicalHuffmanEncoder {
+    pub fn new(alphabet: &[Itf8], bit_lens: &[Itf8]) -> Self {
+        let code_book = build_canonical_code_book(alphabet, bit_lens);
+        Self { code_book }
+    }
+
+    pub fn write<W>(&self, writer: &mut BitWriter<W>, symbol: Itf8) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let (code, len) = self.code_book.get(&symbol).copied().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("symbol not in alphabet: {}", symbol),
+            )
+        })?;
+
+        writer.write_u32(code as u32, len)
+    }
+}
+
 fn build_canonical_code_book(alphabet: &[Itf8], bit_lens: &[Itf8]) -> CodeBook {
     let sorted_alphabet = {
         let mut pairs: Vec<_> = alphabet.iter().zip(bit_lens.iter()).collect();
@@ -107,4 +132,78 @@ mod tests {
         assert_eq!(code_book[&69], (0b1110, 4));
         assert_eq!(code_book[&70], (0b1111, 4));
     }
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        let symbols = [65, 66, 67, 68, 69, 70];
+        let bit_lens = [1, 3, 3, 3, 4, 4];
+
+        let decoder = CanonicalHuffmanDecoder::new(&symbols, &bit_lens);
+
+        // 0 110 1111 100 1110 101
+        let data = [0b01101111, 0b10011101, 0b01000000];
+        let mut reader = BitReader::new(&data[..]);
+
+        for &expected in &[65, 68, 70, 66, 69, 67] {
+            assert_eq!(decoder.read(&mut reader)?, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write() -> io::Result<()> {
+        let symbols = [65, 66, 67, 68, 69, 70];
+        let bit_lens = [1, 3, 3, 3, 4, 4];
+
+        let encoder = CanonicalHuffmanEncoder::new(&symbols, &bit_lens);
+        let mut writer = BitWriter::new(Vec::new());
+
+        for &symbol in &[65, 68, 70, 66] {
+            encoder.write(&mut writer, symbol)?;
+        }
+
+        writer.try_finish()?;
+
+        // 0 110 1111 100
+        assert_eq!(writer.get_ref(), &[0b01101111, 0b10000000]);
+
+        assert!(encoder.write(&mut writer, 71).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> io::Result<()> {
+        let symbols = [65, 66, 67, 68, 69, 70];
+        let bit_lens = [1, 3, 3, 3, 4, 4];
+        let values = [70, 65, 65, 67, 69, 66, 68];
+
+        let encoder = CanonicalHuffmanEncoder::new(&symbols, &bit_lens);
+        let mut writer = BitWriter::new(Vec::new());
+
+        for &value in &values {
+            encoder.write(&mut writer, value)?;
+        }
+
+        let data = writer.finish()?;
+
+        let decoder = CanonicalHuffmanDecoder::new(&symbols, &bit_lens);
+        let mut reader = BitReader::new(&data[..]);
+
+        for &value in &values {
+            assert_eq!(decoder.read(&mut reader)?, value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_single_symbol() -> io::Result<()> {
+        let encoder = CanonicalHuffmanEncoder::new(&[0], &[0]);
+        let mut writer = BitWriter::new(Vec::new());
+        encoder.write(&mut writer, 0)?;
+        assert!(writer.get_ref().is_empty());
+        Ok(())
+    }
 }
@@ -0,0 +1,305 @@
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fs::File,
+    io::{self, BufReader, Read, Seek},
+    path::Path,
+};
+
+use super::{
+    container::{CompressionHeader, ReferenceSequenceId, Slice},
+    crai, DataContainer, Reader,
+};
+
+/// Indexes a CRAM file.
+///
+/// This reads each container and emits a CRAM index record for each slice. Slices with records
+/// that map to multiple reference sequences emit a record for each reference sequence.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_cram as cram;
+/// let index = cram::index("sample.cram")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<crai::Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(BufReader::new).map(Reader::new)?;
+    build_index(&mut reader)
+}
+
+pub(crate) fn build_index<R>(reader: &mut Reader<R>) -> io::Result<crai::Index>
+where
+    R: Read + Seek,
+{
+    reader.read_file_definition()?;
+    reader.read_file_header()?;
+
+    let mut index = Vec::new();
+
+    loop {
+        let container_position = reader.position()?;
+        let container = reader.read_container()?;
+
+        if container.is_eof() {
+            break;
+        }
+
+        let container_len = u64::try_from(container.header().len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let landmarks = container
+            .header()
+            .landmarks()
+            .iter()
+            .map(|&landmark| {
+                u64::try_from(landmark).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let data_container = DataContainer::try_from(container)?;
+
+        for (i, slice) in data_container.slices().iter().enumerate() {
+            let (landmark, slice_len) = slice_bounds(&landmarks, container_len, i)?;
+
+            push_index_records(
+                &mut index,
+                data_container.compression_header(),
+                slice,
+                container_position,
+                landmark,
+                slice_len,
+            )?;
+        }
+    }
+
+    Ok(index)
+}
+
+// Returns the landmark and length of the ith slice in a container.
+fn slice_bounds(landmarks: &[u64], container_len: u64, i: usize) -> io::Result<(u64, u64)> {
+    let landmark = landmarks.get(i).copied().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "missing landmark for slice {}: container has {} landmarks",
+                i,
+                landmarks.len()
+            ),
+        )
+    })?;
+
+    let slice_end = landmarks.get(i + 1).copied().unwrap_or(container_len);
+
+    let slice_len = slice_end.checked_sub(landmark).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid slice bounds: end ({}) < landmark ({})",
+                slice_end, landmark
+            ),
+        )
+    })?;
+
+    Ok((landmark, slice_len))
+}
+
+fn push_index_records(
+    index: &mut crai::Index,
+    compression_header: &CompressionHeader,
+    slice: &Slice,
+    container_position: u64,
+    landmark: u64,
+    slice_len: u64,
+) -> io::Result<()> {
+    let slice_header = slice.header();
+
+    match slice_header.reference_sequence_id() {
+        ReferenceSequenceId::Some(id) => {
+            index.push(crai::Record::new(
+                id,
+                slice_header.alignment_start(),
+                slice_header.alignment_span(),
+                container_position,
+                landmark,
+                slice_len,
+            ));
+        }
+        ReferenceSequenceId::None => {
+            index.push(crai::Record::new(
+                -1,
+                0,
+                0,
+                container_position,
+                landmark,
+                slice_len,
+            ));
+        }
+        ReferenceSequenceId::Many => {
+            let mut reference_sequence_intervals = BTreeMap::new();
+            let mut has_unmapped_records = false;
+
+            for record in slice.records(compression_header)? {
+                match *record.reference_sequence_id() {
+                    Some(id) => {
+                        let start = record.alignment_start();
+                        let end = record.alignment_end();
+
+                        let interval = reference_sequence_intervals
+                            .entry(id)
+                            .or_insert((start, end));
+
+                        interval.0 = interval.0.min(start);
+                        interval.1 = interval.1.max(end);
+                    }
+                    None => has_unmapped_records = true,
+                }
+            }
+
+            for (id, (start, end)) in reference_sequence_intervals {
+                index.push(crai::Record::new(
+                    id,
+                    start,
+                    end - start + 1,
+                    container_position,
+                    landmark,
+                    slice_len,
+                ));
+            }
+
+            if has_unmapped_records {
+                index.push(crai::Record::new(
+                    -1,
+                    0,
+                    0,
+                    container_position,
+                    landmark,
+                    slice_len,
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, SeekFrom};
+
+    use md5::{Digest, Md5};
+    use noodles_fasta as fasta;
+    use noodles_sam::{
+        self as sam,
+        header::{reference_sequence::Md5Checksum, ReferenceSequence},
+    };
+
+    use crate::{reference_sequence_repository::InMemory, Record, Writer};
+
+    use super::*;
+
+    fn build_cram() -> io::Result<Vec<u8>> {
+        let reference_sequence_records = vec![fasta::Record::new(
+            fasta::record::Definition::new(String::from("sq0"), None),
+            b"TTCACCCA".to_vec(),
+        )];
+
+        let mut hasher = Md5::new();
+        hasher.update(reference_sequence_records[0].sequence());
+        let md5_checksum = Md5Checksum::from(<[u8; 16]>::from(hasher.finalize()));
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq0")
+                    .set_length(8)
+                    .set_md5_checksum(md5_checksum)
+                    .build(),
+            )
+            .build();
+
+        let mut writer = Writer::new(Vec::new(), InMemory::new(reference_sequence_records));
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        let record = Record::builder()
+            .set_bam_flags(sam::record::Flags::empty())
+            .set_reference_sequence_id(0.into())
+            .set_alignment_start(2)
+            .set_read_length(4)
+            .set_bases(b"TCAC".to_vec())
+            .set_quality_scores(vec![45, 35, 43, 50])
+            .build();
+
+        writer.write_record(record)?;
+        writer.write_record(Record::default())?;
+
+        writer.try_finish()?;
+
+        Ok(writer.get_ref().clone())
+    }
+
+    #[test]
+    fn test_build_index() -> io::Result<()> {
+        let data = build_cram()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let index = build_index(&mut reader)?;
+
+        assert_eq!(index.len(), 2);
+
+        assert_eq!(index[0].reference_sequence_id(), 0);
+        assert_eq!(index[0].alignment_start(), 2);
+        assert_eq!(index[0].alignment_span(), 4);
+
+        assert_eq!(index[1].reference_sequence_id(), -1);
+        assert_eq!(index[1].alignment_start(), 0);
+        assert_eq!(index[1].alignment_span(), 0);
+
+        assert!(index[0].offset() < index[1].offset());
+
+        // Each record points to a container with a single slice.
+        for record in &index {
+            let mut cursor = Cursor::new(&data);
+            cursor.seek(SeekFrom::Start(record.offset()))?;
+
+            let mut reader = Reader::new(cursor);
+            let container = reader.read_container()?;
+            let header = container.header();
+
+            assert_eq!(header.landmarks(), [record.landmark() as i32]);
+            assert_eq!(header.len() as u64, record.landmark() + record.slice_len());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slice_bounds() -> io::Result<()> {
+        let landmarks = [55, 89];
+
+        assert_eq!(slice_bounds(&landmarks, 144, 0)?, (55, 34));
+        assert_eq!(slice_bounds(&landmarks, 144, 1)?, (89, 55));
+
+        assert!(matches!(
+            slice_bounds(&landmarks, 144, 2),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            slice_bounds(&[89, 55], 144, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            slice_bounds(&landmarks, 21, 1),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -1,18 +1,28 @@
+mod aac;
 mod bit_reader;
 mod bit_writer;
 pub mod container;
 pub mod crai;
 mod data_container;
+mod fqzcomp;
+mod gamma;
+mod golomb;
 mod huffman;
+mod indexer;
+mod name_tokenizer;
 mod num;
 mod rans;
+mod rans_nx16;
 pub mod reader;
 pub mod record;
+pub mod reference_sequence_repository;
+mod subexp;
 pub mod writer;
 
 pub use self::{
     bit_reader::BitReader, bit_writer::BitWriter, container::Container,
-    data_container::DataContainer, reader::Reader, record::Record, writer::Writer,
+    data_container::DataContainer, indexer::index, reader::Reader, record::Record,
+    reference_sequence_repository::ReferenceSequenceRepository, writer::Writer,
 };
 
 static MAGIC_NUMBER: &[u8] = b"CRAM";
@@ -0,0 +1,887 @@
+//! Name tokenizer codec.
+//!
+//! This is the CRAM 3.1 read name codec. Each name is split into tokens, e.g., alphabetic
+//! strings, single characters, and numbers, and each token is encoded relative to the token in
+//! the same position of a previous name. The token types and values are stored in separate
+//! streams, which are each compressed using either rANS Nx16 or adaptive arithmetic coding.
+
+use std::{
+    convert::TryFrom,
+    io::{self, Cursor, Read},
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    aac,
+    num::{read_uint7, write_uint7},
+    rans_nx16,
+};
+
+const TYPE_COUNT: usize = 13;
+
+const NEW_TOKEN_FLAG: u8 = 0x80;
+const DUP_FLAG: u8 = 0x40;
+const TYPE_MASK: u8 = 0x3f;
+
+// Names with more tokens store the remainder of the name as a single alphabetic token.
+const MAX_TOKEN_COUNT: usize = 128;
+
+// The largest number of digits that always fits in a u32.
+const MAX_DIGITS_LEN: usize = 9;
+
+const MAX_DELTA: u32 = 255;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TokenType {
+    Type,
+    Alpha,
+    Char,
+    Digits0,
+    DZLen,
+    Dup,
+    Diff,
+    Digits,
+    Delta,
+    Delta0,
+    Match,
+    Nop,
+    End,
+}
+
+impl TryFrom<u8> for TokenType {
+    type Error = io::Error;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match n {
+            0 => Ok(Self::Type),
+            1 => Ok(Self::Alpha),
+            2 => Ok(Self::Char),
+            3 => Ok(Self::Digits0),
+            4 => Ok(Self::DZLen),
+            5 => Ok(Self::Dup),
+            6 => Ok(Self::Diff),
+            7 => Ok(Self::Digits),
+            8 => Ok(Self::Delta),
+            9 => Ok(Self::Delta0),
+            10 => Ok(Self::Match),
+            11 => Ok(Self::Nop),
+            12 => Ok(Self::End),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid name tokenizer token type: {}", n),
+            )),
+        }
+    }
+}
+
+impl From<TokenType> for u8 {
+    fn from(token_type: TokenType) -> Self {
+        token_type as u8
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Alpha(Vec<u8>),
+    Char(u8),
+    Digits(u32),
+    Digits0(u32, u8),
+    Nop,
+}
+
+impl Token {
+    fn write(&self, dst: &mut Vec<u8>) {
+        match self {
+            Self::Alpha(s) => dst.extend(s),
+            Self::Char(c) => dst.push(*c),
+            Self::Digits(n) => dst.extend(n.to_string().as_bytes()),
+            Self::Digits0(n, width) => {
+                let s = format!("{:0width$}", n, width = usize::from(*width));
+                dst.extend(s.as_bytes());
+            }
+            Self::Nop => {}
+        }
+    }
+}
+
+/// Decodes name tokenizer-encoded read names.
+///
+/// The decoded names are each NUL-terminated.
+pub fn decode<R>(reader: &mut R) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let ulen = reader.read_u32::<LittleEndian>().and_then(|n| {
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })?;
+
+    let name_count = reader.read_u32::<LittleEndian>().and_then(|n| {
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })?;
+
+    let use_arith = reader.read_u8()? != 0;
+
+    let mut streams = read_token_streams(reader, name_count, use_arith)?;
+
+    let mut dst = Vec::with_capacity(ulen);
+    let mut names: Vec<Vec<Token>> = Vec::with_capacity(name_count);
+
+    for n in 0..name_count {
+        let tokens = decode_name(&mut streams, &names, n)?;
+
+        for token in &tokens {
+            token.write(&mut dst);
+        }
+
+        dst.push(0x00);
+
+        names.push(tokens);
+    }
+
+    if dst.len() != ulen {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "name tokenizer length mismatch: expected {}, got {}",
+                ulen,
+                dst.len()
+            ),
+        ));
+    }
+
+    Ok(dst)
+}
+
+fn read_token_streams<R>(
+    reader: &mut R,
+    name_count: usize,
+    use_arith: bool,
+) -> io::Result<Vec<Vec<Cursor<Vec<u8>>>>>
+where
+    R: Read,
+{
+    let mut src = Vec::new();
+    reader.read_to_end(&mut src)?;
+
+    let mut reader = &src[..];
+    let mut streams: Vec<Vec<Cursor<Vec<u8>>>> = Vec::new();
+
+    while !reader.is_empty() {
+        let ttype = reader.read_u8()?;
+        let token_type = TokenType::try_from(ttype & TYPE_MASK)?;
+
+        if ttype & NEW_TOKEN_FLAG != 0 {
+            let mut token_streams = vec![Cursor::new(Vec::new()); TYPE_COUNT];
+
+            // A token without a type stream uses the type of its first stream for every name.
+            if token_type != TokenType::Type {
+                token_streams[usize::from(u8::from(TokenType::Type))] =
+                    Cursor::new(vec![u8::from(token_type); name_count]);
+            }
+
+            streams.push(token_streams);
+        }
+
+        let data = if ttype & DUP_FLAG != 0 {
+            let i = usize::from(reader.read_u8()?);
+            let j = reader.read_u8().and_then(TokenType::try_from)?;
+
+            streams
+                .get(i)
+                .map(|token_streams| token_streams[usize::from(u8::from(j))].get_ref().clone())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "invalid name tokenizer duplicate stream",
+                    )
+                })?
+        } else {
+            let clen = read_uint7(&mut reader).and_then(|n| {
+                usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })?;
+
+            if clen > reader.len() {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+
+            let (mut buf, rest) = reader.split_at(clen);
+            reader = rest;
+
+            if use_arith {
+                aac::decode(&mut buf, 0)?
+            } else {
+                rans_nx16::decode(&mut buf, 0)?
+            }
+        };
+
+        let token_streams = streams.last_mut().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "name tokenizer stream is not part of a token",
+            )
+        })?;
+
+        token_streams[usize::from(u8::from(token_type))] = Cursor::new(data);
+    }
+
+    Ok(streams)
+}
+
+fn get_stream(
+    streams: &mut [Vec<Cursor<Vec<u8>>>],
+    i: usize,
+    token_type: TokenType,
+) -> io::Result<&mut Cursor<Vec<u8>>> {
+    streams
+        .get_mut(i)
+        .map(|token_streams| &mut token_streams[usize::from(u8::from(token_type))])
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("missing name tokenizer token {}", i),
+            )
+        })
+}
+
+fn read_token_type(streams: &mut [Vec<Cursor<Vec<u8>>>], i: usize) -> io::Result<TokenType> {
+    get_stream(streams, i, TokenType::Type)?
+        .read_u8()
+        .and_then(TokenType::try_from)
+}
+
+fn read_string<R>(reader: &mut R) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_u8()? {
+            0x00 => break,
+            b => buf.push(b),
+        }
+    }
+
+    Ok(buf)
+}
+
+fn decode_name(
+    streams: &mut [Vec<Cursor<Vec<u8>>>],
+    names: &[Vec<Token>],
+    n: usize,
+) -> io::Result<Vec<Token>> {
+    let token_type = read_token_type(streams, 0)?;
+
+    let distance = match token_type {
+        TokenType::Dup | TokenType::Diff => get_stream(streams, 0, token_type)?
+            .read_u32::<LittleEndian>()
+            .and_then(|n| {
+                usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })?,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid name tokenizer name type: {:?}", token_type),
+            ))
+        }
+    };
+
+    if distance > n || (token_type == TokenType::Dup && distance == 0) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid name tokenizer name distance",
+        ));
+    }
+
+    let prev_name = if distance > 0 {
+        Some(&names[n - distance])
+    } else {
+        None
+    };
+
+    if token_type == TokenType::Dup {
+        return Ok(prev_name.cloned().unwrap_or_default());
+    }
+
+    let mut tokens = Vec::new();
+
+    for i in 1.. {
+        let token_type = read_token_type(streams, i)?;
+        let prev_token = prev_name.and_then(|tokens| tokens.get(i - 1));
+
+        let token = match token_type {
+            TokenType::End => break,
+            TokenType::Alpha => {
+                read_string(get_stream(streams, i, token_type)?).map(Token::Alpha)?
+            }
+            TokenType::Char => get_stream(streams, i, token_type)?
+                .read_u8()
+                .map(Token::Char)?,
+            TokenType::Digits => get_stream(streams, i, token_type)?
+                .read_u32::<LittleEndian>()
+                .map(Token::Digits)?,
+            TokenType::Digits0 => {
+                let value = get_stream(streams, i, token_type)?.read_u32::<LittleEndian>()?;
+                let width = get_stream(streams, i, TokenType::DZLen)?.read_u8()?;
+                Token::Digits0(value, width)
+            }
+            TokenType::Delta => {
+                let delta = get_stream(streams, i, token_type)?.read_u8()?;
+
+                match prev_token {
+                    Some(Token::Digits(value)) | Some(Token::Digits0(value, _)) => value
+                        .checked_add(u32::from(delta))
+                        .map(Token::Digits)
+                        .ok_or_else(|| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "name tokenizer delta overflow",
+                            )
+                        })?,
+                    _ => return Err(invalid_previous_token(token_type)),
+                }
+            }
+            TokenType::Delta0 => {
+                let delta = get_stream(streams, i, token_type)?.read_u8()?;
+
+                match prev_token {
+                    Some(Token::Digits0(value, width)) => value
+                        .checked_add(u32::from(delta))
+                        .map(|value| Token::Digits0(value, *width))
+                        .ok_or_else(|| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "name tokenizer delta overflow",
+                            )
+                        })?,
+                    _ => return Err(invalid_previous_token(token_type)),
+                }
+            }
+            TokenType::Match => match prev_token {
+                Some(token) => token.clone(),
+                None => return Err(invalid_previous_token(token_type)),
+            },
+            TokenType::Nop => Token::Nop,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid name tokenizer token type: {:?}", token_type),
+                ))
+            }
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn invalid_previous_token(token_type: TokenType) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "name tokenizer {:?} token has no matching previous token",
+            token_type
+        ),
+    )
+}
+
+/// Encodes read names using the name tokenizer.
+///
+/// `src` is a list of names that are each NUL-terminated. The token streams are compressed
+/// using rANS Nx16.
+pub fn encode(src: &[u8]) -> io::Result<Vec<u8>> {
+    let names: Vec<_> = match src.split_last() {
+        Some((0x00, buf)) => buf.split(|&b| b == 0x00).collect(),
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "name is not NUL-terminated",
+            ))
+        }
+        None => Vec::new(),
+    };
+
+    let ulen =
+        u32::try_from(src.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let name_count =
+        u32::try_from(names.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut streams: Vec<Vec<Vec<u8>>> = Vec::new();
+    let mut prev_tokens: Option<Vec<Token>> = None;
+
+    for (n, name) in names.iter().enumerate() {
+        if n > 0 && names[n - 1] == *name {
+            write_token(&mut streams, 0, TokenType::Dup, |dst| {
+                dst.write_u32::<LittleEndian>(1)
+            })?;
+
+            continue;
+        }
+
+        let distance = if n > 0 { 1 } else { 0 };
+
+        write_token(&mut streams, 0, TokenType::Diff, |dst| {
+            dst.write_u32::<LittleEndian>(distance)
+        })?;
+
+        let tokens = tokenize(name);
+
+        for (i, token) in tokens.iter().enumerate() {
+            let prev_token = prev_tokens.as_ref().and_then(|tokens| tokens.get(i));
+            encode_token(&mut streams, i + 1, token, prev_token)?;
+        }
+
+        write_token(&mut streams, tokens.len() + 1, TokenType::End, |_| Ok(()))?;
+
+        prev_tokens = Some(tokens);
+    }
+
+    let mut dst = Vec::new();
+
+    dst.write_u32::<LittleEndian>(ulen)?;
+    dst.write_u32::<LittleEndian>(name_count)?;
+    // use_arith = false
+    dst.write_u8(0)?;
+
+    write_token_streams(&mut dst, &streams)?;
+
+    Ok(dst)
+}
+
+fn tokenize(name: &[u8]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < name.len() {
+        if tokens.len() == MAX_TOKEN_COUNT - 1 {
+            tokens.push(Token::Alpha(name[i..].to_vec()));
+            break;
+        }
+
+        let c = name[i];
+
+        let len = if c.is_ascii_digit() {
+            name[i..].iter().take_while(|b| b.is_ascii_digit()).count()
+        } else if c.is_ascii_alphabetic() {
+            name[i..]
+                .iter()
+                .take_while(|b| b.is_ascii_alphabetic())
+                .count()
+        } else {
+            1
+        };
+
+        let s = &name[i..i + len];
+
+        let token = if c.is_ascii_digit() && len <= MAX_DIGITS_LEN {
+            let value = s.iter().fold(0, |n, &b| n * 10 + u32::from(b - b'0'));
+
+            if len > 1 && c == b'0' {
+                Token::Digits0(value, len as u8)
+            } else {
+                Token::Digits(value)
+            }
+        } else if len == 1 && !c.is_ascii_alphanumeric() {
+            Token::Char(c)
+        } else {
+            Token::Alpha(s.to_vec())
+        };
+
+        tokens.push(token);
+
+        i += len;
+    }
+
+    tokens
+}
+
+fn encode_token(
+    streams: &mut Vec<Vec<Vec<u8>>>,
+    i: usize,
+    token: &Token,
+    prev_token: Option<&Token>,
+) -> io::Result<()> {
+    if prev_token == Some(token) {
+        return write_token(streams, i, TokenType::Match, |_| Ok(()));
+    }
+
+    match (token, prev_token) {
+        (Token::Digits(value), Some(Token::Digits(prev_value)))
+        | (Token::Digits(value), Some(Token::Digits0(prev_value, _)))
+            if value >= prev_value && value - prev_value <= MAX_DELTA =>
+        {
+            let delta = (value - prev_value) as u8;
+            write_token(streams, i, TokenType::Delta, |dst| dst.write_u8(delta))
+        }
+        (Token::Digits0(value, width), Some(Token::Digits0(prev_value, prev_width)))
+            if width == prev_width && value >= prev_value && value - prev_value <= MAX_DELTA =>
+        {
+            let delta = (value - prev_value) as u8;
+            write_token(streams, i, TokenType::Delta0, |dst| dst.write_u8(delta))
+        }
+        (Token::Alpha(s), _) => write_token(streams, i, TokenType::Alpha, |dst| {
+            dst.extend(s);
+            dst.write_u8(0x00)
+        }),
+        (Token::Char(c), _) => write_token(streams, i, TokenType::Char, |dst| dst.write_u8(*c)),
+        (Token::Digits(value), _) => write_token(streams, i, TokenType::Digits, |dst| {
+            dst.write_u32::<LittleEndian>(*value)
+        }),
+        (Token::Digits0(value, width), _) => {
+            write_token(streams, i, TokenType::Digits0, |dst| {
+                dst.write_u32::<LittleEndian>(*value)
+            })?;
+
+            streams[i][usize::from(u8::from(TokenType::DZLen))].write_u8(*width)
+        }
+        (Token::Nop, _) => write_token(streams, i, TokenType::Nop, |_| Ok(())),
+    }
+}
+
+fn write_token<F>(
+    streams: &mut Vec<Vec<Vec<u8>>>,
+    i: usize,
+    token_type: TokenType,
+    f: F,
+) -> io::Result<()>
+where
+    F: FnOnce(&mut Vec<u8>) -> io::Result<()>,
+{
+    if streams.len() <= i {
+        streams.resize(i + 1, vec![Vec::new(); TYPE_COUNT]);
+    }
+
+    let token_streams = &mut streams[i];
+    token_streams[usize::from(u8::from(TokenType::Type))].write_u8(u8::from(token_type))?;
+    f(&mut token_streams[usize::from(u8::from(token_type))])
+}
+
+fn write_token_streams(dst: &mut Vec<u8>, streams: &[Vec<Vec<u8>>]) -> io::Result<()> {
+    let mut written_streams: Vec<(usize, usize)> = Vec::new();
+
+    for (i, token_streams) in streams.iter().enumerate() {
+        let type_stream = &token_streams[usize::from(u8::from(TokenType::Type))];
+
+        // If every name uses the same token type and that type has data, the type stream can
+        // be omitted.
+        let implicit_type = type_stream
+            .first()
+            .filter(|&&t| type_stream.iter().all(|&u| u == t))
+            .map(|&t| usize::from(t))
+            .filter(|&t| !token_streams[t].is_empty());
+
+        let first_type = implicit_type.unwrap_or_else(|| usize::from(u8::from(TokenType::Type)));
+
+        let order = std::iter::once(first_type)
+            .chain((0..TYPE_COUNT).filter(|&t| t != first_type && !token_streams[t].is_empty()));
+
+        for (k, t) in order.enumerate() {
+            let data = &token_streams[t];
+
+            if implicit_type.is_some() && t == usize::from(u8::from(TokenType::Type)) {
+                continue;
+            }
+
+            let dup = if k > 0 {
+                written_streams
+                    .iter()
+                    .find(|&&(j, u)| streams[j][u] == *data)
+                    .copied()
+            } else {
+                None
+            };
+
+            let mut ttype = t as u8;
+
+            if k == 0 {
+                ttype |= NEW_TOKEN_FLAG;
+            }
+
+            if let Some((j, u)) = dup {
+                dst.write_u8(ttype | DUP_FLAG)?;
+                dst.write_u8(j as u8)?;
+                dst.write_u8(u as u8)?;
+            } else {
+                let buf = compress(data)?;
+                dst.write_u8(ttype)?;
+
+                let clen = u32::try_from(buf.len())
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                write_uint7(dst, clen)?;
+
+                dst.extend(buf);
+
+                written_streams.push((i, t));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn compress(src: &[u8]) -> io::Result<Vec<u8>> {
+    let flag_sets = [
+        rans_nx16::Flags::CAT,
+        rans_nx16::Flags::empty(),
+        rans_nx16::Flags::ORDER,
+    ];
+
+    let mut buf: Option<Vec<u8>> = None;
+
+    for &flags in flag_sets.iter() {
+        let data = rans_nx16::encode(flags, src)?;
+
+        if buf.as_ref().map(|b| data.len() < b.len()).unwrap_or(true) {
+            buf = Some(data);
+        }
+    }
+
+    Ok(buf.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize(b"I17_08765:2:123:61541:01763#9"),
+            [
+                Token::Alpha(b"I".to_vec()),
+                Token::Digits(17),
+                Token::Char(b'_'),
+                Token::Digits0(8765, 5),
+                Token::Char(b':'),
+                Token::Digits(2),
+                Token::Char(b':'),
+                Token::Digits(123),
+                Token::Char(b':'),
+                Token::Digits(61541),
+                Token::Char(b':'),
+                Token::Digits0(1763, 5),
+                Token::Char(b'#'),
+                Token::Digits(9),
+            ]
+        );
+
+        assert_eq!(
+            tokenize(b"r12345678901"),
+            [
+                Token::Alpha(b"r".to_vec()),
+                Token::Alpha(b"12345678901".to_vec())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_write() {
+        let mut buf = Vec::new();
+        Token::Digits0(8, 3).write(&mut buf);
+        Token::Char(b':').write(&mut buf);
+        Token::Digits(13).write(&mut buf);
+        assert_eq!(buf, b"008:13");
+    }
+
+    #[test]
+    fn test_encode_round_trip() -> io::Result<()> {
+        let mut many_tokens = Vec::new();
+
+        for i in 0..200 {
+            many_tokens.extend(format!("{}:", i).as_bytes());
+        }
+
+        many_tokens.push(0x00);
+
+        let inputs: Vec<Vec<u8>> = vec![
+            Vec::new(),
+            b"\0".to_vec(),
+            b"r0\0".to_vec(),
+            b"I17_08765:2:123:61541:01763#9\0I17_08765:2:123:1636:08611#9\0I17_08765:2:124:45613:16161#9\0"
+                .to_vec(),
+            b"q0\0q1\0q1\0q2\0q003\0q004\0q9\0q\0q10:ab\0".to_vec(),
+            b"a\0b\0a\0a\0c\0".to_vec(),
+            many_tokens,
+        ];
+
+        for data in inputs {
+            let encoded_data = encode(&data)?;
+            let mut reader = &encoded_data[..];
+            assert_eq!(decode(&mut reader)?, data);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_missing_nul_terminator() {
+        assert!(matches!(
+            encode(b"r0\0r1"),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    // The following streams are built independently of `encode`, directly from the format
+    // described in the CRAM codecs specification.
+    #[test]
+    fn test_decode_with_rans_nx16_streams() -> io::Result<()> {
+        // Token streams compressed with rANS Nx16, including implicit type and duplicate streams
+        let data = [
+            0x77, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x80, 0x06, 0x20, 0x04, 0x06,
+            0x06, 0x05, 0x06, 0x05, 0x1a, 0x00, 0x04, 0x00, 0x01, 0x00, 0x00, 0x98, 0x00, 0x88,
+            0x00, 0x00, 0x0c, 0x02, 0x00, 0x00, 0xa8, 0x00, 0x00, 0x00, 0xa8, 0x00, 0x00, 0x00,
+            0xa8, 0x00, 0x00, 0x06, 0x23, 0x01, 0x0c, 0xc0, 0x00, 0x01, 0x01, 0x00, 0x9a, 0x18,
+            0x82, 0x74, 0x82, 0x74, 0xa0, 0x00, 0x00, 0x01, 0x00, 0x02, 0xd0, 0xe2, 0x00, 0x00,
+            0x18, 0xc7, 0x06, 0x00, 0x2c, 0x48, 0x08, 0x00, 0xd0, 0xe2, 0x00, 0x00, 0x80, 0x81,
+            0x09, 0x04, 0x03, 0x01, 0x0a, 0x00, 0x8a, 0x55, 0x95, 0x2b, 0x08, 0x80, 0x01, 0x00,
+            0xfc, 0xbf, 0x00, 0x00, 0xfc, 0xbf, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x01, 0x04,
+            0x20, 0x02, 0x49, 0x00, 0x80, 0x19, 0x00, 0x03, 0x07, 0x0a, 0x00, 0x8a, 0x55, 0x95,
+            0x2b, 0x08, 0x80, 0x01, 0x00, 0xfc, 0xbf, 0x00, 0x00, 0xfc, 0xbf, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x07, 0x1c, 0x01, 0x04, 0xc0, 0x00, 0x11, 0x00, 0x98, 0x00, 0x88,
+            0x00, 0x00, 0x01, 0x00, 0x0c, 0x02, 0x00, 0x00, 0xa8, 0x00, 0x00, 0x00, 0xa8, 0x00,
+            0x00, 0x00, 0xa8, 0x00, 0x00, 0x82, 0x81, 0x06, 0x04, 0x03, 0x5f, 0x00, 0xa0, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x80, 0x05, 0x20, 0x03, 0x03, 0x0a, 0x0a, 0x03, 0x1c, 0x00, 0x04, 0x00,
+            0x22, 0x3d, 0x00, 0x90, 0x00, 0x88, 0x00, 0x88, 0x00, 0x00, 0x0c, 0x02, 0x00, 0x00,
+            0x08, 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x04, 0x1c, 0x01,
+            0x01, 0xc0, 0x00, 0x05, 0x00, 0x00, 0x00, 0xa0, 0x00, 0x00, 0x01, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x80,
+            0x81, 0x09, 0x04, 0x03, 0x02, 0x0a, 0x00, 0x8a, 0x55, 0x95, 0x2b, 0x08, 0x80, 0x01,
+            0x00, 0xfc, 0xbf, 0x00, 0x00, 0xfc, 0xbf, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x02,
+            0x03, 0x20, 0x01, 0x3a, 0x80, 0x19, 0x00, 0x03, 0x07, 0x0a, 0x00, 0x8a, 0x55, 0x95,
+            0x2b, 0x08, 0x80, 0x01, 0x00, 0xfc, 0xbf, 0x00, 0x00, 0xfc, 0xbf, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x07, 0x1c, 0x01, 0x04, 0xc0, 0x00, 0x02, 0x00, 0x98, 0x00, 0x88,
+            0x00, 0x00, 0x01, 0x00, 0x0c, 0x02, 0x00, 0x00, 0xa8, 0x00, 0x00, 0x00, 0xa8, 0x00,
+            0x00, 0x00, 0xa8, 0x00, 0x00, 0x82, 0x81, 0x06, 0x04, 0x03, 0x3a, 0x00, 0xa0, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x80, 0x05, 0x20, 0x03, 0x07, 0x0a, 0x08, 0x07, 0x19, 0x00, 0x04, 0x00,
+            0x7b, 0x00, 0x98, 0x00, 0x88, 0x00, 0x00, 0x0c, 0x02, 0x00, 0x00, 0xa8, 0x00, 0x00,
+            0x00, 0xa8, 0x00, 0x00, 0x00, 0xa8, 0x00, 0x00, 0x08, 0x1d, 0x01, 0x01, 0xc0, 0x00,
+            0x01, 0x00, 0x00, 0x00, 0x00, 0xa0, 0x00, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0xc2, 0x07, 0x02,
+            0x80, 0x81, 0x0a, 0x04, 0x03, 0x07, 0x08, 0x00, 0x00, 0x95, 0x2b, 0x8a, 0x55, 0xa7,
+            0xba, 0x00, 0x00, 0xb3, 0x8a, 0x01, 0x00, 0xa7, 0xba, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x07, 0x0a, 0x20, 0x08, 0x65, 0xf0, 0x00, 0x00, 0x64, 0x06, 0x00, 0x00, 0x08,
+            0x16, 0x00, 0x01, 0x04, 0x00, 0xa0, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x80, 0x25, 0x01, 0x03, 0xc0,
+            0x00, 0x02, 0x0a, 0x00, 0x00, 0x00, 0xa0, 0x00, 0x00, 0x00, 0x00, 0x01, 0xa0, 0x00,
+            0x00, 0x01, 0xa0, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x42, 0x05, 0x02, 0x80, 0x81, 0x09, 0x04, 0x03,
+            0x03, 0x09, 0x00, 0x95, 0x2b, 0x8a, 0x55, 0xa7, 0xba, 0x00, 0x00, 0xb3, 0x8a, 0x01,
+            0x00, 0xa7, 0xba, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x03, 0x0a, 0x20, 0x08, 0xe3,
+            0x06, 0x00, 0x00, 0xa3, 0x21, 0x00, 0x00, 0x04, 0x16, 0x00, 0x02, 0x05, 0x00, 0xa0,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x49, 0x08, 0x08, 0x80, 0x25, 0x01, 0x03, 0xc0, 0x00, 0x02, 0x0a,
+            0x00, 0x00, 0x00, 0xa0, 0x00, 0x00, 0x00, 0x00, 0x01, 0xa0, 0x00, 0x00, 0x01, 0xa0,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x02, 0x81, 0x06, 0x04, 0x01, 0x23, 0x00, 0xa0, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x80, 0x05, 0x20, 0x03, 0x07, 0x0a, 0x0a, 0x07, 0x19, 0x00, 0x04, 0x00, 0x09, 0x00,
+            0x98, 0x00, 0x88, 0x00, 0x00, 0x0c, 0x02, 0x00, 0x00, 0xa8, 0x00, 0x00, 0x00, 0xa8,
+            0x00, 0x00, 0x00, 0xa8, 0x00, 0x00, 0x80, 0x1e, 0x01, 0x03, 0xc0, 0x00, 0x0c, 0x00,
+            0x00, 0x00, 0xa0, 0x00, 0x00, 0x00, 0xa0, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader)?, &b"I17_08765:2:123:61541:01763#9\0I17_08765:2:123:61545:01764#9\0I17_08765:2:123:61545:01764#9\0I17_08765:2:124:1636:08611#9\0"[..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_arith_streams() -> io::Result<()> {
+        // Token streams compressed with adaptive arithmetic coding
+        let data = [
+            0x0b, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x86, 0x0e, 0x20, 0x0c, 0x00,
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x80, 0x09, 0x00,
+            0x03, 0x0b, 0x00, 0x2e, 0x2f, 0x44, 0x56, 0x59, 0x01, 0x09, 0x01, 0x02, 0x73, 0x00,
+            0xfd, 0xc6, 0x1e, 0xda, 0x00, 0x80, 0x05, 0x20, 0x03, 0x07, 0x08, 0x01, 0x01, 0x09,
+            0x00, 0x02, 0x79, 0x00, 0xfd, 0xe2, 0x61, 0x18, 0x00, 0x07, 0x08, 0x01, 0x04, 0x02,
+            0x00, 0xbe, 0x38, 0xe3, 0x82, 0x08, 0x03, 0x20, 0x01, 0x01, 0x80, 0x09, 0x00, 0x03,
+            0x0d, 0x00, 0xf4, 0x4a, 0x4d, 0x6e, 0x00, 0x02, 0x08, 0x01, 0x01, 0x3b, 0x00, 0xfb,
+            0xa9, 0x38, 0x36, 0x80, 0x03, 0x20, 0x01, 0x0b, 0xc7, 0x02, 0x07, 0x80, 0x08, 0x00,
+            0x01, 0x0d, 0x00, 0xec, 0x4e, 0xc4, 0xe4,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader)?, &b"r1\0r2\0rx:1\0"[..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_invalid_token_type() {
+        let data = [
+            0x02, 0x00, 0x00, 0x00, // ulen = 2
+            0x01, 0x00, 0x00, 0x00, // name count = 1
+            0x00, // use_arith = false
+            0x8d, // new token, type = 13
+        ];
+
+        let mut reader = &data[..];
+
+        assert!(matches!(
+            decode(&mut reader),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
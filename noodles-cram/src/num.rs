@@ -1,9 +1,11 @@
 pub mod itf8;
 mod ltf8;
+mod uint7;
 
 pub use self::{
     itf8::{read_itf8, write_itf8},
     ltf8::{read_ltf8, write_ltf8},
+    uint7::{read_uint7, write_uint7},
 };
 
 pub type Itf8 = i32;
@@ -0,0 +1,114 @@
+use std::io::{self, Read, Write};
+
+use byteorder::{ReadBytesExt, WriteBytesExt};
+
+/// Reads a CRAM codec variable-length unsigned integer (`uint7`).
+///
+/// Each byte holds 7 bits of the value, most significant group first. The high bit of a byte is
+/// set when more bytes follow.
+pub fn read_uint7<R>(reader: &mut R) -> io::Result<u32>
+where
+    R: Read,
+{
+    let mut value: u32 = 0;
+
+    loop {
+        let b = reader.read_u8()?;
+
+        if value > (u32::MAX >> 7) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid uint7: value overflows u32",
+            ));
+        }
+
+        value = (value << 7) | u32::from(b & 0x7f);
+
+        if b & 0x80 == 0 {
+            break;
+        }
+    }
+
+    Ok(value)
+}
+
+pub fn write_uint7<W>(writer: &mut W, value: u32) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut buf = [0; 5];
+    let mut i = buf.len();
+    let mut n = value;
+
+    loop {
+        i -= 1;
+        buf[i] = (n & 0x7f) as u8;
+        n >>= 7;
+
+        if n == 0 {
+            break;
+        }
+    }
+
+    let last = buf.len() - 1;
+
+    for b in &mut buf[i..last] {
+        *b |= 0x80;
+    }
+
+    for &b in &buf[i..] {
+        writer.write_u8(b)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_uint7() -> io::Result<()> {
+        let data = [0x00];
+        let mut reader = &data[..];
+        assert_eq!(read_uint7(&mut reader)?, 0);
+
+        let data = [0x7f];
+        let mut reader = &data[..];
+        assert_eq!(read_uint7(&mut reader)?, 127);
+
+        let data = [0x81, 0x00];
+        let mut reader = &data[..];
+        assert_eq!(read_uint7(&mut reader)?, 128);
+
+        let data = [0x8f, 0xff, 0xff, 0xff, 0x7f];
+        let mut reader = &data[..];
+        assert_eq!(read_uint7(&mut reader)?, u32::MAX);
+
+        let data = [0x9f, 0xff, 0xff, 0xff, 0x7f];
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_uint7(&mut reader),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_uint7() -> io::Result<()> {
+        let mut buf = Vec::new();
+        write_uint7(&mut buf, 0)?;
+        assert_eq!(buf, [0x00]);
+
+        buf.clear();
+        write_uint7(&mut buf, 128)?;
+        assert_eq!(buf, [0x81, 0x00]);
+
+        buf.clear();
+        write_uint7(&mut buf, u32::MAX)?;
+        assert_eq!(buf, [0x8f, 0xff, 0xff, 0xff, 0x7f]);
+
+        Ok(())
+    }
+}
@@ -1,15 +1,19 @@
 use std::{
     convert::TryFrom,
     error, fmt,
-    io::{self, Read},
+    io::{self, Read, Write},
 };
 
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::num::read_itf8;
+use crate::num::{read_itf8, write_itf8, Itf8};
+
+const LOWER_BOUND: u32 = 1 << 23;
+const SCALE_BITS: u32 = 12;
+const TOTAL_FREQ: u32 = 1 << SCALE_BITS;
 
 #[derive(Debug, Eq, PartialEq)]
-struct TryFromByteError(u8);
+pub struct TryFromByteError(u8);
 
 impl fmt::Display for TryFromByteError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -20,7 +24,7 @@ impl fmt::Display for TryFromByteError {
 impl error::Error for TryFromByteError {}
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum Context {
+pub enum Context {
     Order0,
     Order1,
 }
@@ -37,6 +41,15 @@ impl TryFrom<u8> for Context {
     }
 }
 
+impl From<Context> for u8 {
+    fn from(context: Context) -> Self {
+        match context {
+            Context::Order0 => 0,
+            Context::Order1 => 1,
+        }
+    }
+}
+
 pub fn rans_decode<R>(reader: &mut R) -> io::Result<Vec<u8>>
 where
     R: Read,
@@ -197,7 +210,7 @@ where
         } else {
             sym = reader.read_u8()?;
 
-            if sym < 255 && sym == last_sym + 1 {
+            if last_sym < 255 && sym == last_sym + 1 {
                 rle = reader.read_u8()?;
             }
         }
@@ -272,6 +285,265 @@ where
     Ok(())
 }
 
+pub fn rans_encode(context: Context, data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut compressed_data = Vec::new();
+
+    match context {
+        Context::Order0 => rans_encode_0(&mut compressed_data, data)?,
+        Context::Order1 => rans_encode_1(&mut compressed_data, data)?,
+    }
+
+    let compressed_len = u32::try_from(compressed_data.len())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let data_len =
+        u32::try_from(data.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut writer = Vec::with_capacity(9 + compressed_data.len());
+    writer.write_u8(u8::from(context))?;
+    writer.write_u32::<LittleEndian>(compressed_len)?;
+    writer.write_u32::<LittleEndian>(data_len)?;
+    writer.extend(compressed_data);
+
+    Ok(writer)
+}
+
+pub fn normalize_frequencies(freqs: &[u32]) -> Vec<u32> {
+    let mut normalized_freqs = vec![0; 256];
+
+    let sum: u64 = freqs.iter().map(|&f| u64::from(f)).sum();
+
+    if sum == 0 {
+        // The frequency table cannot be empty, so an empty input is given a single symbol.
+        normalized_freqs[0] = TOTAL_FREQ;
+        return normalized_freqs;
+    }
+
+    let mut normalized_sum = 0;
+    let mut max_sym = 0;
+
+    for (sym, (&f, g)) in freqs.iter().zip(normalized_freqs.iter_mut()).enumerate() {
+        if f == 0 {
+            continue;
+        }
+
+        *g = ((u64::from(f) * u64::from(TOTAL_FREQ) / sum) as u32).max(1);
+        normalized_sum += *g;
+
+        if f > freqs[max_sym] {
+            max_sym = sym;
+        }
+    }
+
+    if normalized_sum < TOTAL_FREQ {
+        normalized_freqs[max_sym] += TOTAL_FREQ - normalized_sum;
+    } else {
+        // Rare symbols are rounded up to a frequency of 1, which can overshoot the total. The
+        // excess is taken from the most frequent symbols.
+        let mut excess = normalized_sum - TOTAL_FREQ;
+
+        while excess > 0 {
+            let g = normalized_freqs.iter_mut().max().unwrap();
+            let d = excess.min(*g - 1);
+            *g -= d;
+            excess -= d;
+        }
+    }
+
+    normalized_freqs
+}
+
+fn build_cumulative_frequencies(freqs: &[u32]) -> Vec<u32> {
+    let mut cumulative_freqs = vec![0; 256];
+
+    for i in 0..255 {
+        cumulative_freqs[i + 1] = cumulative_freqs[i] + freqs[i];
+    }
+
+    cumulative_freqs
+}
+
+fn write_symbols<W, F>(writer: &mut W, is_present: &[bool], mut write_entry: F) -> io::Result<()>
+where
+    W: Write,
+    F: FnMut(&mut W, usize) -> io::Result<()>,
+{
+    let mut rle = 0;
+
+    for sym in (0..is_present.len()).filter(|&i| is_present[i]) {
+        if rle > 0 {
+            rle -= 1;
+        } else {
+            writer.write_u8(sym as u8)?;
+
+            if sym > 0 && is_present[sym - 1] {
+                rle = is_present[sym + 1..].iter().take_while(|&&p| p).count();
+                writer.write_u8(rle as u8)?;
+            }
+        }
+
+        write_entry(writer, sym)?;
+    }
+
+    writer.write_u8(0)
+}
+
+fn write_frequencies_0<W>(writer: &mut W, freqs: &[u32]) -> io::Result<()>
+where
+    W: Write,
+{
+    let is_present: Vec<_> = freqs.iter().map(|&f| f > 0).collect();
+
+    write_symbols(writer, &is_present, |w, sym| {
+        write_itf8(w, freqs[sym] as Itf8)
+    })
+}
+
+fn write_frequencies_1<W>(writer: &mut W, freqs: &[Vec<u32>]) -> io::Result<()>
+where
+    W: Write,
+{
+    let is_present: Vec<_> = freqs
+        .iter()
+        .map(|contexts| contexts.iter().any(|&f| f > 0))
+        .collect();
+
+    write_symbols(writer, &is_present, |w, sym| {
+        write_frequencies_0(w, &freqs[sym])
+    })
+}
+
+fn rans_encode_step(buf: &mut Vec<u8>, mut x: u32, c: u32, f: u32) -> u32 {
+    let x_max = ((LOWER_BOUND >> SCALE_BITS) << 8) * f;
+
+    while x >= x_max {
+        buf.push((x & 0xff) as u8);
+        x >>= 8;
+    }
+
+    ((x / f) << SCALE_BITS) + (x % f) + c
+}
+
+fn rans_flush(buf: &mut Vec<u8>, x: u32) {
+    // The output is reversed after encoding, so the state is pushed big-endian to be read as
+    // little-endian.
+    buf.extend(x.to_be_bytes().iter());
+}
+
+fn rans_encode_0<W>(writer: &mut W, data: &[u8]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut raw_freqs = vec![0; 256];
+
+    for &sym in data {
+        raw_freqs[sym as usize] += 1;
+    }
+
+    let freqs = normalize_frequencies(&raw_freqs);
+    let cumulative_freqs = build_cumulative_frequencies(&freqs);
+
+    write_frequencies_0(writer, &freqs)?;
+
+    let mut state = [LOWER_BOUND; 4];
+    let mut buf = Vec::new();
+
+    for (i, &sym) in data.iter().enumerate().rev() {
+        let j = i % 4;
+        let s = sym as usize;
+        state[j] = rans_encode_step(&mut buf, state[j], cumulative_freqs[s], freqs[s]);
+    }
+
+    for &r in state.iter().rev() {
+        rans_flush(&mut buf, r);
+    }
+
+    buf.reverse();
+
+    writer.write_all(&buf)
+}
+
+fn rans_encode_1<W>(writer: &mut W, data: &[u8]) -> io::Result<()>
+where
+    W: Write,
+{
+    // The input is split into 4 interleaved streams. The last stream includes the remainder.
+    let quarter_len = data.len() / 4;
+
+    let streams = [
+        &data[..quarter_len],
+        &data[quarter_len..2 * quarter_len],
+        &data[2 * quarter_len..3 * quarter_len],
+        &data[3 * quarter_len..],
+    ];
+
+    let mut raw_freqs = vec![vec![0; 256]; 256];
+
+    for stream in streams.iter() {
+        let mut last_sym = 0;
+
+        for &sym in stream.iter() {
+            raw_freqs[last_sym as usize][sym as usize] += 1;
+            last_sym = sym;
+        }
+    }
+
+    if data.is_empty() {
+        raw_freqs[0][0] = 1;
+    }
+
+    let freqs: Vec<_> = raw_freqs
+        .iter()
+        .map(|contexts| {
+            if contexts.iter().any(|&f| f > 0) {
+                normalize_frequencies(contexts)
+            } else {
+                vec![0; 256]
+            }
+        })
+        .collect();
+
+    let cumulative_freqs: Vec<_> = freqs
+        .iter()
+        .map(|contexts| build_cumulative_frequencies(contexts))
+        .collect();
+
+    write_frequencies_1(writer, &freqs)?;
+
+    let mut state = [LOWER_BOUND; 4];
+    let mut buf = Vec::new();
+
+    let mut encode_step = |buf: &mut Vec<u8>, j: usize, k: usize| {
+        let stream = streams[j];
+        let last_sym = if k == 0 { 0 } else { stream[k - 1] as usize };
+        let s = stream[k] as usize;
+
+        state[j] = rans_encode_step(
+            buf,
+            state[j],
+            cumulative_freqs[last_sym][s],
+            freqs[last_sym][s],
+        );
+    };
+
+    for k in (quarter_len..streams[3].len()).rev() {
+        encode_step(&mut buf, 3, k);
+    }
+
+    for k in (0..quarter_len).rev() {
+        for j in (0..4).rev() {
+            encode_step(&mut buf, j, k);
+        }
+    }
+
+    for &r in state.iter().rev() {
+        rans_flush(&mut buf, r);
+    }
+
+    buf.reverse();
+
+    writer.write_all(&buf)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,6 +587,81 @@ mod tests {
         Ok(())
     }
 
+    fn build_data() -> Vec<u8> {
+        (0..4099u32)
+            .map(|i| match i % 7 {
+                0..=2 => b'A',
+                3..=4 => b'C',
+                5 => (i % 256) as u8,
+                _ => b'T',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rans_encode_with_order_0() -> io::Result<()> {
+        let data = b"noodles";
+        let encoded_data = rans_encode(Context::Order0, data)?;
+
+        assert_eq!(encoded_data[0], 0);
+        assert_eq!(&encoded_data[5..9], &[0x07, 0x00, 0x00, 0x00]);
+
+        let compressed_len = (encoded_data.len() - 9) as u32;
+        assert_eq!(&encoded_data[1..5], &compressed_len.to_le_bytes());
+
+        let mut reader = &encoded_data[..];
+        assert_eq!(rans_decode(&mut reader)?, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_encode_with_order_1() -> io::Result<()> {
+        let data = b"noodles";
+        let encoded_data = rans_encode(Context::Order1, data)?;
+
+        assert_eq!(encoded_data[0], 1);
+
+        let mut reader = &encoded_data[..];
+        assert_eq!(rans_decode(&mut reader)?, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_encode_round_trip() -> io::Result<()> {
+        let mut inputs: Vec<Vec<u8>> = (0..=9).map(|n| b"ACGTNacgt"[..n].to_vec()).collect();
+        inputs.push((0..=255).collect());
+        inputs.push(build_data());
+
+        for data in &inputs {
+            for &context in &[Context::Order0, Context::Order1] {
+                let encoded_data = rans_encode(context, data)?;
+                let mut reader = &encoded_data[..];
+                assert_eq!(&rans_decode(&mut reader)?, data);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_frequencies() {
+        let mut freqs = vec![0; 256];
+        freqs[0] = 100_000;
+
+        for f in freqs.iter_mut().skip(1) {
+            *f = 1;
+        }
+
+        let normalized_freqs = normalize_frequencies(&freqs);
+        assert_eq!(normalized_freqs.iter().sum::<u32>(), TOTAL_FREQ);
+        assert!(normalized_freqs.iter().all(|&f| f > 0));
+
+        let normalized_freqs = normalize_frequencies(&[0; 256]);
+        assert_eq!(normalized_freqs[0], TOTAL_FREQ);
+    }
+
     mod context {
         use std::convert::TryFrom;
 
@@ -326,5 +673,11 @@ mod tests {
             assert_eq!(Context::try_from(1), Ok(Context::Order1));
             assert_eq!(Context::try_from(2), Err(TryFromByteError(2)));
         }
+
+        #[test]
+        fn test_from_context_for_u8() {
+            assert_eq!(u8::from(Context::Order0), 0);
+            assert_eq!(u8::from(Context::Order1), 1);
+        }
     }
 }
@@ -0,0 +1,1292 @@
+//! rANS Nx16 codec.
+//!
+//! This is the rANS codec introduced in CRAM 3.1. Compared to rANS 4x8, it renormalizes 16 bits
+//! at a time, can interleave 32 states, and has optional stripe, pack, and run-length transforms.
+
+mod flags;
+
+pub use self::flags::Flags;
+
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    io::{self, Read},
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    num::{read_uint7, write_uint7},
+    rans::normalize_frequencies,
+};
+
+const LOWER_BOUND: u32 = 1 << 15;
+const SHIFT: u32 = 12;
+const STRIPE_COUNT: usize = 4;
+
+/// Decodes rANS Nx16-encoded data.
+///
+/// `len` is the uncompressed size and is only used when the stream does not store it.
+pub fn decode<R>(reader: &mut R, mut len: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let flags = reader.read_u8().and_then(|b| {
+        Flags::from_bits(b)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid rANS Nx16 flags"))
+    })?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        len = read_len(reader)?;
+    }
+
+    if flags.contains(Flags::STRIPE) {
+        return decode_stripe(reader, len, |src, len| decode(src, len));
+    }
+
+    let state_count = if flags.contains(Flags::N32) { 32 } else { 4 };
+
+    let pack_meta = if flags.contains(Flags::PACK) {
+        let (symbols, packed_len) = read_pack_meta(reader)?;
+        let meta = (symbols, len);
+        len = packed_len;
+        Some(meta)
+    } else {
+        None
+    };
+
+    let rle_meta = if flags.contains(Flags::RLE) {
+        let (meta, literals_len) = read_rle_meta(reader)?;
+        let meta = (meta, len);
+        len = literals_len;
+        Some(meta)
+    } else {
+        None
+    };
+
+    let mut dst = if flags.contains(Flags::CAT) {
+        let mut buf = vec![0; len];
+        reader.read_exact(&mut buf)?;
+        buf
+    } else if flags.contains(Flags::ORDER) {
+        decode_order_1(reader, len, state_count)?
+    } else {
+        decode_order_0(reader, len, state_count)?
+    };
+
+    if let Some((meta, len)) = rle_meta {
+        dst = decode_rle(&dst, &meta, len)?;
+    }
+
+    if let Some((symbols, len)) = pack_meta {
+        dst = decode_pack(&dst, &symbols, len)?;
+    }
+
+    Ok(dst)
+}
+
+/// Encodes data using rANS Nx16.
+///
+/// The pack and RLE transforms are skipped when they do not apply to the given data, and empty
+/// data is always stored uncompressed.
+pub fn encode(mut flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dst = Vec::new();
+
+    if flags.contains(Flags::STRIPE) {
+        let stream_flags = (flags - Flags::STRIPE) | Flags::NO_SIZE;
+        dst.write_u8((flags & (Flags::STRIPE | Flags::NO_SIZE)).bits())?;
+
+        if !flags.contains(Flags::NO_SIZE) {
+            write_len(&mut dst, src.len())?;
+        }
+
+        encode_stripe(&mut dst, src, |buf| encode(stream_flags, buf))?;
+
+        return Ok(dst);
+    }
+
+    let state_count = if flags.contains(Flags::N32) { 32 } else { 4 };
+
+    let mut meta = Vec::new();
+    let mut data = Cow::from(src);
+
+    if flags.contains(Flags::PACK) {
+        match encode_pack(&data)? {
+            Some((pack_meta, packed_data)) => {
+                meta.extend(pack_meta);
+                data = Cow::from(packed_data);
+            }
+            None => flags.remove(Flags::PACK),
+        }
+    }
+
+    if flags.contains(Flags::RLE) {
+        match encode_rle(&data)? {
+            Some((rle_meta, literals)) => {
+                meta.extend(rle_meta);
+                data = Cow::from(literals);
+            }
+            None => flags.remove(Flags::RLE),
+        }
+    }
+
+    if data.is_empty() {
+        flags.insert(Flags::CAT);
+    }
+
+    dst.write_u8(flags.bits())?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        write_len(&mut dst, src.len())?;
+    }
+
+    dst.extend(meta);
+
+    if flags.contains(Flags::CAT) {
+        dst.extend(data.iter());
+    } else if flags.contains(Flags::ORDER) {
+        encode_order_1(&mut dst, &data, state_count)?;
+    } else {
+        encode_order_0(&mut dst, &data, state_count)?;
+    }
+
+    Ok(dst)
+}
+
+pub(crate) fn read_len<R>(reader: &mut R) -> io::Result<usize>
+where
+    R: Read,
+{
+    read_uint7(reader)
+        .and_then(|n| usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+pub(crate) fn write_len(dst: &mut Vec<u8>, len: usize) -> io::Result<()> {
+    let n = u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    write_uint7(dst, n)
+}
+
+pub(crate) fn decode_stripe<R, F>(reader: &mut R, len: usize, decode: F) -> io::Result<Vec<u8>>
+where
+    R: Read,
+    F: Fn(&mut &[u8], usize) -> io::Result<Vec<u8>>,
+{
+    let stream_count = usize::from(reader.read_u8()?);
+
+    if stream_count == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid stripe stream count",
+        ));
+    }
+
+    let compressed_lens = (0..stream_count)
+        .map(|_| read_len(reader))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut dst = vec![0; len];
+
+    for (i, &compressed_len) in compressed_lens.iter().enumerate() {
+        let stream_len = len / stream_count + usize::from(len % stream_count > i);
+
+        let mut buf = vec![0; compressed_len];
+        reader.read_exact(&mut buf)?;
+
+        let stream = decode(&mut &buf[..], stream_len)?;
+
+        if stream.len() != stream_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stripe stream length mismatch",
+            ));
+        }
+
+        for (d, s) in dst.iter_mut().skip(i).step_by(stream_count).zip(stream) {
+            *d = s;
+        }
+    }
+
+    Ok(dst)
+}
+
+pub(crate) fn encode_stripe<F>(dst: &mut Vec<u8>, src: &[u8], encode: F) -> io::Result<()>
+where
+    F: Fn(&[u8]) -> io::Result<Vec<u8>>,
+{
+    dst.write_u8(STRIPE_COUNT as u8)?;
+
+    let streams = (0..STRIPE_COUNT)
+        .map(|i| {
+            let stream: Vec<_> = src.iter().skip(i).step_by(STRIPE_COUNT).copied().collect();
+            encode(&stream)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    for stream in &streams {
+        write_len(dst, stream.len())?;
+    }
+
+    for stream in streams {
+        dst.extend(stream);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn read_pack_meta<R>(reader: &mut R) -> io::Result<(Vec<u8>, usize)>
+where
+    R: Read,
+{
+    let symbol_count = match reader.read_u8()? {
+        0 => 256,
+        n => usize::from(n),
+    };
+
+    let mut symbols = vec![0; symbol_count];
+    reader.read_exact(&mut symbols)?;
+
+    let packed_len = read_len(reader)?;
+
+    Ok((symbols, packed_len))
+}
+
+fn bits_per_packed_symbol(symbol_count: usize) -> usize {
+    match symbol_count {
+        0 | 1 => 0,
+        2 => 1,
+        3..=4 => 2,
+        5..=16 => 4,
+        _ => 8,
+    }
+}
+
+pub(crate) fn decode_pack(src: &[u8], symbols: &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let bits = bits_per_packed_symbol(symbols.len());
+
+    if bits == 0 {
+        return Ok(vec![symbols[0]; len]);
+    }
+
+    let symbols_per_byte = 8 / bits;
+    let mask = ((1u16 << bits) - 1) as u8;
+
+    let mut dst = Vec::with_capacity(len);
+
+    for i in 0..len {
+        let b = src.get(i / symbols_per_byte).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "unexpected end of packed data")
+        })?;
+
+        let j = (b >> ((i % symbols_per_byte) * bits)) & mask;
+
+        let sym = symbols
+            .get(usize::from(j))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid packed symbol"))?;
+
+        dst.push(*sym);
+    }
+
+    Ok(dst)
+}
+
+pub(crate) fn encode_pack(src: &[u8]) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let mut is_present = [false; 256];
+
+    for &sym in src {
+        is_present[usize::from(sym)] = true;
+    }
+
+    let symbols: Vec<u8> = (0..=255).filter(|&i| is_present[usize::from(i)]).collect();
+
+    if symbols.is_empty() || symbols.len() > 16 {
+        return Ok(None);
+    }
+
+    let mut map = [0; 256];
+
+    for (i, &sym) in symbols.iter().enumerate() {
+        map[usize::from(sym)] = i as u8;
+    }
+
+    let mut meta = vec![symbols.len() as u8];
+    meta.extend(&symbols);
+
+    let bits = bits_per_packed_symbol(symbols.len());
+
+    if bits == 0 {
+        write_len(&mut meta, 0)?;
+        return Ok(Some((meta, Vec::new())));
+    }
+
+    let symbols_per_byte = 8 / bits;
+
+    let packed_data: Vec<u8> = src
+        .chunks(symbols_per_byte)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |b, (i, &sym)| b | (map[usize::from(sym)] << (i * bits)))
+        })
+        .collect();
+
+    write_len(&mut meta, packed_data.len())?;
+
+    Ok(Some((meta, packed_data)))
+}
+
+fn read_rle_meta<R>(reader: &mut R) -> io::Result<(Vec<u8>, usize)>
+where
+    R: Read,
+{
+    let meta_len = read_len(reader)?;
+    let literals_len = read_len(reader)?;
+
+    // The lowest bit of the meta length marks whether the meta is stored uncompressed.
+    let meta = if meta_len & 0x01 == 1 {
+        let mut buf = vec![0; meta_len >> 1];
+        reader.read_exact(&mut buf)?;
+        buf
+    } else {
+        let compressed_len = read_len(reader)?;
+        let mut buf = vec![0; compressed_len];
+        reader.read_exact(&mut buf)?;
+        decode_order_0(&mut &buf[..], meta_len >> 1, 4)?
+    };
+
+    Ok((meta, literals_len))
+}
+
+fn decode_rle(src: &[u8], meta: &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let mut reader = meta;
+
+    let symbol_count = match reader.read_u8()? {
+        0 => 256,
+        n => usize::from(n),
+    };
+
+    let mut is_rle_symbol = [false; 256];
+
+    for _ in 0..symbol_count {
+        let sym = reader.read_u8()?;
+        is_rle_symbol[usize::from(sym)] = true;
+    }
+
+    let mut dst = Vec::with_capacity(len);
+
+    for &sym in src {
+        if is_rle_symbol[usize::from(sym)] {
+            let run_len = read_len(&mut reader)?;
+
+            if dst.len() + run_len >= len {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "RLE run exceeds data length",
+                ));
+            }
+
+            dst.resize(dst.len() + run_len + 1, sym);
+        } else {
+            dst.push(sym);
+        }
+    }
+
+    if dst.len() != len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "RLE data length mismatch",
+        ));
+    }
+
+    Ok(dst)
+}
+
+fn runs(src: &[u8]) -> Vec<(u8, usize)> {
+    let mut runs: Vec<(u8, usize)> = Vec::new();
+
+    for &sym in src {
+        match runs.last_mut() {
+            Some((last_sym, len)) if *last_sym == sym => *len += 1,
+            _ => runs.push((sym, 1)),
+        }
+    }
+
+    runs
+}
+
+fn encode_rle(src: &[u8]) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let runs = runs(src);
+
+    // Each run of an RLE symbol saves its repeated bytes but costs a run length.
+    let mut scores = [0i64; 256];
+
+    for &(sym, len) in &runs {
+        scores[usize::from(sym)] += len as i64 - 2;
+    }
+
+    let symbols: Vec<u8> = (0..=255).filter(|&i| scores[usize::from(i)] > 0).collect();
+
+    if symbols.is_empty() {
+        return Ok(None);
+    }
+
+    let mut is_rle_symbol = [false; 256];
+
+    for &sym in &symbols {
+        is_rle_symbol[usize::from(sym)] = true;
+    }
+
+    let mut meta = vec![symbols.len() as u8];
+    meta.extend(&symbols);
+
+    let mut literals = Vec::new();
+
+    for (sym, len) in runs {
+        if is_rle_symbol[usize::from(sym)] {
+            literals.push(sym);
+            write_len(&mut meta, len - 1)?;
+        } else {
+            literals.resize(literals.len() + len, sym);
+        }
+    }
+
+    let mut header = Vec::new();
+    write_len(&mut header, (meta.len() << 1) | 0x01)?;
+    write_len(&mut header, literals.len())?;
+    header.extend(meta);
+
+    Ok(Some((header, literals)))
+}
+
+fn read_alphabet<R>(reader: &mut R) -> io::Result<[bool; 256]>
+where
+    R: Read,
+{
+    let mut alphabet = [false; 256];
+
+    let mut sym = reader.read_u8()?;
+    let mut last_sym = sym;
+    let mut rle = 0;
+
+    loop {
+        alphabet[usize::from(sym)] = true;
+
+        if rle > 0 {
+            rle -= 1;
+            sym = sym.checked_add(1).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid alphabet run")
+            })?;
+        } else {
+            sym = reader.read_u8()?;
+
+            if last_sym < 255 && sym == last_sym + 1 {
+                rle = reader.read_u8()?;
+            }
+        }
+
+        last_sym = sym;
+
+        if sym == 0 {
+            break;
+        }
+    }
+
+    Ok(alphabet)
+}
+
+fn write_alphabet(dst: &mut Vec<u8>, alphabet: &[bool]) -> io::Result<()> {
+    let mut rle = 0;
+
+    for sym in (0..alphabet.len()).filter(|&i| alphabet[i]) {
+        if rle > 0 {
+            rle -= 1;
+            continue;
+        }
+
+        dst.write_u8(sym as u8)?;
+
+        if sym > 0 && alphabet[sym - 1] {
+            rle = alphabet[sym + 1..].iter().take_while(|&&p| p).count();
+            dst.write_u8(rle as u8)?;
+        }
+    }
+
+    dst.write_u8(0)
+}
+
+fn normalize_frequencies_to_shift(freqs: &mut [u32], shift: u32) -> io::Result<()> {
+    let total: u64 = freqs.iter().map(|&f| u64::from(f)).sum();
+    let max_total = 1 << shift;
+
+    if total == 0 || total == max_total {
+        return Ok(());
+    }
+
+    let mut n = 0;
+
+    while (total << n) < max_total {
+        n += 1;
+    }
+
+    if total << n != max_total {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid frequency total",
+        ));
+    }
+
+    for f in freqs.iter_mut() {
+        *f <<= n;
+    }
+
+    Ok(())
+}
+
+fn build_cumulative_frequencies(freqs: &[u32]) -> Vec<u32> {
+    let mut cumulative_freqs = vec![0; 257];
+
+    for (i, &f) in freqs.iter().enumerate() {
+        cumulative_freqs[i + 1] = cumulative_freqs[i] + f;
+    }
+
+    cumulative_freqs
+}
+
+fn build_symbol_table(cumulative_freqs: &[u32], shift: u32) -> io::Result<Vec<u8>> {
+    if cumulative_freqs[256] != 1 << shift {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid frequency total",
+        ));
+    }
+
+    let mut table = vec![0; 1 << shift];
+
+    for (sym, window) in cumulative_freqs.windows(2).enumerate() {
+        for t in &mut table[window[0] as usize..window[1] as usize] {
+            *t = sym as u8;
+        }
+    }
+
+    Ok(table)
+}
+
+fn read_states<R>(reader: &mut R, state_count: usize) -> io::Result<Vec<u32>>
+where
+    R: Read,
+{
+    (0..state_count)
+        .map(|_| reader.read_u32::<LittleEndian>())
+        .collect()
+}
+
+fn renormalize<R>(reader: &mut R, mut x: u32) -> io::Result<u32>
+where
+    R: Read,
+{
+    if x < LOWER_BOUND {
+        x = (x << 16) | u32::from(reader.read_u16::<LittleEndian>()?);
+    }
+
+    Ok(x)
+}
+
+fn decode_step<R>(
+    reader: &mut R,
+    x: u32,
+    cumulative_freqs: &[u32],
+    freqs: &[u32],
+    table: &[u8],
+    shift: u32,
+) -> io::Result<(u8, u32)>
+where
+    R: Read,
+{
+    let m = x & ((1 << shift) - 1);
+    let sym = table[m as usize];
+    let s = usize::from(sym);
+
+    let x = freqs[s] * (x >> shift) + m - cumulative_freqs[s];
+    let x = renormalize(reader, x)?;
+
+    Ok((sym, x))
+}
+
+fn read_frequencies_0<R>(reader: &mut R) -> io::Result<Vec<u32>>
+where
+    R: Read,
+{
+    let alphabet = read_alphabet(reader)?;
+    let mut freqs = vec![0; 256];
+
+    for (f, _) in freqs.iter_mut().zip(alphabet.iter()).filter(|(_, &p)| p) {
+        *f = read_uint7(reader)?;
+    }
+
+    normalize_frequencies_to_shift(&mut freqs, SHIFT)?;
+
+    Ok(freqs)
+}
+
+fn decode_order_0<R>(reader: &mut R, len: usize, state_count: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let freqs = read_frequencies_0(reader)?;
+    let cumulative_freqs = build_cumulative_frequencies(&freqs);
+    let table = build_symbol_table(&cumulative_freqs, SHIFT)?;
+
+    let mut states = read_states(reader, state_count)?;
+    let mut dst = vec![0; len];
+
+    for (i, d) in dst.iter_mut().enumerate() {
+        let j = i % state_count;
+
+        let (sym, x) = decode_step(reader, states[j], &cumulative_freqs, &freqs, &table, SHIFT)?;
+
+        *d = sym;
+        states[j] = x;
+    }
+
+    Ok(dst)
+}
+
+fn read_frequencies_1<R>(reader: &mut R) -> io::Result<(Vec<Vec<u32>>, u32)>
+where
+    R: Read,
+{
+    let b = reader.read_u8()?;
+    let shift = u32::from(b >> 4);
+
+    if shift == 0 || shift > SHIFT {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid order-1 frequency shift",
+        ));
+    }
+
+    // The lowest bit marks whether the frequency table is itself compressed (using order-0).
+    let freqs = if b & 0x01 == 1 {
+        let uncompressed_len = read_len(reader)?;
+        let compressed_len = read_len(reader)?;
+
+        let mut buf = vec![0; compressed_len];
+        reader.read_exact(&mut buf)?;
+
+        let table = decode_order_0(&mut &buf[..], uncompressed_len, 4)?;
+        read_frequencies_1_table(&mut &table[..], shift)?
+    } else {
+        read_frequencies_1_table(reader, shift)?
+    };
+
+    Ok((freqs, shift))
+}
+
+fn read_frequencies_1_table<R>(reader: &mut R, shift: u32) -> io::Result<Vec<Vec<u32>>>
+where
+    R: Read,
+{
+    let alphabet = read_alphabet(reader)?;
+    let mut freqs = vec![vec![0; 256]; 256];
+
+    for (row, _) in freqs.iter_mut().zip(alphabet.iter()).filter(|(_, &p)| p) {
+        let mut zero_run = 0;
+
+        for (f, _) in row.iter_mut().zip(alphabet.iter()).filter(|(_, &p)| p) {
+            if zero_run > 0 {
+                zero_run -= 1;
+                continue;
+            }
+
+            *f = read_uint7(reader)?;
+
+            if *f == 0 {
+                zero_run = reader.read_u8()?;
+            }
+        }
+
+        normalize_frequencies_to_shift(row, shift)?;
+    }
+
+    Ok(freqs)
+}
+
+fn decode_order_1<R>(reader: &mut R, len: usize, state_count: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let (freqs, shift) = read_frequencies_1(reader)?;
+
+    let cumulative_freqs: Vec<_> = freqs
+        .iter()
+        .map(|row| build_cumulative_frequencies(row))
+        .collect();
+
+    // Contexts that are never used have no frequencies.
+    let tables: Vec<_> = cumulative_freqs
+        .iter()
+        .map(|row| build_symbol_table(row, shift).ok())
+        .collect();
+
+    let mut states = read_states(reader, state_count)?;
+    let mut last_syms = vec![0; state_count];
+
+    let mut decode_symbol = |reader: &mut R, j: usize| -> io::Result<u8> {
+        let ctx = usize::from(last_syms[j]);
+
+        let table = tables[ctx]
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing order-1 context"))?;
+
+        let (sym, x) = decode_step(
+            reader,
+            states[j],
+            &cumulative_freqs[ctx],
+            &freqs[ctx],
+            table,
+            shift,
+        )?;
+
+        states[j] = x;
+        last_syms[j] = sym;
+
+        Ok(sym)
+    };
+
+    let segment_len = len / state_count;
+    let mut dst = vec![0; len];
+
+    for i in 0..segment_len {
+        for j in 0..state_count {
+            dst[i + j * segment_len] = decode_symbol(reader, j)?;
+        }
+    }
+
+    // The last state decodes the remainder.
+    for d in dst.iter_mut().skip(state_count * segment_len) {
+        *d = decode_symbol(reader, state_count - 1)?;
+    }
+
+    Ok(dst)
+}
+
+fn encode_step(buf: &mut Vec<u8>, mut x: u32, c: u32, f: u32, shift: u32) -> u32 {
+    let x_max = ((LOWER_BOUND >> shift) << 16) * f;
+
+    if x >= x_max {
+        // The output is reversed after encoding, so the 16-bit word is pushed big-endian to be
+        // read as little-endian.
+        buf.push((x >> 8) as u8);
+        buf.push(x as u8);
+        x >>= 16;
+    }
+
+    ((x / f) << shift) + (x % f) + c
+}
+
+fn write_states(dst: &mut Vec<u8>, states: &[u32], mut buf: Vec<u8>) {
+    for &x in states.iter().rev() {
+        buf.extend(x.to_be_bytes().iter());
+    }
+
+    buf.reverse();
+    dst.extend(buf);
+}
+
+fn write_frequencies_0(dst: &mut Vec<u8>, freqs: &[u32]) -> io::Result<()> {
+    let alphabet: Vec<_> = freqs.iter().map(|&f| f > 0).collect();
+    write_alphabet(dst, &alphabet)?;
+
+    for &f in freqs.iter().filter(|&&f| f > 0) {
+        write_uint7(dst, f)?;
+    }
+
+    Ok(())
+}
+
+fn encode_order_0(dst: &mut Vec<u8>, src: &[u8], state_count: usize) -> io::Result<()> {
+    let mut raw_freqs = vec![0; 256];
+
+    for &sym in src {
+        raw_freqs[usize::from(sym)] += 1;
+    }
+
+    let freqs = normalize_frequencies(&raw_freqs);
+    let cumulative_freqs = build_cumulative_frequencies(&freqs);
+
+    write_frequencies_0(dst, &freqs)?;
+
+    let mut states = vec![LOWER_BOUND; state_count];
+    let mut buf = Vec::new();
+
+    for (i, &sym) in src.iter().enumerate().rev() {
+        let j = i % state_count;
+        let s = usize::from(sym);
+        states[j] = encode_step(&mut buf, states[j], cumulative_freqs[s], freqs[s], SHIFT);
+    }
+
+    write_states(dst, &states, buf);
+
+    Ok(())
+}
+
+fn write_frequencies_1(dst: &mut Vec<u8>, alphabet: &[bool], freqs: &[Vec<u32>]) -> io::Result<()> {
+    // uncompressed table
+    dst.write_u8((SHIFT << 4) as u8)?;
+
+    write_alphabet(dst, alphabet)?;
+
+    let symbols: Vec<_> = (0..alphabet.len()).filter(|&i| alphabet[i]).collect();
+
+    for &ctx in &symbols {
+        let row = &freqs[ctx];
+        let mut i = 0;
+
+        while i < symbols.len() {
+            let f = row[symbols[i]];
+            write_uint7(dst, f)?;
+            i += 1;
+
+            if f == 0 {
+                let zero_run = symbols[i..]
+                    .iter()
+                    .take_while(|&&sym| row[sym] == 0)
+                    .take(255)
+                    .count();
+
+                dst.write_u8(zero_run as u8)?;
+                i += zero_run;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn encode_order_1(dst: &mut Vec<u8>, src: &[u8], state_count: usize) -> io::Result<()> {
+    let segment_len = src.len() / state_count;
+
+    // The last segment includes the remainder.
+    let segments: Vec<_> = (0..state_count)
+        .map(|j| {
+            let start = j * segment_len;
+            let end = if j == state_count - 1 {
+                src.len()
+            } else {
+                start + segment_len
+            };
+
+            &src[start..end]
+        })
+        .collect();
+
+    let mut raw_freqs = vec![vec![0; 256]; 256];
+
+    for segment in &segments {
+        let mut last_sym = 0;
+
+        for &sym in segment.iter() {
+            raw_freqs[usize::from(last_sym)][usize::from(sym)] += 1;
+            last_sym = sym;
+        }
+    }
+
+    // Every segment starts with a context of 0.
+    let mut alphabet = vec![false; 256];
+    alphabet[0] = true;
+
+    for &sym in src {
+        alphabet[usize::from(sym)] = true;
+    }
+
+    let freqs: Vec<_> = raw_freqs
+        .iter()
+        .map(|row| {
+            if row.iter().any(|&f| f > 0) {
+                normalize_frequencies(row)
+            } else {
+                vec![0; 256]
+            }
+        })
+        .collect();
+
+    let cumulative_freqs: Vec<_> = freqs
+        .iter()
+        .map(|row| build_cumulative_frequencies(row))
+        .collect();
+
+    write_frequencies_1(dst, &alphabet, &freqs)?;
+
+    let mut states = vec![LOWER_BOUND; state_count];
+    let mut buf = Vec::new();
+
+    let mut encode_symbol = |buf: &mut Vec<u8>, j: usize, k: usize| {
+        let segment = segments[j];
+        let ctx = if k == 0 {
+            0
+        } else {
+            usize::from(segment[k - 1])
+        };
+        let s = usize::from(segment[k]);
+
+        states[j] = encode_step(
+            buf,
+            states[j],
+            cumulative_freqs[ctx][s],
+            freqs[ctx][s],
+            SHIFT,
+        );
+    };
+
+    for k in (segment_len..segments[state_count - 1].len()).rev() {
+        encode_symbol(&mut buf, state_count - 1, k);
+    }
+
+    for k in (0..segment_len).rev() {
+        for j in (0..state_count).rev() {
+            encode_symbol(&mut buf, j, k);
+        }
+    }
+
+    write_states(dst, &states, buf);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_data() -> Vec<u8> {
+        (0..1031u32)
+            .map(|i| match i % 11 {
+                0..=4 => b'A',
+                5..=7 => b'C',
+                8 => (i % 256) as u8,
+                _ => b'T',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_with_order_0() -> io::Result<()> {
+        // A single symbol with a frequency of 1 is normalized to 4096, which leaves the states
+        // unchanged.
+        let data = [
+            0x00, // flags
+            0x04, // len
+            b'a', 0x00, // alphabet
+            0x01, // frequencies
+            0x00, 0x80, 0x00, 0x00, // states
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"aaaa");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_cat() -> io::Result<()> {
+        let data = [0x20, 0x03, b'n', b'd', b'l'];
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"ndl");
+
+        let data = [0x30, b'n', b'd', b'l'];
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 3)?, b"ndl");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_pack() -> io::Result<()> {
+        // flags (PACK | CAT), len, symbol count, symbols, packed len, packed data
+        let data = [0xa0, 0x05, 0x02, b'A', b'C', 0x01, 0b0001_0110];
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"ACCAC");
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_rle() -> io::Result<()> {
+        // flags (RLE | CAT), len, meta len (uncompressed), literals len, meta, literals
+        let data = [0x60, 0x06, 0x07, 0x03, 0x01, b'A', 0x03, b'A', b'C', b'G'];
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"AAAACG");
+        Ok(())
+    }
+
+    // The following streams are built independently of `encode`, directly from the format
+    // described in the CRAM codecs specification.
+    #[test]
+    fn test_decode_with_order_0_n32() -> io::Result<()> {
+        // flags (N32), len, alphabet, frequencies, 32 states, renormalization words
+        let data = [
+            0x04, 0x2c, 0x61, 0x62, 0x02, 0x72, 0x00, 0x8e, 0x48, 0x85, 0x68, 0x82, 0x74, 0x82,
+            0x74, 0x85, 0x68, 0xd0, 0x56, 0x02, 0x00, 0x68, 0xf7, 0x05, 0x00, 0x60, 0x4f, 0x0f,
+            0x00, 0xa8, 0x23, 0x06, 0x00, 0x50, 0xea, 0x0b, 0x00, 0xc0, 0x25, 0x0c, 0x00, 0xc4,
+            0xeb, 0x0b, 0x00, 0x34, 0x27, 0x0c, 0x00, 0x68, 0xf7, 0x05, 0x00, 0x60, 0x4f, 0x0f,
+            0x00, 0xa8, 0x23, 0x06, 0x00, 0xd0, 0x56, 0x02, 0x00, 0x68, 0xc7, 0x02, 0x00, 0x38,
+            0xcd, 0x02, 0x00, 0x38, 0x14, 0x01, 0x00, 0x50, 0x8a, 0x05, 0x00, 0x38, 0x14, 0x01,
+            0x00, 0xc4, 0x8b, 0x05, 0x00, 0x38, 0x14, 0x01, 0x00, 0x68, 0xc7, 0x02, 0x00, 0x38,
+            0xcd, 0x02, 0x00, 0x38, 0x14, 0x01, 0x00, 0x38, 0x14, 0x01, 0x00, 0x68, 0xc7, 0x02,
+            0x00, 0x38, 0xcd, 0x02, 0x00, 0x38, 0x14, 0x01, 0x00, 0x50, 0x8a, 0x05, 0x00, 0x38,
+            0x14, 0x01, 0x00, 0xc4, 0x8b, 0x05, 0x00, 0x38, 0x14, 0x01, 0x00, 0x68, 0xc7, 0x02,
+            0x00, 0x38, 0xcd, 0x02, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(
+            decode(&mut reader, 0)?,
+            b"abracadabraabracadabraabracadabraabracadabra"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_order_1() -> io::Result<()> {
+        // flags (ORDER), len, shift (12), alphabet, frequencies, states, renormalization words
+        let data = [
+            0x01, 0x17, 0xc0, 0x00, 0x20, 0x61, 0x62, 0x02, 0x72, 0x00, 0x00, 0x01, 0xa0, 0x00,
+            0x00, 0x03, 0x00, 0x01, 0xa0, 0x00, 0x00, 0x03, 0x00, 0x00, 0x83, 0x47, 0x00, 0x00,
+            0x8e, 0x1d, 0x87, 0x0e, 0x87, 0x0e, 0x00, 0x00, 0x00, 0x05, 0xa0, 0x00, 0x00, 0x01,
+            0xa0, 0x00, 0x00, 0x03, 0x00, 0x01, 0xa0, 0x00, 0x00, 0x03, 0x00, 0x01, 0xa0, 0x00,
+            0x00, 0x03, 0x69, 0x23, 0x05, 0x00, 0x7a, 0xfc, 0x04, 0x00, 0x08, 0xe0, 0x09, 0x00,
+            0x3c, 0x79, 0x16, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"abracadabra abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_order_1_n32() -> io::Result<()> {
+        // flags (ORDER | N32), len, shift (12), alphabet, frequencies, 32 states, renormalization words
+        let data = [
+            0x05, 0x2c, 0xc0, 0x00, 0x61, 0x62, 0x02, 0x72, 0x00, 0x00, 0x00, 0x8e, 0x00, 0x86,
+            0x00, 0x83, 0x00, 0x83, 0x00, 0x86, 0x00, 0x00, 0x00, 0x86, 0x33, 0x8c, 0x67, 0x86,
+            0x33, 0x86, 0x33, 0x00, 0x00, 0x00, 0x04, 0xa0, 0x00, 0x00, 0x00, 0xa0, 0x00, 0x00,
+            0x03, 0x00, 0x00, 0xa0, 0x00, 0x00, 0x03, 0x00, 0x00, 0xa0, 0x00, 0x00, 0x03, 0x00,
+            0x22, 0x01, 0x00, 0x00, 0xa9, 0x02, 0x00, 0x00, 0xaf, 0x02, 0x00, 0x00, 0x22, 0x01,
+            0x00, 0x80, 0x5a, 0x05, 0x00, 0x00, 0x22, 0x01, 0x00, 0x00, 0x5c, 0x05, 0x00, 0x00,
+            0x22, 0x01, 0x00, 0x00, 0xa9, 0x02, 0x00, 0x00, 0xaf, 0x02, 0x00, 0x00, 0x22, 0x01,
+            0x00, 0x00, 0x22, 0x01, 0x00, 0x00, 0xa9, 0x02, 0x00, 0x00, 0xaf, 0x02, 0x00, 0x00,
+            0x22, 0x01, 0x00, 0x80, 0x5a, 0x05, 0x00, 0x00, 0x22, 0x01, 0x00, 0x00, 0x5c, 0x05,
+            0x00, 0x00, 0x22, 0x01, 0x00, 0x00, 0xa9, 0x02, 0x00, 0x00, 0xaf, 0x02, 0x00, 0x00,
+            0x22, 0x01, 0x00, 0x00, 0x22, 0x01, 0x00, 0x00, 0xa9, 0x02, 0x00, 0x00, 0xaf, 0x02,
+            0x00, 0x00, 0x22, 0x01, 0x00, 0x80, 0x5a, 0x05, 0x00, 0x00, 0x22, 0x01, 0x00, 0x00,
+            0x5c, 0x05, 0x00, 0x00, 0x22, 0x01, 0x00, 0x00, 0xa9, 0x02, 0x00, 0x5a, 0xbe, 0x0c,
+            0x08,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(
+            decode(&mut reader, 0)?,
+            b"abracadabraabracadabraabracadabraabracadabra"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_order_1_with_compressed_frequencies() -> io::Result<()> {
+        // flags (ORDER), len, shift (10) | compressed, uncompressed table len, compressed table len, order-0 table, states, renormalization words
+        let data = [
+            0x01, 0x17, 0xa1, 0x36, 0x45, 0x00, 0x01, 0x02, 0x05, 0x20, 0x49, 0x61, 0x62, 0x01,
+            0x71, 0x72, 0x00, 0x81, 0x83, 0x88, 0x00, 0x8e, 0x72, 0x82, 0x7b, 0x4b, 0x82, 0x7b,
+            0x4b, 0x4b, 0x4b, 0x4b, 0x4b, 0x81, 0x17, 0x4b, 0x4b, 0x81, 0x17, 0x4b, 0x83, 0x47,
+            0xe5, 0x80, 0x04, 0x00, 0x3a, 0x1b, 0x1e, 0x00, 0xd7, 0x7b, 0x01, 0x00, 0xf8, 0x4b,
+            0xc7, 0x15, 0x4e, 0x87, 0xd4, 0x67, 0x34, 0x9f, 0x5f, 0x9b, 0x78, 0x5d, 0x72, 0x85,
+            0x93, 0xb2, 0x5c, 0x08, 0x97, 0x10, 0x05, 0x00, 0x49, 0x0b, 0x05, 0x00, 0x1b, 0x1c,
+            0x0a, 0x00, 0x73, 0xc2, 0x16, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"abracadabra abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_stripe() -> io::Result<()> {
+        // flags (STRIPE), len, stream count, compressed lens, 4 order-0 streams (NO_SIZE)
+        let data = [
+            0x08, 0x0b, 0x04, 0x1b, 0x1c, 0x1b, 0x15, 0x10, 0x61, 0x62, 0x01, 0x00, 0x8a, 0x56,
+            0x8a, 0x55, 0x8a, 0x55, 0x46, 0x75, 0x01, 0x00, 0xb3, 0x8a, 0x01, 0x00, 0x5e, 0x85,
+            0x01, 0x00, 0x00, 0x80, 0x00, 0x00, 0x10, 0x61, 0x62, 0x00, 0x72, 0x00, 0x8a, 0x56,
+            0x8a, 0x55, 0x8a, 0x55, 0x5e, 0x85, 0x01, 0x00, 0x46, 0x75, 0x01, 0x00, 0xb3, 0x8a,
+            0x01, 0x00, 0x00, 0x80, 0x00, 0x00, 0x10, 0x61, 0x64, 0x72, 0x00, 0x8a, 0x56, 0x8a,
+            0x55, 0x8a, 0x55, 0xb3, 0x8a, 0x01, 0x00, 0x5e, 0x85, 0x01, 0x00, 0x46, 0x75, 0x01,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x10, 0x61, 0x00, 0xa0, 0x00, 0x00, 0x80, 0x00, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_stripe_and_no_size() -> io::Result<()> {
+        // flags (STRIPE | NO_SIZE), stream count, compressed lens, 4 order-0 streams (NO_SIZE)
+        let data = [
+            0x18, 0x04, 0x1b, 0x1c, 0x1b, 0x15, 0x10, 0x61, 0x62, 0x01, 0x00, 0x8a, 0x56, 0x8a,
+            0x55, 0x8a, 0x55, 0x46, 0x75, 0x01, 0x00, 0xb3, 0x8a, 0x01, 0x00, 0x5e, 0x85, 0x01,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x10, 0x61, 0x62, 0x00, 0x72, 0x00, 0x8a, 0x56, 0x8a,
+            0x55, 0x8a, 0x55, 0x5e, 0x85, 0x01, 0x00, 0x46, 0x75, 0x01, 0x00, 0xb3, 0x8a, 0x01,
+            0x00, 0x00, 0x80, 0x00, 0x00, 0x10, 0x61, 0x64, 0x72, 0x00, 0x8a, 0x56, 0x8a, 0x55,
+            0x8a, 0x55, 0xb3, 0x8a, 0x01, 0x00, 0x5e, 0x85, 0x01, 0x00, 0x46, 0x75, 0x01, 0x00,
+            0x00, 0x80, 0x00, 0x00, 0x10, 0x61, 0x00, 0xa0, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 11)?, b"abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_pack_and_order_0() -> io::Result<()> {
+        // flags (PACK), len, symbol count, symbols, packed len, order-0 stream
+        let data = [
+            0x80, 0x0c, 0x04, 0x41, 0x43, 0x47, 0x54, 0x03, 0x1b, 0xe4, 0x00, 0x8a, 0x55, 0x95,
+            0x2b, 0xfc, 0xbf, 0x00, 0x00, 0x08, 0x80, 0x01, 0x00, 0xfc, 0xbf, 0x00, 0x00, 0x00,
+            0x80, 0x00, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"ACGTTGCAACGT");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_rle_and_order_0() -> io::Result<()> {
+        // flags (RLE), len, meta len (uncompressed), literals len, meta, order-0 stream
+        let data = [
+            0x40, 0x10, 0x0b, 0x04, 0x02, 0x41, 0x54, 0x07, 0x05, 0x41, 0x43, 0x47, 0x54, 0x00,
+            0x88, 0x00, 0x88, 0x00, 0x88, 0x00, 0x88, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x04,
+            0x02, 0x00, 0x00, 0x08, 0x02, 0x00, 0x00, 0x0c, 0x02, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"AAAAAAAACGTTTTTT");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_rle_with_compressed_meta_and_order_1() -> io::Result<()> {
+        // flags (RLE | ORDER), len, meta len (compressed), literals len, compressed meta len, order-0 meta, order-1 stream
+        let data = [
+            0x41, 0x18, 0x0c, 0x06, 0x23, 0x02, 0x05, 0x06, 0x01, 0x41, 0x54, 0x00, 0x85, 0x2e,
+            0x85, 0x2a, 0x85, 0x2a, 0x85, 0x2a, 0x85, 0x2a, 0x85, 0x2a, 0xbc, 0xf1, 0x11, 0x00,
+            0x90, 0x2b, 0x12, 0x00, 0x76, 0x0d, 0x03, 0x00, 0x22, 0x08, 0x03, 0x00, 0xc0, 0x00,
+            0x41, 0x43, 0x47, 0x54, 0x00, 0x00, 0x00, 0x88, 0x00, 0x88, 0x00, 0x88, 0x00, 0x88,
+            0x00, 0x00, 0x01, 0xa0, 0x00, 0x00, 0x01, 0x00, 0x04, 0x00, 0x04, 0x00, 0x00, 0xa0,
+            0x00, 0x00, 0x02, 0x00, 0x00, 0x02, 0x00, 0x00, 0x04, 0x02, 0x00, 0x00, 0x08, 0x02,
+            0x00, 0x00, 0x0c, 0x02, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"AAAAAAAACGTTTTTTAAAAAAAC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_pack_and_rle() -> io::Result<()> {
+        // flags (PACK | RLE), len, pack meta, RLE meta (uncompressed), order-0 stream
+        let data = [
+            0xc0, 0x10, 0x04, 0x41, 0x43, 0x47, 0x54, 0x04, 0x0b, 0x03, 0x02, 0x00, 0xff, 0x01,
+            0x00, 0x00, 0xf9, 0xff, 0x00, 0x8a, 0x56, 0x8a, 0x55, 0x8a, 0x55, 0x46, 0x75, 0x01,
+            0x00, 0x5e, 0x85, 0x01, 0x00, 0xb3, 0x8a, 0x01, 0x00, 0x00, 0x80, 0x00, 0x00,
+        ];
+
+        let mut reader = &data[..];
+        assert_eq!(decode(&mut reader, 0)?, b"AAAAAAAACGTTTTTT");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_invalid_flags() {
+        let data = [0x02, 0x00];
+        let mut reader = &data[..];
+
+        assert!(matches!(
+            decode(&mut reader, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_encode_round_trip() -> io::Result<()> {
+        let mut inputs: Vec<Vec<u8>> = (0..=9).map(|n| b"ACGTNacgt"[..n].to_vec()).collect();
+        inputs.push((0..=255).collect());
+        inputs.push(build_data());
+        inputs.push(b"AAAAAAAAAAAAAAAAACCCCCCCCCGTTTTTTTTTTTTTT".to_vec());
+
+        let flag_sets = [
+            Flags::empty(),
+            Flags::ORDER,
+            Flags::N32,
+            Flags::ORDER | Flags::N32,
+            Flags::CAT,
+            Flags::PACK,
+            Flags::PACK | Flags::ORDER,
+            Flags::RLE,
+            Flags::RLE | Flags::ORDER,
+            Flags::PACK | Flags::RLE,
+            Flags::STRIPE,
+            Flags::STRIPE | Flags::ORDER | Flags::RLE,
+        ];
+
+        for data in &inputs {
+            for &flags in flag_sets.iter() {
+                let encoded_data = encode(flags, data)?;
+                let mut reader = &encoded_data[..];
+                assert_eq!(&decode(&mut reader, 0)?, data, "flags = {:?}", flags);
+            }
+
+            for &flags in &[Flags::NO_SIZE, Flags::STRIPE | Flags::NO_SIZE] {
+                let encoded_data = encode(flags, data)?;
+                let mut reader = &encoded_data[..];
+                assert_eq!(
+                    &decode(&mut reader, data.len())?,
+                    data,
+                    "flags = {:?}",
+                    flags
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_pack_and_rle() -> io::Result<()> {
+        let data = b"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAC";
+
+        let encoded_data = encode(Flags::PACK | Flags::RLE, data)?;
+        assert!(encoded_data.len() < data.len());
+        assert_eq!(encoded_data[0], (Flags::PACK | Flags::RLE).bits());
+
+        Ok(())
+    }
+}
@@ -1,27 +1,52 @@
 pub mod block;
+mod checksum_mismatch_error;
 pub mod compression_header;
 mod container;
+mod counting_reader;
 mod encoding;
+mod query;
 pub mod record;
 mod records;
 pub mod slice;
 
-pub use self::records::Records;
+pub use self::{
+    checksum_mismatch_error::{ChecksumMismatchError, ChecksummedPart},
+    query::Query,
+    records::Records,
+};
 
 use std::{
-    io::{self, Read},
+    convert::TryFrom,
+    io::{self, Read, Seek, SeekFrom},
     str,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use noodles::Region;
+use noodles_sam::{self as sam, header::ReferenceSequences};
+
+use super::{
+    container::{CompressionHeader, Slice},
+    crai, Container, ReferenceSequenceRepository, MAGIC_NUMBER,
+};
 
-use super::{Container, MAGIC_NUMBER};
+use self::counting_reader::CountingReader;
 
+// CRAM 3.1 only adds block compression methods, so both versions share the same file layout.
+const SUPPORTED_VERSIONS: [(u8, u8); 2] = [(3, 0), (3, 1)];
+
+/// A CRAM reader.
+///
+/// The CRC32 checksums of container headers and blocks are verified when they are read. A
+/// mismatch is returned as an [`io::Error`] with the kind [`io::ErrorKind::InvalidData`], wrapping
+/// a [`ChecksumMismatchError`] that includes the position of the corrupt container. This position
+/// is relative to where the stream was when the reader was created (see
+/// [`ChecksumMismatchError::container_position`]).
 pub struct Reader<R>
 where
     R: Read,
 {
-    inner: R,
+    inner: CountingReader<R>,
 }
 
 impl<R> Reader<R>
@@ -29,7 +54,9 @@ where
     R: Read,
 {
     pub fn new(reader: R) -> Self {
-        Self { inner: reader }
+        Self {
+            inner: CountingReader::new(reader),
+        }
     }
 
     pub fn read_file_definition(&mut self) -> io::Result<[u8; 20]> {
@@ -42,7 +69,14 @@ where
             ));
         }
 
-        read_format(&mut self.inner)?;
+        let (major, minor) = read_format(&mut self.inner)?;
+
+        if !SUPPORTED_VERSIONS.contains(&(major, minor)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported CRAM version: {}.{}", major, minor),
+            ));
+        }
 
         read_file_id(&mut self.inner)
     }
@@ -68,22 +102,289 @@ where
     }
 
     pub fn read_container(&mut self) -> io::Result<Container> {
-        let header = container::read_header(&mut self.inner)?;
+        let container_position = self.inner.position();
+
+        let header = container::read_header(&mut self.inner)
+            .map_err(|e| set_container_position(e, container_position))?;
 
         let blocks_len = header.block_count() as usize;
         let mut blocks = Vec::with_capacity(blocks_len);
 
         for _ in 0..blocks_len {
-            let block = block::read_block(&mut self.inner)?;
+            let block = block::read_block(&mut self.inner)
+                .map_err(|e| set_container_position(e, container_position))?;
             blocks.push(block);
         }
 
         Ok(Container::new(header, blocks))
     }
 
+    /// Returns an iterator over records starting from the current stream position.
+    ///
+    /// The records are not resolved, e.g., the bases of mapped records are not restored from the
+    /// reference sequence. Use [`Self::resolved_records`] to read complete records.
     pub fn records(&mut self) -> Records<'_, R> {
         Records::new(self)
     }
+
+    /// Returns an iterator over resolved records starting from the current stream position.
+    ///
+    /// Each slice is resolved using the given SAM header and reference sequence repository (see
+    /// [`Slice::resolve_records`]), so the records can be converted to SAM or BAM records.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_cram::{self as cram, reference_sequence_repository::IndexedFasta};
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    ///
+    /// let header: sam::Header = reader.read_file_header()?.parse()?;
+    /// let mut reference_sequence_repository = IndexedFasta::open("reference.fa")?;
+    ///
+    /// for result in reader.resolved_records(&header, &mut reference_sequence_repository) {
+    ///     let record = result?;
+    ///     let sam_record = record.try_into_sam_record(header.reference_sequences())?;
+    ///     println!("{:?}", sam_record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn resolved_records<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+        reference_sequence_repository: &'a mut dyn ReferenceSequenceRepository,
+    ) -> Records<'a, R> {
+        Records::with_reference_sequence_repository(self, header, reference_sequence_repository)
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: Read + Seek,
+{
+    /// Returns the current position of the underlying stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_cram as cram;
+    /// let mut reader = cram::Reader::new(Cursor::new(Vec::new()));
+    /// assert_eq!(reader.position()?, 0);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn position(&mut self) -> io::Result<u64> {
+        self.inner.stream_position()
+    }
+
+    /// Seeks the underlying stream to the given position.
+    ///
+    /// This is typically used to seek to the start of a container, e.g., using an offset from a
+    /// CRAM index record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, SeekFrom};
+    /// use noodles_cram as cram;
+    /// let mut reader = cram::Reader::new(Cursor::new(vec![0; 26]));
+    /// reader.seek(SeekFrom::Start(26))?;
+    /// assert_eq!(reader.position()?, 26);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    /// Reads the compression header and a single slice of a container.
+    ///
+    /// `container_position` is the position of the start of the container in the stream, and
+    /// `landmark` is the offset of the slice relative to the start of the container data, e.g.,
+    /// the offset and landmark of a CRAM index record. Only the compression header and the blocks
+    /// of the given slice are read.
+    ///
+    /// The compression header is required to decode the records of the slice, and both are
+    /// independent of the reader, e.g., for decoding slices in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram::{self as cram, crai};
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// let index = crai::read("sample.cram.crai")?;
+    ///
+    /// for index_record in &index {
+    ///     let (compression_header, slice) =
+    ///         reader.read_slice(index_record.offset(), index_record.landmark())?;
+    ///
+    ///     for record in slice.records(&compression_header)? {
+    ///         println!("{:?}", record);
+    ///     }
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_slice(
+        &mut self,
+        container_position: u64,
+        landmark: u64,
+    ) -> io::Result<(CompressionHeader, Slice)> {
+        self.seek(SeekFrom::Start(container_position))?;
+
+        self.read_slice_blocks(landmark)
+            .map_err(|e| set_container_position(e, container_position))
+    }
+
+    fn read_slice_blocks(&mut self, landmark: u64) -> io::Result<(CompressionHeader, Slice)> {
+        let header = container::read_header(&mut self.inner)?;
+
+        if !header
+            .landmarks()
+            .iter()
+            .any(|&container_landmark| container_landmark as u64 == landmark)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("container has no slice at landmark {}", landmark),
+            ));
+        }
+
+        let container_data_position = self.position()?;
+
+        let compression_header = block::read_block(&mut self.inner)
+            .and_then(|block| CompressionHeader::try_from(&block))?;
+
+        self.seek(SeekFrom::Start(container_data_position + landmark))?;
+
+        let slice_header = block::read_block(&mut self.inner).and_then(|block| {
+            let data = block.decompressed_data()?;
+            let mut reader = &data[..];
+            slice::read_header(&mut reader)
+        })?;
+
+        let core_data_block = block::read_block(&mut self.inner)?;
+
+        let external_block_count = (slice_header.block_count() - 1) as usize;
+        let mut external_blocks = Vec::with_capacity(external_block_count);
+
+        for _ in 0..external_block_count {
+            let block = block::read_block(&mut self.inner)?;
+            external_blocks.push(block);
+        }
+
+        let slice = Slice::new(slice_header, core_data_block, external_blocks);
+
+        Ok((compression_header, slice))
+    }
+
+    /// Returns an iterator over resolved records that intersect the given region.
+    ///
+    /// The CRAM index is used to find the slices that may contain records in the region. Only
+    /// those slices are read, decoded (see [`read_slice`]), and resolved using the given SAM
+    /// header and reference sequence repository (see [`Slice::resolve_records`]).
+    ///
+    /// [`read_slice`]: #method.read_slice
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles::Region;
+    /// use noodles_cram::{self as cram, crai, reference_sequence_repository::IndexedFasta};
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    ///
+    /// let header: sam::Header = reader.read_file_header()?.parse()?;
+    /// let mut reference_sequence_repository = IndexedFasta::open("reference.fa")?;
+    ///
+    /// let index = crai::read("sample.cram.crai")?;
+    /// let region = Region::mapped("sq0", 17711, 28657);
+    /// let query = reader.query(&header, &mut reference_sequence_repository, &index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+        reference_sequence_repository: &'a mut dyn ReferenceSequenceRepository,
+        index: &[crai::Record],
+        region: &Region,
+    ) -> io::Result<Query<'a, R>> {
+        let (reference_sequence_id, start, end) =
+            resolve_region(header.reference_sequences(), region)?;
+
+        let index_records = index
+            .iter()
+            .filter(|record| {
+                let record_start = record.alignment_start() as u64;
+                let record_end = record_start + record.alignment_span() as u64;
+
+                record.reference_sequence_id() == reference_sequence_id as i32
+                    && record_start <= end
+                    && start < record_end
+            })
+            .cloned()
+            .collect();
+
+        Ok(Query::new(
+            self,
+            header,
+            reference_sequence_repository,
+            index_records,
+            reference_sequence_id,
+            start,
+            end,
+        ))
+    }
+}
+
+fn resolve_region(
+    reference_sequences: &ReferenceSequences,
+    region: &Region,
+) -> io::Result<(usize, u64, u64)> {
+    match region {
+        Region::Mapped { name, start, end } => {
+            let i = reference_sequences.get_index_of(name).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in reference sequences: {:?}",
+                        region
+                    ),
+                )
+            })?;
+
+            Ok((i, *start, *end))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "region is not mapped",
+        )),
+    }
+}
+
+// Adds the container position to a checksum mismatch error.
+fn set_container_position(e: io::Error, container_position: u64) -> io::Error {
+    match e
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<ChecksumMismatchError>())
+    {
+        Some(error) => io::Error::new(
+            io::ErrorKind::InvalidData,
+            error.with_container_position(container_position),
+        ),
+        None => e,
+    }
 }
 
 fn read_magic<R>(reader: &mut R) -> io::Result<[u8; 4]>
@@ -115,12 +416,25 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::io::Cursor;
+
+    use md5::{Digest, Md5};
+    use noodles_fasta as fasta;
+    use noodles_sam::{
+        self as sam,
+        header::{reference_sequence::Md5Checksum, ReferenceSequence},
+    };
+
+    use crate::{indexer::build_index, reference_sequence_repository::InMemory, Record, Writer};
+
     use super::*;
 
     fn build_file_definition() -> Vec<u8> {
-        let mut data = MAGIC_NUMBER.to_vec();
+        build_file_definition_with_format([0x03, 0x00])
+    }
 
-        let format = [0x03, 0x00];
+    fn build_file_definition_with_format(format: [u8; 2]) -> Vec<u8> {
+        let mut data = MAGIC_NUMBER.to_vec();
         data.extend_from_slice(&format);
 
         let file_id = [
@@ -148,10 +462,436 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_file_definition_with_format() {
+        let data = build_file_definition_with_format([0x03, 0x01]);
+        let mut reader = Reader::new(&data[..]);
+        assert!(reader.read_file_definition().is_ok());
+
+        for format in &[[0x02, 0x01], [0x03, 0x02], [0x04, 0x00]] {
+            let data = build_file_definition_with_format(*format);
+            let mut reader = Reader::new(&data[..]);
+
+            assert!(matches!(
+                reader.read_file_definition(),
+                Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+            ));
+        }
+    }
+
     #[test]
     fn test_read_file_definition_with_invalid_magic_number() {
         let data = b"BAM\x01";
         let mut reader = Reader::new(&data[..]);
         assert!(reader.read_file_definition().is_err());
     }
+
+    fn build_reference_sequence_records() -> Vec<fasta::Record> {
+        vec![
+            fasta::Record::new(
+                fasta::record::Definition::new(String::from("sq0"), None),
+                b"TTCACCCA".to_vec(),
+            ),
+            fasta::Record::new(
+                fasta::record::Definition::new(String::from("sq1"), None),
+                b"GATCTTACTTTTT".to_vec(),
+            ),
+        ]
+    }
+
+    fn build_cram() -> Result<(sam::Header, Vec<u8>), Box<dyn std::error::Error>> {
+        let reference_sequence_records = build_reference_sequence_records();
+
+        let mut header_builder = sam::Header::builder();
+
+        for record in &reference_sequence_records {
+            let mut hasher = Md5::new();
+            hasher.update(record.sequence());
+            let md5_checksum = Md5Checksum::from(<[u8; 16]>::from(hasher.finalize()));
+
+            header_builder = header_builder.add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name(record.reference_sequence_name())
+                    .set_length(record.sequence().len() as i32)
+                    .set_md5_checksum(md5_checksum)
+                    .build(),
+            );
+        }
+
+        let header = header_builder.build();
+
+        let mut writer = Writer::new(Vec::new(), InMemory::new(reference_sequence_records));
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for (reference_sequence_id, alignment_start, bases) in
+            &[(0, 2, b"TCAC"), (1, 3, b"TCTT"), (1, 9, b"TTTT")]
+        {
+            let record = Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_flags(crate::record::Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+                .set_reference_sequence_id((*reference_sequence_id).into())
+                .set_alignment_start(*alignment_start)
+                .set_read_length(4)
+                .set_bases(bases.to_vec())
+                .set_quality_scores(vec![45, 35, 43, 50])
+                .build();
+
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+
+        let data = writer.get_ref().clone();
+
+        Ok((header, data))
+    }
+
+    #[test]
+    fn test_read_slice() -> Result<(), Box<dyn std::error::Error>> {
+        let (_, data) = build_cram()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let index = build_index(&mut reader)?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+
+        for index_record in &index {
+            let (compression_header, slice) =
+                reader.read_slice(index_record.offset(), index_record.landmark())?;
+
+            let slice_header = slice.header();
+            assert_eq!(
+                i32::from(slice_header.reference_sequence_id()),
+                index_record.reference_sequence_id()
+            );
+            assert_eq!(
+                slice_header.alignment_start(),
+                index_record.alignment_start()
+            );
+
+            let records = slice.records(&compression_header)?;
+            assert_eq!(records.len(), slice_header.record_count() as usize);
+        }
+
+        let index_record = &index[0];
+        assert!(reader
+            .read_slice(index_record.offset(), index_record.landmark() + 1)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_records() -> Result<(), Box<dyn std::error::Error>> {
+        let (header, data) = build_cram()?;
+        let mut reference_sequence_repository = InMemory::new(build_reference_sequence_records());
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let index = build_index(&mut reader)?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+
+        let index_record = &index[0];
+        let (compression_header, slice) =
+            reader.read_slice(index_record.offset(), index_record.landmark())?;
+
+        let mut records = slice.records(&compression_header)?;
+        slice.resolve_records(
+            &header,
+            &mut reference_sequence_repository,
+            &compression_header,
+            &mut records,
+        )?;
+
+        let record = records[0].try_into_sam_record(header.reference_sequences())?;
+        assert_eq!(record.position().map(i32::from), Some(2));
+        assert_eq!(record.cigar().to_string(), "4M");
+        assert_eq!(record.sequence().to_string(), "TCAC");
+        assert_eq!(record.quality_scores().to_string(), "NDLS");
+
+        let record = records[0].try_into_bam_record(header.reference_sequences())?;
+        assert_eq!(record.sequence().to_string(), "TCAC");
+
+        assert!(slice
+            .resolve_records(
+                &header,
+                &mut InMemory::default(),
+                &compression_header,
+                &mut records,
+            )
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolved_records() -> Result<(), Box<dyn std::error::Error>> {
+        let (header, data) = build_cram()?;
+        let mut reference_sequence_repository = InMemory::new(build_reference_sequence_records());
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .resolved_records(&header, &mut reference_sequence_repository)
+            .collect::<Result<_, _>>()?;
+
+        let actual: Vec<_> = records
+            .iter()
+            .map(|record| record.bases().to_vec())
+            .collect();
+        assert_eq!(actual, [b"TCAC", b"TCTT", b"TTTT"]);
+
+        let record = records[1].try_into_sam_record(header.reference_sequences())?;
+        assert_eq!(record.sequence().to_string(), "TCTT");
+        assert_eq!(record.cigar().to_string(), "4M");
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+        assert!(records.iter().all(|record| record.bases().is_empty()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolved_records_with_overhanging_record() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::{Feature, Flags};
+
+        let (header, _) = build_cram()?;
+
+        // The record is aligned to sq0:7-10, which overhangs the end of sq0 (length 8).
+        let record = Record::builder()
+            .set_bam_flags(sam::record::Flags::empty())
+            .set_flags(Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+            .set_reference_sequence_id(0.into())
+            .set_alignment_start(7)
+            .set_read_length(4)
+            .set_bases(b"CAGT".to_vec())
+            .add_feature(Feature::Bases(3, b"GT".to_vec()))
+            .set_quality_scores(vec![45, 35, 43, 50])
+            .build();
+
+        let mut writer = Writer::new(
+            Vec::new(),
+            InMemory::new(build_reference_sequence_records()),
+        );
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+        writer.write_record(record)?;
+        writer.try_finish()?;
+
+        let data = writer.get_ref();
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut reference_sequence_repository = InMemory::new(build_reference_sequence_records());
+        let records: Vec<_> = reader
+            .resolved_records(&header, &mut reference_sequence_repository)
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].alignment_end(), 10);
+        assert_eq!(records[0].bases(), b"CAGT");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolved_records_with_invalid_reference_sequences(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (header, data) = build_cram()?;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut reference_sequence_repository = InMemory::default();
+        assert!(matches!(
+            reader
+                .resolved_records(&header, &mut reference_sequence_repository)
+                .next(),
+            Some(Err(ref e)) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        // The slice alignment region matches, but the sequence does not match the header `M5`.
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut reference_sequence_repository = InMemory::new(vec![fasta::Record::new(
+            fasta::record::Definition::new(String::from("sq0"), None),
+            b"TTCACCCG".to_vec(),
+        )]);
+        assert!(matches!(
+            reader
+                .resolved_records(&header, &mut reference_sequence_repository)
+                .next(),
+            Some(Err(ref e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_records_with_reference_md5_mismatch() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (header, data) = build_cram()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let index = build_index(&mut reader)?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+
+        let index_record = &index[0];
+        let (compression_header, slice) =
+            reader.read_slice(index_record.offset(), index_record.landmark())?;
+
+        let build_repository = |sequence: &[u8]| {
+            InMemory::new(vec![fasta::Record::new(
+                fasta::record::Definition::new(String::from("sq0"), None),
+                sequence.to_vec(),
+            )])
+        };
+
+        let mut records = slice.records(&compression_header)?;
+        let mut reference_sequence_repository = build_repository(b"ttcaccca");
+        slice.resolve_records(
+            &header,
+            &mut reference_sequence_repository,
+            &compression_header,
+            &mut records,
+        )?;
+
+        let mut records = slice.records(&compression_header)?;
+        let mut reference_sequence_repository = build_repository(b"TTCAGCCA");
+        assert!(matches!(
+            slice.resolve_records(
+                &header,
+                &mut reference_sequence_repository,
+                &compression_header,
+                &mut records,
+            ),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    fn get_checksum_mismatch_error(e: &io::Error) -> Option<&ChecksumMismatchError> {
+        e.get_ref()
+            .and_then(|inner| inner.downcast_ref::<ChecksumMismatchError>())
+    }
+
+    #[test]
+    fn test_read_container_with_checksum_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        let (_, data) = build_cram()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let index = build_index(&mut reader)?;
+
+        let container_position = index[0].offset();
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        reader.seek(SeekFrom::Start(container_position))?;
+        reader.read_container()?;
+        let container_end = reader.position()? as usize;
+
+        // container header: reference sequence ID
+        let mut corrupt_data = data.clone();
+        corrupt_data[container_position as usize + 4] ^= 0x01;
+
+        let mut reader = Reader::new(&corrupt_data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let e = reader.read_container().unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+        let error = get_checksum_mismatch_error(&e).ok_or("missing checksum mismatch error")?;
+        assert_eq!(error.part(), ChecksummedPart::ContainerHeader);
+        assert_eq!(error.container_position(), Some(container_position));
+
+        // last block: CRC32
+        let mut corrupt_data = data.clone();
+        corrupt_data[container_end - 1] ^= 0x01;
+
+        let mut reader = Reader::new(&corrupt_data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let e = reader.read_container().unwrap_err();
+        let error = get_checksum_mismatch_error(&e).ok_or("missing checksum mismatch error")?;
+        assert_eq!(error.part(), ChecksummedPart::Block);
+        assert_eq!(error.container_position(), Some(container_position));
+
+        // The stream starts after the file definition, so the position is relative to it.
+        const FILE_DEFINITION_LEN: usize = 26;
+
+        let mut reader = Reader::new(&corrupt_data[FILE_DEFINITION_LEN..]);
+        reader.read_file_header()?;
+
+        let e = reader.read_container().unwrap_err();
+        let error = get_checksum_mismatch_error(&e).ok_or("missing checksum mismatch error")?;
+        assert_eq!(
+            error.container_position(),
+            Some(container_position - FILE_DEFINITION_LEN as u64)
+        );
+
+        let mut reader = Reader::new(Cursor::new(&corrupt_data));
+        let index_record = &index[0];
+        let e = reader
+            .read_slice(index_record.offset(), index_record.landmark())
+            .unwrap_err();
+        let error = get_checksum_mismatch_error(&e).ok_or("missing checksum mismatch error")?;
+        assert_eq!(error.part(), ChecksummedPart::Block);
+        assert_eq!(error.container_position(), Some(container_position));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let (header, data) = build_cram()?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let index = build_index(&mut reader)?;
+
+        let mut reader = Reader::new(Cursor::new(&data));
+        let mut reference_sequence_repository = InMemory::new(build_reference_sequence_records());
+
+        let region = Region::mapped("sq1", 8, 13);
+        let records: Vec<_> = reader
+            .query(&header, &mut reference_sequence_repository, &index, &region)?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].alignment_start(), 9);
+        assert_eq!(records[0].bases(), b"TTTT");
+
+        let region = Region::mapped("sq0", 1, 8);
+        let records: Vec<_> = reader
+            .query(&header, &mut reference_sequence_repository, &index, &region)?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].alignment_start(), 2);
+        assert_eq!(records[0].bases(), b"TCAC");
+
+        let region = Region::mapped("sq0", 7, 8);
+        let records: Vec<_> = reader
+            .query(&header, &mut reference_sequence_repository, &index, &region)?
+            .collect::<Result<_, _>>()?;
+        assert!(records.is_empty());
+
+        let region = Region::mapped("sq2", 1, 8);
+        assert!(reader
+            .query(&header, &mut reference_sequence_repository, &index, &region)
+            .is_err());
+
+        Ok(())
+    }
 }
@@ -4,6 +4,7 @@ use std::{
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use flate2::CrcReader;
 
 use crate::{
     container::{
@@ -13,27 +14,41 @@ use crate::{
     num::read_itf8,
 };
 
+use super::{ChecksumMismatchError, ChecksummedPart};
+
 pub fn read_block<R>(reader: &mut R) -> io::Result<Block>
 where
     R: Read,
 {
-    let method = reader.read_u8().and_then(|b| {
+    let mut crc_reader = CrcReader::new(reader);
+
+    let method = crc_reader.read_u8().and_then(|b| {
         CompressionMethod::try_from(b).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let block_content_type_id = reader.read_u8().and_then(|b| {
+    let block_content_type_id = crc_reader.read_u8().and_then(|b| {
         ContentType::try_from(b).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let block_content_id = read_itf8(reader)?;
-    let size_in_bytes = read_itf8(reader)?;
-    let raw_size_in_bytes = read_itf8(reader)?;
+    let block_content_id = read_itf8(&mut crc_reader)?;
+    let size_in_bytes = read_itf8(&mut crc_reader)?;
+    let raw_size_in_bytes = read_itf8(&mut crc_reader)?;
 
     let mut data = vec![0; size_in_bytes as usize];
-    reader.read_exact(&mut data)?;
+    crc_reader.read_exact(&mut data)?;
+
+    let actual_crc32 = crc_reader.crc().sum();
+    let reader = crc_reader.into_inner();
 
     let crc32 = reader.read_u32::<LittleEndian>()?;
 
+    if crc32 != actual_crc32 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            ChecksumMismatchError::new(ChecksummedPart::Block, crc32, actual_crc32),
+        ));
+    }
+
     Ok(Block::builder()
         .set_compression_method(method)
         .set_content_type(block_content_type_id)
@@ -0,0 +1,115 @@
+use std::{error, fmt};
+
+/// A part of a container that is covered by a CRC32 checksum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChecksummedPart {
+    /// The container header.
+    ContainerHeader,
+    /// A block.
+    Block,
+}
+
+impl fmt::Display for ChecksummedPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ContainerHeader => f.write_str("container header"),
+            Self::Block => f.write_str("block"),
+        }
+    }
+}
+
+/// An error returned when a stored CRC32 checksum does not match the checksum of the data it
+/// covers.
+///
+/// This is returned as the inner error of an [`std::io::Error`] with the kind
+/// [`std::io::ErrorKind::InvalidData`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChecksumMismatchError {
+    part: ChecksummedPart,
+    container_position: Option<u64>,
+    expected: u32,
+    actual: u32,
+}
+
+impl ChecksumMismatchError {
+    pub(crate) fn new(part: ChecksummedPart, expected: u32, actual: u32) -> Self {
+        Self {
+            part,
+            container_position: None,
+            expected,
+            actual,
+        }
+    }
+
+    pub(crate) fn with_container_position(mut self, container_position: u64) -> Self {
+        self.container_position = Some(container_position);
+        self
+    }
+
+    /// Returns the part of the container with the mismatched checksum.
+    pub fn part(&self) -> ChecksummedPart {
+        self.part
+    }
+
+    /// Returns the position of the start of the container in the stream, if known.
+    ///
+    /// The position is counted from where the stream was when the reader was created, not
+    /// necessarily from the start of the stream. It is only the absolute stream position if the
+    /// reader was created at the start of the stream or the stream was seeked since, e.g., by
+    /// [`Reader::seek`] or [`Reader::position`].
+    ///
+    /// [`Reader::seek`]: crate::Reader::seek
+    /// [`Reader::position`]: crate::Reader::position
+    pub fn container_position(&self) -> Option<u64> {
+        self.container_position
+    }
+
+    /// Returns the stored checksum.
+    pub fn expected(&self) -> u32 {
+        self.expected
+    }
+
+    /// Returns the checksum calculated from the data.
+    pub fn actual(&self) -> u32 {
+        self.actual
+    }
+}
+
+impl error::Error for ChecksumMismatchError {}
+
+impl fmt::Display for ChecksumMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} CRC32 mismatch", self.part)?;
+
+        if let Some(container_position) = self.container_position {
+            write!(f, " in container at offset {}", container_position)?;
+        }
+
+        write!(
+            f,
+            ": expected {:08x}, got {:08x}",
+            self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let error = ChecksumMismatchError::new(ChecksummedPart::Block, 0x4b6d7a1c, 0x00c0ffee);
+        assert_eq!(
+            error.to_string(),
+            "block CRC32 mismatch: expected 4b6d7a1c, got 00c0ffee"
+        );
+
+        let error = ChecksumMismatchError::new(ChecksummedPart::ContainerHeader, 1, 2)
+            .with_container_position(8);
+        assert_eq!(
+            error.to_string(),
+            "container header CRC32 mismatch in container at offset 8: expected 00000001, got 00000002"
+        );
+    }
+}
@@ -4,31 +4,47 @@ use std::{
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use flate2::CrcReader;
 
 use crate::{
     container::{Header, ReferenceSequenceId},
     num::{read_itf8, read_ltf8, Itf8},
 };
 
+use super::{ChecksumMismatchError, ChecksummedPart};
+
 pub fn read_header<R>(reader: &mut R) -> io::Result<Header>
 where
     R: Read,
 {
-    let length = reader.read_i32::<LittleEndian>()?;
+    let mut crc_reader = CrcReader::new(reader);
+
+    let length = crc_reader.read_i32::<LittleEndian>()?;
 
-    let reference_sequence_id = read_itf8(reader).and_then(|n| {
+    let reference_sequence_id = read_itf8(&mut crc_reader).and_then(|n| {
         ReferenceSequenceId::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
 
-    let starting_position_on_the_reference = read_itf8(reader)?;
-    let alignment_span = read_itf8(reader)?;
-    let number_of_records = read_itf8(reader)?;
-    let record_counter = read_ltf8(reader)?;
-    let bases = read_ltf8(reader)?;
-    let number_of_blocks = read_itf8(reader)?;
-    let landmarks = read_landmarks(reader)?;
+    let starting_position_on_the_reference = read_itf8(&mut crc_reader)?;
+    let alignment_span = read_itf8(&mut crc_reader)?;
+    let number_of_records = read_itf8(&mut crc_reader)?;
+    let record_counter = read_ltf8(&mut crc_reader)?;
+    let bases = read_ltf8(&mut crc_reader)?;
+    let number_of_blocks = read_itf8(&mut crc_reader)?;
+    let landmarks = read_landmarks(&mut crc_reader)?;
+
+    let actual_crc32 = crc_reader.crc().sum();
+    let reader = crc_reader.into_inner();
+
     let crc32 = reader.read_u32::<LittleEndian>()?;
 
+    if crc32 != actual_crc32 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            ChecksumMismatchError::new(ChecksummedPart::ContainerHeader, crc32, actual_crc32),
+        ));
+    }
+
     Ok(Header::builder()
         .set_length(length)
         .set_reference_sequence_id(reference_sequence_id)
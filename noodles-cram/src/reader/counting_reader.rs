@@ -0,0 +1,41 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+// A reader that tracks the position of the stream, even if the stream is not seekable.
+//
+// The count starts at 0, i.e., it is relative to the position of the stream when the reader is
+// created. Seeking sets it to the absolute position returned by the stream.
+pub struct CountingReader<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R> CountingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+impl<R> Read for CountingReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R> Seek for CountingReader<R>
+where
+    R: Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        Ok(self.position)
+    }
+}
@@ -13,14 +13,14 @@ where
     match raw_kind {
         0 => Ok(Encoding::Null),
         1 => read_external_encoding(reader),
-        2 => unimplemented!("GOLOMB"),
+        2 => read_golomb_encoding(reader),
         3 => read_huffman_encoding(reader),
         4 => read_byte_array_len_encoding(reader),
         5 => read_byte_array_stop_encoding(reader),
         6 => read_beta_encoding(reader),
-        7 => unimplemented!("SUBEXP"),
-        8 => unimplemented!("GOLOMB_RICE"),
-        9 => unimplemented!("GAMMA"),
+        7 => read_subexp_encoding(reader),
+        8 => read_golomb_rice_encoding(reader),
+        9 => read_gamma_encoding(reader),
         _ => Err(io::Error::new(
             io::ErrorKind::InvalidData,
             "invalid encoding kind",
@@ -117,3 +117,75 @@ where
 
     Ok(Encoding::Beta(offset, len))
 }
+
+fn read_golomb_encoding<R>(reader: &mut R) -> io::Result<Encoding>
+where
+    R: Read,
+{
+    let args = read_args(reader)?;
+    let mut args_reader = &args[..];
+
+    let offset = read_itf8(&mut args_reader)?;
+    let m = read_itf8(&mut args_reader)?;
+
+    Ok(Encoding::Golomb(offset, m))
+}
+
+fn read_subexp_encoding<R>(reader: &mut R) -> io::Result<Encoding>
+where
+    R: Read,
+{
+    let args = read_args(reader)?;
+    let mut args_reader = &args[..];
+
+    let offset = read_itf8(&mut args_reader)?;
+    let k = read_itf8(&mut args_reader)?;
+
+    Ok(Encoding::Subexp(offset, k))
+}
+
+fn read_golomb_rice_encoding<R>(reader: &mut R) -> io::Result<Encoding>
+where
+    R: Read,
+{
+    let args = read_args(reader)?;
+    let mut args_reader = &args[..];
+
+    let offset = read_itf8(&mut args_reader)?;
+    let log2_m = read_itf8(&mut args_reader)?;
+
+    Ok(Encoding::GolombRice(offset, log2_m))
+}
+
+fn read_gamma_encoding<R>(reader: &mut R) -> io::Result<Encoding>
+where
+    R: Read,
+{
+    let args = read_args(reader)?;
+    let mut args_reader = &args[..];
+
+    let offset = read_itf8(&mut args_reader)?;
+
+    Ok(Encoding::Gamma(offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_encoding() -> io::Result<()> {
+        fn t(mut data: &[u8], expected: Encoding) -> io::Result<()> {
+            let actual = read_encoding(&mut data)?;
+            assert_eq!(actual, expected);
+            Ok(())
+        }
+
+        t(&[2, 2, 1, 5], Encoding::Golomb(1, 5))?;
+        t(&[7, 2, 0, 2], Encoding::Subexp(0, 2))?;
+        t(&[8, 2, 1, 3], Encoding::GolombRice(1, 3))?;
+        t(&[9, 1, 1], Encoding::Gamma(1))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    io::{self, Read, Seek},
+    vec,
+};
+
+use noodles_sam as sam;
+
+use crate::{crai, reference_sequence_repository::Md5Checked, Record, ReferenceSequenceRepository};
+
+use super::Reader;
+
+/// An iterator over resolved records of a CRAM reader that intersect a given region.
+///
+/// This is created by calling [`cram::Reader::query`].
+///
+/// [`cram::Reader::query`]: struct.Reader.html#method.query
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: &'a mut Reader<R>,
+    header: &'a sam::Header,
+    reference_sequence_repository: Md5Checked<&'a mut dyn ReferenceSequenceRepository>,
+    index_records: vec::IntoIter<crai::Record>,
+    reference_sequence_id: usize,
+    start: u64,
+    end: u64,
+    records: vec::IntoIter<Record>,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(crate) fn new(
+        reader: &'a mut Reader<R>,
+        header: &'a sam::Header,
+        reference_sequence_repository: &'a mut dyn ReferenceSequenceRepository,
+        index_records: Vec<crai::Record>,
+        reference_sequence_id: usize,
+        start: u64,
+        end: u64,
+    ) -> Self {
+        Self {
+            reader,
+            header,
+            reference_sequence_repository: Md5Checked::new(reference_sequence_repository),
+            index_records: index_records.into_iter(),
+            reference_sequence_id,
+            start,
+            end,
+            records: Vec::new().into_iter(),
+        }
+    }
+
+    fn read_next_slice_records(&mut self) -> io::Result<Option<Vec<Record>>> {
+        let index_record = match self.index_records.next() {
+            Some(r) => r,
+            None => return Ok(None),
+        };
+
+        let (compression_header, slice) = self
+            .reader
+            .read_slice(index_record.offset(), index_record.landmark())?;
+
+        let mut records = slice.records(&compression_header)?;
+
+        slice.resolve_records(
+            self.header,
+            &mut self.reference_sequence_repository,
+            &compression_header,
+            &mut records,
+        )?;
+
+        Ok(Some(records))
+    }
+}
+
+impl<'a, R> Iterator for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.records.next() {
+                Some(record) => {
+                    if intersects(&record, self.reference_sequence_id, self.start, self.end) {
+                        return Some(Ok(record));
+                    }
+                }
+                None => match self.read_next_slice_records() {
+                    Ok(Some(records)) => self.records = records.into_iter(),
+                    Ok(None) => return None,
+                    Err(e) => return Some(Err(e)),
+                },
+            }
+        }
+    }
+}
+
+fn intersects(record: &Record, reference_sequence_id: usize, start: u64, end: u64) -> bool {
+    let record_reference_sequence_id = match *record.reference_sequence_id() {
+        Some(id) => id as usize,
+        None => return false,
+    };
+
+    let record_start = record.alignment_start() as u64;
+    let record_end = record.alignment_end() as u64;
+
+    record_reference_sequence_id == reference_sequence_id
+        && in_interval(record_start, record_end, start, end)
+}
+
+fn in_interval(a_start: u64, a_end: u64, b_start: u64, b_end: u64) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersects() {
+        let record = Record::builder()
+            .set_reference_sequence_id(0.into())
+            .set_alignment_start(8)
+            .set_read_length(4)
+            .build();
+
+        assert!(intersects(&record, 0, 1, 8));
+        assert!(intersects(&record, 0, 11, 13));
+        assert!(!intersects(&record, 0, 12, 13));
+        assert!(!intersects(&record, 1, 8, 11));
+
+        let record = Record::default();
+        assert!(!intersects(&record, 0, 1, 8));
+    }
+}
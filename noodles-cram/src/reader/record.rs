@@ -14,10 +14,11 @@ use crate::{
         compression_header::{data_series_encoding_map::DataSeries, encoding::Encoding},
         CompressionHeader, ReferenceSequenceId,
     },
+    gamma, golomb,
     huffman::CanonicalHuffmanDecoder,
     num::{read_itf8, Itf8},
     record::{self, feature, tag, Feature, ReadGroupId, Tag},
-    BitReader, Record,
+    subexp, BitReader, Record,
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -870,14 +871,25 @@ where
 
             reader.read_u8()
         }
-        Encoding::Huffman(alphabet, bit_lens) => {
-            let decoder = CanonicalHuffmanDecoder::new(&alphabet, &bit_lens);
-            decoder.read(core_data_reader).map(|i| i as u8)
+        Encoding::Huffman(..)
+        | Encoding::Beta(..)
+        | Encoding::Golomb(..)
+        | Encoding::Subexp(..)
+        | Encoding::GolombRice(..)
+        | Encoding::Gamma(..) => {
+            let n = decode_itf8(encoding, core_data_reader, external_data_readers)?;
+
+            u8::try_from(n).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid byte value: {}", n),
+                )
+            })
         }
-        Encoding::Beta(offset, len) => core_data_reader
-            .read_u32(*len as usize)
-            .map(|i| (i as i32 - offset) as u8),
-        _ => todo!("decode_byte: {:?}", encoding),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid byte encoding: {:?}", encoding),
+        )),
     }
 }
 
@@ -907,13 +919,39 @@ where
             let decoder = CanonicalHuffmanDecoder::new(&alphabet, &bit_lens);
             decoder.read(core_data_reader)
         }
-        Encoding::Beta(offset, len) => core_data_reader
-            .read_u32(*len as usize)
-            .map(|i| (i as i32 - offset)),
-        _ => todo!("decode_itf8: {:?}", encoding),
+        Encoding::Beta(offset, len) => decode_beta(core_data_reader, *offset, *len),
+        Encoding::Golomb(offset, m) => golomb::read(core_data_reader, *offset, *m),
+        Encoding::Subexp(offset, k) => subexp::read(core_data_reader, *offset, *k),
+        Encoding::GolombRice(offset, log2_m) => {
+            golomb::read_rice(core_data_reader, *offset, *log2_m)
+        }
+        Encoding::Gamma(offset) => gamma::read(core_data_reader, *offset),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid integer encoding: {:?}", encoding),
+        )),
     }
 }
 
+fn decode_beta<R>(core_data_reader: &mut BitReader<R>, offset: Itf8, len: Itf8) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|&len| len <= 32)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid beta length: {}", len),
+            )
+        })?;
+
+    core_data_reader
+        .read_u32(len)
+        .and_then(|n| golomb::remove_offset(offset, n))
+}
+
 fn decode_byte_array<R, S>(
     encoding: &Encoding,
     core_data_reader: &mut BitReader<R>,
@@ -935,15 +973,29 @@ where
                     )
                 })?;
 
-            let mut buf = buf.expect("missing buf");
+            let mut buf = buf.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid byte array encoding: missing length",
+                )
+            })?;
             reader.read_exact(&mut buf)?;
 
             Ok(buf)
         }
         Encoding::ByteArrayLen(len_encoding, value_encoding) => {
-            let len = decode_itf8(&len_encoding, core_data_reader, external_data_readers)?;
+            let len = decode_itf8(len_encoding, core_data_reader, external_data_readers).and_then(
+                |n| {
+                    usize::try_from(n).map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("invalid byte array length: {}", n),
+                        )
+                    })
+                },
+            )?;
 
-            let buf = vec![0; len as usize];
+            let buf = vec![0; len];
             let value = decode_byte_array(
                 &value_encoding,
                 core_data_reader,
@@ -971,6 +1023,113 @@ where
 
             Ok(buf)
         }
-        _ => todo!("decode_byte_array: {:?}", encoding),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid byte array encoding: {:?}", encoding),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_byte() -> io::Result<()> {
+        let mut core_data_reader = BitReader::new(&[][..]);
+        let mut external_data_readers: HashMap<Itf8, &[u8]> = HashMap::new();
+
+        let encoding = Encoding::Huffman(vec![13], vec![0]);
+        assert_eq!(
+            decode_byte(&encoding, &mut core_data_reader, &mut external_data_readers)?,
+            13
+        );
+
+        let encoding = Encoding::Huffman(vec![256], vec![0]);
+        assert!(matches!(
+            decode_byte(&encoding, &mut core_data_reader, &mut external_data_readers),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let encoding = Encoding::ByteArrayStop(0x00, 1);
+        assert!(matches!(
+            decode_byte(&encoding, &mut core_data_reader, &mut external_data_readers),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_itf8() -> io::Result<()> {
+        let data = [0b10100000];
+        let mut core_data_reader = BitReader::new(&data[..]);
+        let mut external_data_readers: HashMap<Itf8, &[u8]> = HashMap::new();
+
+        let encoding = Encoding::Beta(1, 3);
+        assert_eq!(
+            decode_itf8(&encoding, &mut core_data_reader, &mut external_data_readers)?,
+            4
+        );
+
+        let encoding = Encoding::Beta(0, 33);
+        assert!(matches!(
+            decode_itf8(&encoding, &mut core_data_reader, &mut external_data_readers),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let encoding = Encoding::ByteArrayLen(
+            Box::new(Encoding::External(1)),
+            Box::new(Encoding::External(2)),
+        );
+        assert!(matches!(
+            decode_itf8(&encoding, &mut core_data_reader, &mut external_data_readers),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_byte_array() {
+        let mut core_data_reader = BitReader::new(&[][..]);
+        let mut external_data_readers: HashMap<Itf8, &[u8]> = HashMap::new();
+        external_data_readers.insert(1, &[0x00][..]);
+
+        let encoding = Encoding::Huffman(vec![13], vec![0]);
+        assert!(matches!(
+            decode_byte_array(
+                &encoding,
+                &mut core_data_reader,
+                &mut external_data_readers,
+                None
+            ),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let encoding = Encoding::External(1);
+        assert!(matches!(
+            decode_byte_array(
+                &encoding,
+                &mut core_data_reader,
+                &mut external_data_readers,
+                None
+            ),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let encoding = Encoding::ByteArrayLen(
+            Box::new(Encoding::Huffman(vec![-1], vec![0])),
+            Box::new(Encoding::External(1)),
+        );
+        assert!(matches!(
+            decode_byte_array(
+                &encoding,
+                &mut core_data_reader,
+                &mut external_data_readers,
+                None
+            ),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
     }
 }
@@ -4,15 +4,29 @@ use std::{
     vec,
 };
 
-use crate::{DataContainer, Record};
+use noodles_sam as sam;
+
+use crate::{
+    reference_sequence_repository::Md5Checked, DataContainer, Record, ReferenceSequenceRepository,
+};
 
 use super::Reader;
 
+/// An iterator over records of a CRAM reader.
+///
+/// This is created by calling [`cram::Reader::records`] or [`cram::Reader::resolved_records`].
+///
+/// [`cram::Reader::records`]: struct.Reader.html#method.records
+/// [`cram::Reader::resolved_records`]: struct.Reader.html#method.resolved_records
 pub struct Records<'a, R>
 where
     R: Read,
 {
     reader: &'a mut Reader<R>,
+    resolver: Option<(
+        &'a sam::Header,
+        Md5Checked<&'a mut dyn ReferenceSequenceRepository>,
+    )>,
     records: vec::IntoIter<Record>,
 }
 
@@ -20,9 +34,22 @@ impl<'a, R> Records<'a, R>
 where
     R: Read,
 {
-    pub(crate) fn new(reader: &'a mut Reader<R>) -> Records<'_, R> {
+    pub(crate) fn new(reader: &'a mut Reader<R>) -> Self {
+        Self {
+            reader,
+            resolver: None,
+            records: Vec::new().into_iter(),
+        }
+    }
+
+    pub(crate) fn with_reference_sequence_repository(
+        reader: &'a mut Reader<R>,
+        header: &'a sam::Header,
+        reference_sequence_repository: &'a mut dyn ReferenceSequenceRepository,
+    ) -> Self {
         Self {
             reader,
+            resolver: Some((header, Md5Checked::new(reference_sequence_repository))),
             records: Vec::new().into_iter(),
         }
     }
@@ -35,16 +62,26 @@ where
         }
 
         let data_container = DataContainer::try_from(container)?;
+        let compression_header = data_container.compression_header();
+
+        let mut records = Vec::new();
+
+        for slice in data_container.slices() {
+            let mut slice_records = slice.records(compression_header)?;
+
+            if let Some((header, reference_sequence_repository)) = self.resolver.as_mut() {
+                slice.resolve_records(
+                    header,
+                    reference_sequence_repository,
+                    compression_header,
+                    &mut slice_records,
+                )?;
+            }
+
+            records.extend(slice_records);
+        }
 
-        self.records = data_container
-            .slices()
-            .iter()
-            .map(|slice| slice.records(data_container.compression_header()))
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .into_iter();
+        self.records = records.into_iter();
 
         Ok(false)
     }
@@ -11,11 +11,13 @@ pub use self::{
     read_group_id::ReadGroupId, tag::Tag,
 };
 
-use std::{fmt, str};
+use std::{convert::TryFrom, fmt, io, str};
 
 use noodles_bam as bam;
 use noodles_sam as sam;
 
+use self::resolve::{resolve_cigar, MISSING_QUALITY_SCORE};
+
 #[derive(Clone, PartialEq)]
 pub struct Record {
     pub id: i64,
@@ -150,6 +152,153 @@ impl Record {
     pub fn quality_scores(&self) -> &[u8] {
         &self.quality_scores
     }
+
+    /// Converts this CRAM record to a SAM record.
+    ///
+    /// The record is expected to already be resolved, i.e., the bases of mapped records are
+    /// restored from the reference sequence (see [`Slice::resolve_records`]). The CIGAR is built
+    /// from the read features, and the reference sequences are used to resolve the reference
+    /// sequence and mate reference sequence names from their IDs.
+    ///
+    /// [`Slice::resolve_records`]: crate::container::Slice::resolve_records
+    pub fn try_into_sam_record(
+        &self,
+        reference_sequences: &sam::header::ReferenceSequences,
+    ) -> io::Result<sam::Record> {
+        let mut builder = sam::Record::builder();
+
+        if !self.read_name().is_empty() {
+            let read_name = str::from_utf8(self.read_name())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                .and_then(|s| {
+                    s.parse()
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                })?;
+
+            builder = builder.set_read_name(read_name);
+        }
+
+        builder = builder.set_flags(self.bam_flags());
+
+        if let Some(name) =
+            get_reference_sequence_name(reference_sequences, self.reference_sequence_id())?
+        {
+            builder = builder.set_reference_sequence_name(name);
+        }
+
+        if self.alignment_start() > 0 {
+            let position = sam::record::Position::try_from(self.alignment_start())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            builder = builder.set_position(position);
+        }
+
+        builder = builder.set_mapping_quality(self.mapping_quality());
+
+        if !self.bam_flags().is_unmapped() {
+            let cigar = resolve_cigar(self.features(), self.read_length() as usize)?;
+            builder = builder.set_cigar(cigar);
+        }
+
+        if let Some(name) = get_reference_sequence_name(
+            reference_sequences,
+            self.next_fragment_reference_sequence_id(),
+        )? {
+            builder = builder.set_mate_reference_sequence_name(name);
+        }
+
+        if self.next_mate_alignment_start() > 0 {
+            let mate_position = sam::record::Position::try_from(self.next_mate_alignment_start())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            builder = builder.set_mate_position(mate_position);
+        }
+
+        builder = builder.set_template_length(self.template_size());
+
+        if !self.flags().decode_sequence_as_unknown() {
+            let bases = self
+                .bases()
+                .iter()
+                .map(|&b| {
+                    sam::record::sequence::Base::try_from(char::from(b))
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+
+            builder = builder.set_sequence(sam::record::Sequence::from(bases));
+        }
+
+        let quality_scores = self.quality_scores();
+
+        if !quality_scores.is_empty() && quality_scores[0] != MISSING_QUALITY_SCORE {
+            let scores = quality_scores
+                .iter()
+                .map(|&n| {
+                    sam::record::quality_scores::Score::try_from(n)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+
+            builder = builder.set_quality_scores(sam::record::QualityScores::from(scores));
+        }
+
+        let fields = self
+            .tags()
+            .iter()
+            .map(sam::record::data::Field::try_from)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let record = builder.set_data(sam::record::Data::from(fields)).build();
+
+        Ok(record)
+    }
+
+    /// Converts this CRAM record to a BAM record.
+    ///
+    /// This has the same requirements as [`Self::try_into_sam_record`].
+    pub fn try_into_bam_record(
+        &self,
+        reference_sequences: &sam::header::ReferenceSequences,
+    ) -> io::Result<bam::Record> {
+        let sam_record = self.try_into_sam_record(reference_sequences)?;
+        bam::Record::try_from_sam_record(reference_sequences, &sam_record)
+    }
+}
+
+impl sam::AlignmentRecord for Record {
+    fn try_into_sam_record(
+        &self,
+        reference_sequences: &sam::header::ReferenceSequences,
+    ) -> io::Result<sam::Record> {
+        Record::try_into_sam_record(self, reference_sequences)
+    }
+}
+
+fn get_reference_sequence_name(
+    reference_sequences: &sam::header::ReferenceSequences,
+    reference_sequence_id: bam::record::ReferenceSequenceId,
+) -> io::Result<Option<sam::record::ReferenceSequenceName>> {
+    let id = match *reference_sequence_id {
+        Some(id) => id as usize,
+        None => return Ok(None),
+    };
+
+    reference_sequences
+        .get_index(id)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid reference sequence ID: expected < {}, got {}",
+                    reference_sequences.len(),
+                    id
+                ),
+            )
+        })
+        .and_then(|(name, _)| {
+            name.parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
 }
 
 impl Default for Record {
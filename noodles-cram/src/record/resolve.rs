@@ -1,6 +1,9 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, io};
 
-use noodles_fasta as fasta;
+use noodles_sam::record::{
+    cigar::{op::Kind, Op},
+    Cigar,
+};
 
 use crate::{
     container::compression_header::preservation_map::substitution_matrix::Base,
@@ -9,68 +12,323 @@ use crate::{
 
 use super::Feature;
 
+/// The quality score used when a read position has no stored quality score.
+pub const MISSING_QUALITY_SCORE: u8 = 0xff;
+
+/// Resolves the read bases of a mapped record from its features and the reference sequence.
+///
+/// Bases at read positions without a feature are copied from the reference sequence.
 pub fn resolve_bases(
-    reference_sequence_record: &fasta::Record,
+    reference_sequence: &[u8],
     substitution_matrix: &SubstitutionMatrix,
     features: &[Feature],
     alignment_start: i32,
     read_len: usize,
-) -> Vec<u8> {
+) -> io::Result<Vec<u8>> {
     let mut buf = vec![b'-'; read_len];
 
-    let mut ref_pos = (alignment_start - 1) as usize;
+    let mut ref_pos = usize::try_from(alignment_start - 1)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
     let mut read_pos = 0;
 
-    let reference_sequence = reference_sequence_record.sequence();
+    let reference_base = |i: usize| {
+        reference_sequence
+            .get(i)
+            .map(|b| b.to_ascii_uppercase())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "reference position out of bounds: expected < {}, got {}",
+                        reference_sequence.len(),
+                        i
+                    ),
+                )
+            })
+    };
 
     for feature in features {
-        let feature_pos = feature.position() as usize;
+        let feature_pos = feature_index(feature)?;
 
-        while read_pos < feature_pos - 1 {
-            buf[read_pos] = reference_sequence[ref_pos];
+        while read_pos < feature_pos {
+            set_at(&mut buf, read_pos, reference_base(ref_pos)?)?;
             ref_pos += 1;
             read_pos += 1;
         }
 
         match feature {
+            Feature::Bases(_, bases) => {
+                for &base in bases {
+                    set_at(&mut buf, read_pos, base)?;
+                    read_pos += 1;
+                }
+
+                ref_pos += bases.len();
+            }
+            Feature::ReadBase(_, base, _) => {
+                set_at(&mut buf, read_pos, *base)?;
+                ref_pos += 1;
+                read_pos += 1;
+            }
             Feature::Substitution(_, code) => {
-                let base = reference_sequence[ref_pos] as char;
+                let base = char::from(reference_base(ref_pos)?);
                 let reference_base = Base::try_from(base).unwrap_or_default();
 
                 let read_base = substitution_matrix.get(reference_base, *code);
-                buf[read_pos] = char::from(read_base) as u8;
+                set_at(&mut buf, read_pos, char::from(read_base) as u8)?;
 
                 ref_pos += 1;
                 read_pos += 1;
             }
-            Feature::Insertion(_, bases) => {
+            Feature::Insertion(_, bases) | Feature::SoftClip(_, bases) => {
                 for &base in bases {
-                    buf[read_pos] = base;
+                    set_at(&mut buf, read_pos, base)?;
                     read_pos += 1;
                 }
             }
-            Feature::Deletion(_, len) => {
-                ref_pos += *len as usize;
-            }
             Feature::InsertBase(_, base) => {
-                buf[read_pos] = *base;
+                set_at(&mut buf, read_pos, *base)?;
                 read_pos += 1;
             }
-            Feature::SoftClip(_, bases) => {
-                for &base in bases {
-                    buf[read_pos] = base;
-                    read_pos += 1;
-                }
+            Feature::Deletion(_, len) | Feature::ReferenceSkip(_, len) => {
+                ref_pos += feature_len(*len)?;
             }
-            Feature::HardClip(..) => {}
-            _ => todo!("resolve_bases: {:?}", feature),
+            Feature::Scores(..)
+            | Feature::QualityScore(..)
+            | Feature::Padding(..)
+            | Feature::HardClip(..) => {}
         }
     }
 
     for base in buf.iter_mut().skip(read_pos) {
-        *base = reference_sequence[ref_pos];
+        *base = reference_base(ref_pos)?;
         ref_pos += 1;
     }
 
-    buf
+    Ok(buf)
+}
+
+/// Resolves the quality scores of a record from its features.
+///
+/// This is used when the quality scores are not stored as an array. Read positions without a
+/// quality score are set to [`MISSING_QUALITY_SCORE`].
+pub fn resolve_quality_scores(features: &[Feature], read_len: usize) -> io::Result<Vec<u8>> {
+    let mut scores = vec![MISSING_QUALITY_SCORE; read_len];
+
+    for feature in features {
+        let i = feature_index(feature)?;
+
+        match feature {
+            Feature::Scores(_, feature_scores) => {
+                for (j, &score) in feature_scores.iter().enumerate() {
+                    set_at(&mut scores, i + j, score)?;
+                }
+            }
+            Feature::ReadBase(_, _, score) | Feature::QualityScore(_, score) => {
+                set_at(&mut scores, i, *score)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(scores)
+}
+
+/// Builds the CIGAR of a mapped record from its features.
+///
+/// Read positions that are not covered by a feature are alignment matches (`M`).
+pub fn resolve_cigar(features: &[Feature], read_len: usize) -> io::Result<Cigar> {
+    let mut ops: Vec<Op> = Vec::new();
+    let mut read_pos = 0;
+
+    let mut push_op = |kind: Kind, len: usize| -> io::Result<()> {
+        if len == 0 {
+            return Ok(());
+        }
+
+        let len = u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        match ops.last_mut() {
+            Some(op) if op.kind() == kind => *op = Op::new(kind, op.len() + len),
+            _ => ops.push(Op::new(kind, len)),
+        }
+
+        Ok(())
+    };
+
+    for feature in features {
+        let feature_pos = feature_index(feature)?;
+
+        if feature_pos > read_pos {
+            push_op(Kind::Match, feature_pos - read_pos)?;
+            read_pos = feature_pos;
+        }
+
+        let (kind, len) = match feature {
+            Feature::Bases(_, bases) => (Kind::Match, bases.len()),
+            Feature::ReadBase(..) | Feature::Substitution(..) => (Kind::Match, 1),
+            Feature::Insertion(_, bases) => (Kind::Insertion, bases.len()),
+            Feature::InsertBase(..) => (Kind::Insertion, 1),
+            Feature::SoftClip(_, bases) => (Kind::SoftClip, bases.len()),
+            Feature::Deletion(_, len) => (Kind::Deletion, feature_len(*len)?),
+            Feature::ReferenceSkip(_, len) => (Kind::Skip, feature_len(*len)?),
+            Feature::Padding(_, len) => (Kind::Pad, feature_len(*len)?),
+            Feature::HardClip(_, len) => (Kind::HardClip, feature_len(*len)?),
+            Feature::Scores(..) | Feature::QualityScore(..) => continue,
+        };
+
+        push_op(kind, len)?;
+
+        if matches!(kind, Kind::Match | Kind::Insertion | Kind::SoftClip) {
+            read_pos += len;
+        }
+    }
+
+    if read_len > read_pos {
+        push_op(Kind::Match, read_len - read_pos)?;
+    }
+
+    Ok(Cigar::from(ops))
+}
+
+// Returns the 0-based read position of a feature.
+fn feature_index(feature: &Feature) -> io::Result<usize> {
+    usize::try_from(feature.position() - 1).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid feature position: {}", feature.position()),
+        )
+    })
+}
+
+fn feature_len(len: i32) -> io::Result<usize> {
+    usize::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn set_at(buf: &mut [u8], i: usize, value: u8) -> io::Result<()> {
+    let len = buf.len();
+
+    buf.get_mut(i).map(|b| *b = value).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("read position out of bounds: expected < {}, got {}", len, i),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_bases() -> io::Result<()> {
+        let reference_sequence = b"ACGTACGTacgt";
+        let substitution_matrix = SubstitutionMatrix::default();
+
+        let t = |features: &[Feature], read_len: usize, expected: &[u8]| -> io::Result<()> {
+            let actual = resolve_bases(
+                reference_sequence,
+                &substitution_matrix,
+                features,
+                2,
+                read_len,
+            )?;
+
+            assert_eq!(actual, expected);
+
+            Ok(())
+        };
+
+        t(&[], 4, b"CGTA")?;
+        t(&[Feature::Bases(1, vec![b'T', b'G'])], 4, b"TGTA")?;
+        t(&[Feature::ReadBase(2, b'Y', 30)], 4, b"CYTA")?;
+        t(&[Feature::Insertion(2, vec![b'G', b'G'])], 4, b"CGGG")?;
+        t(&[Feature::InsertBase(2, b'N')], 4, b"CNGT")?;
+        t(&[Feature::Deletion(2, 2)], 4, b"CACG")?;
+        t(&[Feature::ReferenceSkip(2, 3)], 4, b"CCGT")?;
+        t(&[Feature::SoftClip(1, vec![b'A', b'T'])], 4, b"ATCG")?;
+        t(
+            &[Feature::Padding(1, 2), Feature::HardClip(1, 2)],
+            4,
+            b"CGTA",
+        )?;
+        t(&[Feature::Scores(1, vec![8, 13])], 4, b"CGTA")?;
+        t(&[Feature::QualityScore(1, 8)], 4, b"CGTA")?;
+        t(&[], 10, b"CGTACGTACG")?;
+
+        let default_substitution = substitution_matrix.get(Base::C, 0);
+        t(
+            &[Feature::Substitution(1, 0)],
+            2,
+            &[char::from(default_substitution) as u8, b'G'],
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_bases_with_out_of_bounds_positions() {
+        let reference_sequence = b"ACGT";
+        let substitution_matrix = SubstitutionMatrix::default();
+
+        assert!(resolve_bases(reference_sequence, &substitution_matrix, &[], 2, 4).is_err());
+
+        let features = [Feature::InsertBase(5, b'N')];
+        assert!(resolve_bases(reference_sequence, &substitution_matrix, &features, 1, 4).is_err());
+
+        let features = [Feature::InsertBase(0, b'N')];
+        assert!(resolve_bases(reference_sequence, &substitution_matrix, &features, 1, 4).is_err());
+    }
+
+    #[test]
+    fn test_resolve_quality_scores() -> io::Result<()> {
+        let features = [
+            Feature::Scores(1, vec![8, 13]),
+            Feature::ReadBase(3, b'N', 21),
+            Feature::QualityScore(5, 34),
+        ];
+
+        assert_eq!(
+            resolve_quality_scores(&features, 6)?,
+            [8, 13, 21, MISSING_QUALITY_SCORE, 34, MISSING_QUALITY_SCORE]
+        );
+
+        assert!(resolve_quality_scores(&features, 4).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_cigar() -> io::Result<()> {
+        assert_eq!(
+            resolve_cigar(&[], 4)?,
+            Cigar::from(vec![Op::new(Kind::Match, 4)])
+        );
+
+        let features = [
+            Feature::SoftClip(1, vec![b'A', b'T']),
+            Feature::Substitution(4, 0),
+            Feature::QualityScore(4, 8),
+            Feature::Insertion(6, vec![b'G', b'G']),
+            Feature::Deletion(8, 3),
+            Feature::ReferenceSkip(8, 100),
+            Feature::Bases(9, vec![b'A']),
+            Feature::HardClip(11, 5),
+        ];
+
+        assert_eq!(
+            resolve_cigar(&features, 10)?,
+            Cigar::from(vec![
+                Op::new(Kind::SoftClip, 2),
+                Op::new(Kind::Match, 3),
+                Op::new(Kind::Insertion, 2),
+                Op::new(Kind::Deletion, 3),
+                Op::new(Kind::Skip, 100),
+                Op::new(Kind::Match, 3),
+                Op::new(Kind::HardClip, 5),
+            ])
+        );
+
+        Ok(())
+    }
 }
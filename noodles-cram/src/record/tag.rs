@@ -2,7 +2,10 @@ mod key;
 
 pub use self::key::Key;
 
+use std::{convert::TryFrom, io, str};
+
 use noodles_bam::record::data::field::Value;
+use noodles_sam as sam;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Tag {
@@ -23,3 +26,22 @@ impl Tag {
         &self.value
     }
 }
+
+impl TryFrom<&Tag> for sam::record::data::Field {
+    type Error = io::Error;
+
+    fn try_from(tag: &Tag) -> Result<Self, Self::Error> {
+        let raw_tag = tag.key().tag();
+
+        let sam_tag = str::from_utf8(&raw_tag)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            .and_then(|s| {
+                s.parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })?;
+
+        let value = sam::record::data::field::Value::from(tag.value().clone());
+
+        Ok(Self::new(sam_tag, value))
+    }
+}
@@ -0,0 +1,55 @@
+//! CRAM reference sequence repositories.
+
+mod in_memory;
+mod indexed_fasta;
+mod md5_cache;
+mod md5_checked;
+
+pub use self::{in_memory::InMemory, indexed_fasta::IndexedFasta, md5_cache::Md5Cache};
+
+pub(crate) use self::md5_checked::Md5Checked;
+
+use std::{convert::TryFrom, io};
+
+use noodles_sam::header::{ReferenceSequence, ReferenceSequences};
+
+/// A reference sequence repository.
+///
+/// A repository provides the sequences of reference sequences described in a SAM header. Writers
+/// use them to calculate slice reference MD5 checksums, and readers use them to restore the bases
+/// of mapped records.
+pub trait ReferenceSequenceRepository {
+    /// Returns the sequence of the given reference sequence.
+    ///
+    /// This returns `None` if the repository does not have the reference sequence.
+    fn get(&mut self, reference_sequence: &ReferenceSequence) -> io::Result<Option<&[u8]>>;
+}
+
+impl<T> ReferenceSequenceRepository for &mut T
+where
+    T: ReferenceSequenceRepository + ?Sized,
+{
+    fn get(&mut self, reference_sequence: &ReferenceSequence) -> io::Result<Option<&[u8]>> {
+        (**self).get(reference_sequence)
+    }
+}
+
+// Returns the sequence of the reference sequence at the given index in the SAM header.
+pub(crate) fn get_by_id<'a>(
+    reference_sequence_repository: &'a mut dyn ReferenceSequenceRepository,
+    reference_sequences: &ReferenceSequences,
+    reference_sequence_id: i32,
+) -> io::Result<Option<&'a [u8]>> {
+    let reference_sequence = usize::try_from(reference_sequence_id)
+        .ok()
+        .and_then(|i| reference_sequences.get_index(i))
+        .map(|(_, reference_sequence)| reference_sequence)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid reference sequence ID: {}", reference_sequence_id),
+            )
+        })?;
+
+    reference_sequence_repository.get(reference_sequence)
+}
@@ -0,0 +1,84 @@
+use std::{collections::HashMap, io};
+
+use noodles_fasta as fasta;
+use noodles_sam::header::ReferenceSequence;
+
+use super::ReferenceSequenceRepository;
+
+/// An in-memory reference sequence repository.
+///
+/// Reference sequences are looked up by name.
+#[derive(Debug, Default)]
+pub struct InMemory {
+    records: Vec<fasta::Record>,
+    indices: HashMap<String, usize>,
+}
+
+impl InMemory {
+    /// Creates an in-memory reference sequence repository from FASTA records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::InMemory;
+    /// use noodles_fasta as fasta;
+    ///
+    /// let repository = InMemory::new(vec![fasta::Record::new(
+    ///     fasta::record::Definition::new(String::from("sq0"), None),
+    ///     b"ACGT".to_vec(),
+    /// )]);
+    /// ```
+    pub fn new(records: Vec<fasta::Record>) -> Self {
+        let indices = records
+            .iter()
+            .enumerate()
+            .map(|(i, record)| (record.reference_sequence_name().into(), i))
+            .collect();
+
+        Self { records, indices }
+    }
+}
+
+impl From<Vec<fasta::Record>> for InMemory {
+    fn from(records: Vec<fasta::Record>) -> Self {
+        Self::new(records)
+    }
+}
+
+impl ReferenceSequenceRepository for InMemory {
+    fn get(&mut self, reference_sequence: &ReferenceSequence) -> io::Result<Option<&[u8]>> {
+        let records = &self.records;
+
+        Ok(self
+            .indices
+            .get(reference_sequence.name())
+            .map(|&i| records[i].sequence()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let mut repository = InMemory::new(vec![
+            fasta::Record::new(
+                fasta::record::Definition::new(String::from("sq0"), None),
+                b"ACGT".to_vec(),
+            ),
+            fasta::Record::new(
+                fasta::record::Definition::new(String::from("sq1"), None),
+                b"NNNN".to_vec(),
+            ),
+        ]);
+
+        let reference_sequence = ReferenceSequence::new(String::from("sq1"), 4);
+        assert_eq!(repository.get(&reference_sequence)?, Some(&b"NNNN"[..]));
+
+        let reference_sequence = ReferenceSequence::new(String::from("sq2"), 4);
+        assert!(repository.get(&reference_sequence)?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io::{self, BufRead, BufReader, Seek, SeekFrom},
+    path::Path,
+};
+
+use noodles_fasta::{self as fasta, fai};
+use noodles_sam::header::ReferenceSequence;
+
+use super::ReferenceSequenceRepository;
+
+/// An indexed FASTA reference sequence repository.
+///
+/// Reference sequences are looked up by name and read using the FASTA index (FAI). Only the most
+/// recently read sequence is kept in memory.
+pub struct IndexedFasta<R> {
+    reader: fasta::Reader<R>,
+    index: Vec<fai::Record>,
+    cache: Option<(String, Vec<u8>)>,
+}
+
+impl<R> IndexedFasta<R>
+where
+    R: BufRead + Seek,
+{
+    /// Creates an indexed FASTA reference sequence repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_cram::reference_sequence_repository::IndexedFasta;
+    /// use noodles_fasta::{self as fasta, fai};
+    ///
+    /// let data = b">sq0\nACGT\n";
+    /// let reader = fasta::Reader::new(Cursor::new(&data[..]));
+    /// let index = vec![fai::Record::new(String::from("sq0"), 4, 5, 4, 5)];
+    ///
+    /// let repository = IndexedFasta::new(reader, index);
+    /// ```
+    pub fn new(reader: fasta::Reader<R>, index: Vec<fai::Record>) -> Self {
+        Self {
+            reader,
+            index,
+            cache: None,
+        }
+    }
+}
+
+impl IndexedFasta<BufReader<File>> {
+    /// Opens a FASTA file and its associated index.
+    ///
+    /// The index is read from `<src>.fai`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_cram::reference_sequence_repository::IndexedFasta;
+    /// let repository = IndexedFasta::open("reference.fa")?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn open<P>(src: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let src = src.as_ref();
+
+        let mut index_src = src.as_os_str().to_owned();
+        index_src.push(".fai");
+
+        let index = File::open(index_src)
+            .map(BufReader::new)
+            .and_then(read_index)?;
+
+        let reader = File::open(src)
+            .map(BufReader::new)
+            .map(fasta::Reader::new)?;
+
+        Ok(Self::new(reader, index))
+    }
+}
+
+impl<R> ReferenceSequenceRepository for IndexedFasta<R>
+where
+    R: BufRead + Seek,
+{
+    fn get(&mut self, reference_sequence: &ReferenceSequence) -> io::Result<Option<&[u8]>> {
+        let name = reference_sequence.name();
+
+        let is_cached = matches!(&self.cache, Some((cached_name, _)) if cached_name == name);
+
+        if !is_cached {
+            let index_record = match self
+                .index
+                .iter()
+                .find(|record| record.reference_sequence_name() == name)
+            {
+                Some(record) => record,
+                None => return Ok(None),
+            };
+
+            let len = usize::try_from(index_record.len())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            self.reader.seek(SeekFrom::Start(index_record.offset()))?;
+
+            let mut sequence = Vec::with_capacity(len);
+            self.reader.read_sequence(&mut sequence)?;
+
+            if sequence.len() < len {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "reference sequence length mismatch: expected {}, got {}",
+                        len,
+                        sequence.len()
+                    ),
+                ));
+            }
+
+            sequence.truncate(len);
+
+            self.cache = Some((name.into(), sequence));
+        }
+
+        Ok(self.cache.as_ref().map(|(_, sequence)| &sequence[..]))
+    }
+}
+
+fn read_index<R>(reader: R) -> io::Result<Vec<fai::Record>>
+where
+    R: BufRead,
+{
+    let mut reader = fai::Reader::new(reader);
+    let mut index = Vec::new();
+    let mut buf = String::new();
+
+    loop {
+        buf.clear();
+
+        if reader.read_record(&mut buf)? == 0 {
+            break;
+        }
+
+        let record = buf
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        index.push(record);
+    }
+
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let data = b">sq0\nACGT\n>sq1\nNNNN\nNNNN\nNN\n";
+        let reader = fasta::Reader::new(Cursor::new(&data[..]));
+        let index = vec![
+            fai::Record::new(String::from("sq0"), 4, 5, 4, 5),
+            fai::Record::new(String::from("sq1"), 10, 15, 4, 5),
+        ];
+
+        let mut repository = IndexedFasta::new(reader, index);
+
+        let reference_sequence = ReferenceSequence::new(String::from("sq1"), 10);
+        assert_eq!(
+            repository.get(&reference_sequence)?,
+            Some(&b"NNNNNNNNNN"[..])
+        );
+        assert_eq!(
+            repository.get(&reference_sequence)?,
+            Some(&b"NNNNNNNNNN"[..])
+        );
+
+        let reference_sequence = ReferenceSequence::new(String::from("sq0"), 4);
+        assert_eq!(repository.get(&reference_sequence)?, Some(&b"ACGT"[..]));
+
+        let reference_sequence = ReferenceSequence::new(String::from("sq2"), 4);
+        assert!(repository.get(&reference_sequence)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_index() -> io::Result<()> {
+        let data = b"sq0\t4\t5\t4\t5\nsq1\t10\t15\t4\t5\n";
+        let index = read_index(&data[..])?;
+
+        assert_eq!(
+            index,
+            [
+                fai::Record::new(String::from("sq0"), 4, 5, 4, 5),
+                fai::Record::new(String::from("sq1"), 10, 15, 4, 5),
+            ]
+        );
+
+        Ok(())
+    }
+}
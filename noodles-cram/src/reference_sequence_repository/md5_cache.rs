@@ -0,0 +1,271 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use noodles_sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+use super::ReferenceSequenceRepository;
+
+const REF_CACHE: &str = "REF_CACHE";
+const REF_PATH: &str = "REF_PATH";
+
+const PATH_SEPARATOR: char = ':';
+
+/// An MD5-keyed reference sequence cache repository.
+///
+/// This reads sequences from local directories using the layout of the htslib `REF_CACHE` and
+/// `REF_PATH` environment variables. Reference sequences are looked up by their MD5 checksums
+/// (`M5`), which are expanded into paths using path templates. In a template, `%s` is replaced
+/// with the remaining characters of the hex-encoded checksum, and `%<n>s`, with the next `n`
+/// characters. If a template does not have `%s`, the checksum is appended as a path component.
+///
+/// Each file is expected to contain only the sequence, i.e., no definition or newlines. Only the
+/// most recently read sequence is kept in memory.
+#[derive(Debug, Default)]
+pub struct Md5Cache {
+    templates: Vec<String>,
+    cache: Option<(Md5Checksum, Vec<u8>)>,
+}
+
+impl Md5Cache {
+    /// Creates an MD5-keyed reference sequence cache repository from a list of path templates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::Md5Cache;
+    /// let repository = Md5Cache::new(vec![String::from("/tmp/hts-ref/%2s/%2s/%s")]);
+    /// ```
+    pub fn new(templates: Vec<String>) -> Self {
+        Self {
+            templates,
+            cache: None,
+        }
+    }
+
+    /// Creates an MD5-keyed reference sequence cache repository using the `REF_CACHE` and
+    /// `REF_PATH` environment variables.
+    ///
+    /// `REF_CACHE` is a single path template and is searched first. `REF_PATH` is a
+    /// colon-separated list of path templates. URL entries in `REF_PATH` are not supported and
+    /// are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::Md5Cache;
+    /// let repository = Md5Cache::from_env();
+    /// ```
+    pub fn from_env() -> Self {
+        let mut templates = Vec::new();
+
+        if let Ok(template) = env::var(REF_CACHE) {
+            if !template.is_empty() {
+                templates.push(template);
+            }
+        }
+
+        if let Ok(s) = env::var(REF_PATH) {
+            templates.extend(parse_ref_path(&s));
+        }
+
+        Self::new(templates)
+    }
+}
+
+impl ReferenceSequenceRepository for Md5Cache {
+    fn get(&mut self, reference_sequence: &ReferenceSequence) -> io::Result<Option<&[u8]>> {
+        let md5_checksum = match reference_sequence.md5_checksum() {
+            Some(md5_checksum) => md5_checksum,
+            None => return Ok(None),
+        };
+
+        let is_cached =
+            matches!(&self.cache, Some((cached_checksum, _)) if *cached_checksum == md5_checksum);
+
+        if !is_cached {
+            let hex_checksum = md5_checksum.to_string();
+            let mut sequence = None;
+
+            for template in &self.templates {
+                let path = expand_template(template, &hex_checksum);
+
+                match read_sequence(&path)? {
+                    Some(buf) => {
+                        sequence = Some(buf);
+                        break;
+                    }
+                    None => continue,
+                }
+            }
+
+            match sequence {
+                Some(buf) => self.cache = Some((md5_checksum, buf)),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(self.cache.as_ref().map(|(_, sequence)| &sequence[..]))
+    }
+}
+
+fn read_sequence(path: &Path) -> io::Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(buf) => Ok(Some(buf)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn parse_ref_path(s: &str) -> Vec<String> {
+    let mut templates = Vec::new();
+    let mut components = s.split(PATH_SEPARATOR).peekable();
+
+    while let Some(component) = components.next() {
+        // e.g., `http://www.ebi.ac.uk/ena/cram/md5/%s` is split into `http` and
+        // `//www.ebi.ac.uk/ena/cram/md5/%s`.
+        if components
+            .peek()
+            .map(|next| next.starts_with("//"))
+            .unwrap_or(false)
+        {
+            components.next();
+            continue;
+        }
+
+        if !component.is_empty() {
+            templates.push(component.into());
+        }
+    }
+
+    templates
+}
+
+fn expand_template(template: &str, hex_checksum: &str) -> PathBuf {
+    let mut path = String::new();
+    let mut remaining = hex_checksum;
+    let mut has_remaining_placeholder = false;
+
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            path.push(c);
+            continue;
+        }
+
+        let mut digits = String::new();
+
+        while let Some(&d) = chars.peek() {
+            if d.is_ascii_digit() {
+                digits.push(d);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        if chars.peek() != Some(&'s') {
+            path.push(c);
+            path.push_str(&digits);
+            continue;
+        }
+
+        chars.next();
+
+        if digits.is_empty() {
+            path.push_str(remaining);
+            remaining = "";
+            has_remaining_placeholder = true;
+        } else {
+            let n = digits
+                .parse::<usize>()
+                .map(|n| n.min(remaining.len()))
+                .unwrap_or(remaining.len());
+
+            path.push_str(&remaining[..n]);
+            remaining = &remaining[n..];
+        }
+    }
+
+    let mut path = PathBuf::from(path);
+
+    if !has_remaining_placeholder {
+        path.push(remaining);
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let md5_checksum = Md5Checksum::from([
+            0xf7, 0xf9, 0x3f, 0x1a, 0x8b, 0x0a, 0x31, 0x1b, 0x4c, 0x47, 0xb1, 0x6c, 0x30, 0xf0,
+            0x71, 0x8f,
+        ]);
+
+        let root = env::temp_dir().join(format!("noodles-cram-md5-cache-{}", std::process::id()));
+        let dst = root
+            .join("f7")
+            .join("f9")
+            .join("3f1a8b0a311b4c47b16c30f0718f");
+        fs::create_dir_all(dst.parent().unwrap())?;
+        fs::write(&dst, b"ACGT")?;
+
+        let template = format!("{}/%2s/%2s/%s", root.display());
+        let mut repository = Md5Cache::new(vec![String::from("/nonexistent/%s"), template]);
+
+        let reference_sequence = ReferenceSequence::builder()
+            .set_name("sq0")
+            .set_length(4)
+            .set_md5_checksum(md5_checksum)
+            .build();
+
+        let result = repository.get(&reference_sequence);
+        fs::remove_dir_all(&root)?;
+        assert_eq!(result?, Some(&b"ACGT"[..]));
+
+        let reference_sequence = ReferenceSequence::new(String::from("sq0"), 4);
+        assert!(repository.get(&reference_sequence)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ref_path() {
+        assert_eq!(
+            parse_ref_path("/data/ref/%s:http://www.ebi.ac.uk/ena/cram/md5/%s::/tmp/%2s/%s"),
+            [String::from("/data/ref/%s"), String::from("/tmp/%2s/%s")]
+        );
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let hex_checksum = "f7f93f1a8b0a311b4c47b16c30f0718f";
+
+        assert_eq!(
+            expand_template("/tmp/hts-ref/%2s/%2s/%s", hex_checksum),
+            PathBuf::from("/tmp/hts-ref/f7/f9/3f1a8b0a311b4c47b16c30f0718f")
+        );
+
+        assert_eq!(
+            expand_template("/tmp/hts-ref/%s.fa", hex_checksum),
+            PathBuf::from("/tmp/hts-ref/f7f93f1a8b0a311b4c47b16c30f0718f.fa")
+        );
+
+        assert_eq!(
+            expand_template("/tmp/hts-ref", hex_checksum),
+            PathBuf::from("/tmp/hts-ref/f7f93f1a8b0a311b4c47b16c30f0718f")
+        );
+
+        assert_eq!(
+            expand_template("/tmp/100%/%2s", hex_checksum),
+            PathBuf::from("/tmp/100%/f7/f93f1a8b0a311b4c47b16c30f0718f")
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::{collections::HashSet, io};
+
+use md5::{Digest, Md5};
+use noodles_sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+use super::ReferenceSequenceRepository;
+
+const CHUNK_SIZE: usize = 4096;
+
+// A reference sequence repository that checks sequences against the MD5 checksums (`M5`) of their
+// SAM header reference sequences.
+//
+// Each reference sequence is only checked the first time it is read.
+#[derive(Debug)]
+pub(crate) struct Md5Checked<R> {
+    inner: R,
+    checked_names: HashSet<String>,
+}
+
+impl<R> Md5Checked<R>
+where
+    R: ReferenceSequenceRepository,
+{
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            checked_names: HashSet::new(),
+        }
+    }
+}
+
+impl<R> ReferenceSequenceRepository for Md5Checked<R>
+where
+    R: ReferenceSequenceRepository,
+{
+    fn get(&mut self, reference_sequence: &ReferenceSequence) -> io::Result<Option<&[u8]>> {
+        let sequence = self.inner.get(reference_sequence)?;
+
+        if let Some(sequence) = sequence {
+            if !self.checked_names.contains(reference_sequence.name()) {
+                validate_md5_checksum(reference_sequence, sequence)?;
+                self.checked_names.insert(reference_sequence.name().into());
+            }
+        }
+
+        Ok(sequence)
+    }
+}
+
+fn validate_md5_checksum(
+    reference_sequence: &ReferenceSequence,
+    sequence: &[u8],
+) -> io::Result<()> {
+    let expected = match reference_sequence.md5_checksum() {
+        Some(md5_checksum) => md5_checksum,
+        None => return Ok(()),
+    };
+
+    let actual = calculate_md5_checksum(sequence);
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "reference sequence MD5 mismatch for {}: expected {}, got {}",
+                reference_sequence.name(),
+                expected,
+                actual
+            ),
+        ))
+    }
+}
+
+// The checksum is calculated over the uppercased sequence.
+fn calculate_md5_checksum(sequence: &[u8]) -> Md5Checksum {
+    let mut hasher = Md5::new();
+
+    for chunk in sequence.chunks(CHUNK_SIZE) {
+        hasher.update(chunk.to_ascii_uppercase());
+    }
+
+    Md5Checksum::from(<[u8; 16]>::from(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_fasta as fasta;
+
+    use crate::reference_sequence_repository::InMemory;
+
+    use super::*;
+
+    fn build_reference_sequence(md5_checksum: Option<Md5Checksum>) -> ReferenceSequence {
+        let mut builder = ReferenceSequence::builder().set_name("sq0").set_length(8);
+
+        if let Some(md5_checksum) = md5_checksum {
+            builder = builder.set_md5_checksum(md5_checksum);
+        }
+
+        builder.build()
+    }
+
+    fn build_repository() -> Md5Checked<InMemory> {
+        Md5Checked::new(InMemory::new(vec![fasta::Record::new(
+            fasta::record::Definition::new(String::from("sq0"), None),
+            b"acgtACGT".to_vec(),
+        )]))
+    }
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let mut repository = build_repository();
+
+        // echo -n ACGTACGT | md5sum
+        let md5_checksum: Md5Checksum = "cc0af3a4fedb18378b4b57b98068e69f".parse().unwrap();
+        let reference_sequence = build_reference_sequence(Some(md5_checksum));
+        assert_eq!(repository.get(&reference_sequence)?, Some(&b"acgtACGT"[..]));
+
+        let reference_sequence = build_reference_sequence(None);
+        assert_eq!(repository.get(&reference_sequence)?, Some(&b"acgtACGT"[..]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_md5_checksum_mismatch() {
+        let mut repository = build_repository();
+
+        let md5_checksum = Md5Checksum::from([0xff; 16]);
+        let reference_sequence = build_reference_sequence(Some(md5_checksum));
+
+        assert!(matches!(
+            repository.get(&reference_sequence),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use std::io::{self, Read, Write};
+
+use crate::{
+    golomb::{offset_value, read_unary, remove_offset, write_unary},
+    num::Itf8,
+    BitReader, BitWriter,
+};
+
+/// Reads a subexponential-encoded integer.
+pub fn read<R>(reader: &mut BitReader<R>, offset: Itf8, k: Itf8) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let k = parse_k(k)?;
+
+    let u = read_unary(reader)? as usize;
+
+    let x = if u == 0 {
+        reader.read_u32(k)?
+    } else {
+        let b = u + k - 1;
+
+        if b >= 32 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid subexponential-encoded value",
+            ));
+        }
+
+        (1 << b) | reader.read_u32(b)?
+    };
+
+    remove_offset(offset, x)
+}
+
+/// Writes an integer using subexponential encoding.
+pub fn write<W>(writer: &mut BitWriter<W>, offset: Itf8, k: Itf8, value: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let k = parse_k(k)?;
+    let x = offset_value(offset, value)?;
+
+    let (b, u) = if x < (1 << k) {
+        (k, 0)
+    } else {
+        let b = (31 - x.leading_zeros()) as usize;
+        (b, b - k + 1)
+    };
+
+    write_unary(writer, u as u32)?;
+    writer.write_u32(x, b)
+}
+
+fn parse_k(k: Itf8) -> io::Result<usize> {
+    if (0..32).contains(&k) {
+        Ok(k as usize)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid subexponential parameter: {}", k),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        // k = 2
+        // 3 => 0 11, 4 => 10 00, 13 => 110 101
+        let data = [0b01110001, 0b10101000];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(read(&mut reader, 0, 2)?, 3);
+        assert_eq!(read(&mut reader, 0, 2)?, 4);
+        assert_eq!(read(&mut reader, 0, 2)?, 13);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_overflowing_value() {
+        // k = 0, u = 32, b = 31
+        let data = [0xff, 0xff, 0xff, 0xff, 0x7f, 0xff, 0xff, 0xff, 0xff];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert!(matches!(
+            read(&mut reader, 0, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_write() -> io::Result<()> {
+        let mut writer = BitWriter::new(Vec::new());
+
+        write(&mut writer, 0, 2, 3)?;
+        write(&mut writer, 0, 2, 4)?;
+        write(&mut writer, 0, 2, 13)?;
+        writer.try_finish()?;
+
+        assert_eq!(writer.get_ref(), &[0b01110001, 0b10101000]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> io::Result<()> {
+        let values = [-1, 0, 1, 2, 3, 4, 21, 144, 4181];
+
+        for &k in &[0, 1, 2, 5] {
+            let mut writer = BitWriter::new(Vec::new());
+
+            for &value in &values {
+                write(&mut writer, 1, k, value)?;
+            }
+
+            let data = writer.finish()?;
+            let mut reader = BitReader::new(&data[..]);
+
+            for &value in &values {
+                assert_eq!(read(&mut reader, 1, k)?, value);
+            }
+        }
+
+        Ok(())
+    }
+}
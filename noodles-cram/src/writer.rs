@@ -7,14 +7,19 @@ pub mod slice;
 
 use std::{
     convert::TryFrom,
+    fmt,
     io::{self, Write},
     mem,
 };
 
-use noodles_fasta as fasta;
 use noodles_sam as sam;
 
-use super::{container::Container, data_container, DataContainer, Record, MAGIC_NUMBER};
+use super::{
+    container::Container,
+    data_container,
+    reference_sequence_repository::{get_by_id, Md5Checked},
+    DataContainer, Record, ReferenceSequenceRepository, MAGIC_NUMBER,
+};
 
 use self::block::write_block;
 
@@ -25,14 +30,22 @@ const RECORD_COUNTER_START: i64 = 0;
 
 /// A CRAM writer.
 ///
+/// The writer uses a [`ReferenceSequenceRepository`] to look up the reference sequences of mapped
+/// records. Reference sequences are matched to the reference sequence dictionary of the header
+/// passed to [`Writer::write_file_header`]. The repository can be owned or borrowed, e.g., to
+/// share it with a reader.
+///
+/// Each reference sequence is checked against its MD5 checksum (`M5`) in the header, if set, the
+/// first time it is used.
+///
 /// # Examples
 ///
 /// ```
 /// # use std::io;
-/// use noodles_cram as cram;
+/// use noodles_cram::{self as cram, reference_sequence_repository::InMemory};
 /// use noodles_sam as sam;
 ///
-/// let mut writer = cram::Writer::new(Vec::new(), Vec::new());
+/// let mut writer = cram::Writer::new(Vec::new(), InMemory::default());
 /// writer.write_file_definition()?;
 ///
 /// let header = sam::Header::builder().add_comment("noodles-cram").build();
@@ -42,33 +55,39 @@ const RECORD_COUNTER_START: i64 = 0;
 /// writer.write_record(record)?;
 /// # Ok::<(), io::Error>(())
 /// ```
-#[derive(Debug)]
-pub struct Writer<W>
+pub struct Writer<W, R>
 where
     W: Write,
+    R: ReferenceSequenceRepository,
 {
     inner: W,
-    reference_sequences: Vec<fasta::Record>,
+    reference_sequence_repository: Md5Checked<R>,
+    reference_sequences: sam::header::ReferenceSequences,
     data_container_builder: data_container::Builder,
     record_counter: i64,
 }
 
-impl<W> Writer<W>
+impl<W, R> Writer<W, R>
 where
     W: Write,
+    R: ReferenceSequenceRepository,
 {
     /// Creates a new CRAM writer.
     ///
     /// # Examples
     ///
     /// ```
-    /// use noodles_cram as cram;
-    /// let writer = cram::Writer::new(Vec::new(), Vec::new());
+    /// use noodles_cram::{self as cram, reference_sequence_repository::InMemory};
+    /// let writer = cram::Writer::new(Vec::new(), InMemory::default());
+    ///
+    /// let mut repository = InMemory::default();
+    /// let writer = cram::Writer::new(Vec::new(), &mut repository);
     /// ```
-    pub fn new(inner: W, reference_sequences: Vec<fasta::Record>) -> Self {
+    pub fn new(inner: W, reference_sequence_repository: R) -> Self {
         Self {
             inner,
-            reference_sequences,
+            reference_sequence_repository: Md5Checked::new(reference_sequence_repository),
+            reference_sequences: sam::header::ReferenceSequences::default(),
             data_container_builder: DataContainer::builder(RECORD_COUNTER_START),
             record_counter: RECORD_COUNTER_START,
         }
@@ -79,8 +98,8 @@ where
     /// # Examples
     ///
     /// ```
-    /// use noodles_cram as cram;
-    /// let writer = cram::Writer::new(Vec::new(), Vec::new());
+    /// use noodles_cram::{self as cram, reference_sequence_repository::InMemory};
+    /// let writer = cram::Writer::new(Vec::new(), InMemory::default());
     /// assert!(writer.get_ref().is_empty());
     /// ```
     pub fn get_ref(&self) -> &W {
@@ -97,8 +116,8 @@ where
     ///
     /// ```
     /// # use std::io;
-    /// use noodles_cram as cram;
-    /// let mut writer = cram::Writer::new(Vec::new(), Vec::new());
+    /// use noodles_cram::{self as cram, reference_sequence_repository::InMemory};
+    /// let mut writer = cram::Writer::new(Vec::new(), InMemory::default());
     /// writer.try_finish()?;
     /// # Ok::<(), io::Error>(())
     /// ```
@@ -116,9 +135,9 @@ where
     ///
     /// ```
     /// # use std::io;
-    /// use noodles_cram as cram;
+    /// use noodles_cram::{self as cram, reference_sequence_repository::InMemory};
     ///
-    /// let mut writer = cram::Writer::new(Vec::new(), Vec::new());
+    /// let mut writer = cram::Writer::new(Vec::new(), InMemory::default());
     /// writer.write_file_definition()?;
     ///
     /// assert_eq!(writer.get_ref(), &[
@@ -154,28 +173,46 @@ where
     pub fn write_file_header(&mut self, header: &sam::Header) -> io::Result<()> {
         Container::try_from(header)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-            .and_then(|container| self.write_container(&container))
+            .and_then(|container| self.write_container(&container))?;
+
+        self.reference_sequences = header.reference_sequences().clone();
+
+        Ok(())
     }
 
     /// Writes a CRAM record.
     ///
+    /// The reference sequence of a record with a reference sequence ID must be in the reference
+    /// sequence repository.
+    ///
     /// # Examples
     ///
     /// ```
     /// # use std::io;
-    /// use noodles_cram as cram;
-    /// let mut writer = cram::Writer::new(Vec::new(), Vec::new());
+    /// use noodles_cram::{self as cram, reference_sequence_repository::InMemory};
+    /// let mut writer = cram::Writer::new(Vec::new(), InMemory::default());
     /// let record = cram::Record::default();
     /// writer.write_record(record)?;
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_record(&mut self, mut record: Record) -> io::Result<()> {
         loop {
-            match add_record(
-                &mut self.data_container_builder,
-                &self.reference_sequences,
-                record,
-            ) {
+            let reference_sequence = match *record.reference_sequence_id() {
+                Some(id) => get_by_id(
+                    &mut self.reference_sequence_repository,
+                    &self.reference_sequences,
+                    id,
+                )?
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence")
+                })?,
+                None => &[],
+            };
+
+            match self
+                .data_container_builder
+                .add_record(reference_sequence, record)
+            {
                 Ok(_) => {
                     self.record_counter += 1;
                     return Ok(());
@@ -217,7 +254,10 @@ where
         let base_count = data_container_builder.base_count();
 
         data_container_builder
-            .build(&self.reference_sequences)
+            .build(
+                &mut self.reference_sequence_repository,
+                &self.reference_sequences,
+            )
             .and_then(|data_container| {
                 Container::try_from_data_container(&data_container, base_count)
             })
@@ -225,25 +265,195 @@ where
     }
 }
 
-impl<W> Drop for Writer<W>
+impl<W, R> Drop for Writer<W, R>
 where
     W: Write,
+    R: ReferenceSequenceRepository,
 {
     fn drop(&mut self) {
         let _ = self.try_finish();
     }
 }
 
-fn add_record(
-    data_container_builder: &mut data_container::Builder,
-    reference_sequences: &[fasta::Record],
-    record: Record,
-) -> Result<(), data_container::builder::AddRecordError> {
-    let reference_sequence = record
-        .reference_sequence_id()
-        .and_then(|id| reference_sequences.get(id as usize))
-        .map(|rs| rs.sequence())
-        .unwrap_or_default();
-
-    data_container_builder.add_record(reference_sequence, record)
+impl<W, R> fmt::Debug for Writer<W, R>
+where
+    W: Write + fmt::Debug,
+    R: ReferenceSequenceRepository,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Writer")
+            .field("inner", &self.inner)
+            .field("reference_sequences", &self.reference_sequences)
+            .field("data_container_builder", &self.data_container_builder)
+            .field("record_counter", &self.record_counter)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_fasta as fasta;
+    use noodles_sam::header::ReferenceSequence;
+
+    use crate::reference_sequence_repository::InMemory;
+
+    use super::*;
+
+    fn build_header() -> sam::Header {
+        sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .build()
+    }
+
+    fn build_record() -> Record {
+        Record::builder()
+            .set_bam_flags(sam::record::Flags::empty())
+            .set_reference_sequence_id(0.into())
+            .set_alignment_start(2)
+            .set_read_length(4)
+            .set_bases(b"TCAC".to_vec())
+            .build()
+    }
+
+    #[test]
+    fn test_write_record_with_borrowed_reference_sequence_repository() -> io::Result<()> {
+        let mut reference_sequence_repository = InMemory::new(vec![fasta::Record::new(
+            fasta::record::Definition::new(String::from("sq0"), None),
+            b"TTCACCCA".to_vec(),
+        )]);
+
+        let mut writer = Writer::new(Vec::new(), &mut reference_sequence_repository);
+        writer.reference_sequences = build_header().reference_sequences().clone();
+        writer.write_record(build_record())?;
+        writer.try_finish()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_core_data_series_encodings() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use md5::{Digest, Md5};
+        use noodles_sam::header::reference_sequence::Md5Checksum;
+
+        use crate::{
+            container::compression_header::Encoding, reader::Reader, record::Flags as CramFlags,
+        };
+
+        let reference_sequence = b"TTCACCCA";
+
+        let mut hasher = Md5::new();
+        hasher.update(reference_sequence);
+        let md5_checksum = Md5Checksum::from(<[u8; 16]>::from(hasher.finalize()));
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq0")
+                    .set_length(8)
+                    .set_md5_checksum(md5_checksum)
+                    .build(),
+            )
+            .build();
+
+        let build_reference_sequence_repository = || {
+            InMemory::new(vec![fasta::Record::new(
+                fasta::record::Definition::new(String::from("sq0"), None),
+                reference_sequence.to_vec(),
+            )])
+        };
+
+        let records = [
+            (sam::record::Flags::empty(), 2, &b"TCAC"[..]),
+            (sam::record::Flags::REVERSE_COMPLEMENTED, 4, &b"ACC"[..]),
+        ];
+
+        let mut writer = Writer::new(Vec::new(), build_reference_sequence_repository());
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for &(bam_flags, alignment_start, bases) in &records {
+            let record = Record::builder()
+                .set_bam_flags(bam_flags)
+                .set_flags(CramFlags::QUALITY_SCORES_STORED_AS_ARRAY)
+                .set_reference_sequence_id(0.into())
+                .set_alignment_start(alignment_start)
+                .set_read_length(bases.len() as i32)
+                .set_bases(bases.to_vec())
+                .set_quality_scores(vec![45; bases.len()])
+                .build();
+
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+        let data = writer.get_ref().clone();
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let data_container = DataContainer::try_from(reader.read_container()?)?;
+        let data_series_encoding_map = data_container
+            .compression_header()
+            .data_series_encoding_map();
+
+        assert_eq!(
+            data_series_encoding_map.bam_bit_flags_encoding(),
+            &Encoding::Beta(0, 5)
+        );
+        assert_eq!(
+            data_series_encoding_map.cram_bit_flags_encoding(),
+            &Encoding::Huffman(
+                vec![i32::from(u8::from(
+                    CramFlags::QUALITY_SCORES_STORED_AS_ARRAY
+                ))],
+                vec![0]
+            )
+        );
+        assert_eq!(
+            data_series_encoding_map.read_lengths_encoding(),
+            &Encoding::Beta(-3, 1)
+        );
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut reference_sequence_repository = build_reference_sequence_repository();
+        let actual: Vec<_> = reader
+            .resolved_records(&header, &mut reference_sequence_repository)
+            .map(|result| {
+                result.map(|record| {
+                    (
+                        record.bam_flags(),
+                        record.alignment_start(),
+                        record.bases().to_vec(),
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let expected: Vec<_> = records
+            .iter()
+            .map(|&(bam_flags, alignment_start, bases)| {
+                (bam_flags, alignment_start, bases.to_vec())
+            })
+            .collect();
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_missing_reference_sequence() {
+        let mut writer = Writer::new(Vec::new(), InMemory::default());
+        writer.reference_sequences = build_header().reference_sequences().clone();
+
+        assert!(matches!(
+            writer.write_record(build_record()),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
 }
@@ -14,18 +14,20 @@ where
     match encoding {
         Encoding::Null => write_null_encoding(writer),
         Encoding::External(block_content_id) => write_external_encoding(writer, *block_content_id),
-        Encoding::Golomb(..) => unimplemented!("GOLOMB"),
-        Encoding::Huffman(..) => todo!("HUFFMAN"),
+        Encoding::Golomb(offset, m) => write_golomb_encoding(writer, *offset, *m),
+        Encoding::Huffman(alphabet, bit_lens) => write_huffman_encoding(writer, alphabet, bit_lens),
         Encoding::ByteArrayLen(len_encoding, value_encoding) => {
             write_byte_array_len_encoding(writer, len_encoding, value_encoding)
         }
         Encoding::ByteArrayStop(stop_byte, block_content_id) => {
             write_byte_array_stop_encoding(writer, *stop_byte, *block_content_id)
         }
-        Encoding::Beta(..) => todo!("BETA"),
-        Encoding::Subexp(..) => unimplemented!("SUBEXP"),
-        Encoding::GolombRice(..) => unimplemented!("GOLOMB_RICE"),
-        Encoding::Gamma(_) => unimplemented!("GAMMA"),
+        Encoding::Beta(offset, len) => write_beta_encoding(writer, *offset, *len),
+        Encoding::Subexp(offset, k) => write_subexp_encoding(writer, *offset, *k),
+        Encoding::GolombRice(offset, log2_m) => {
+            write_golomb_rice_encoding(writer, *offset, *log2_m)
+        }
+        Encoding::Gamma(offset) => write_gamma_encoding(writer, *offset),
     }
 }
 
@@ -98,3 +100,129 @@ where
 
     Ok(())
 }
+
+fn write_huffman_encoding<W>(writer: &mut W, alphabet: &[Itf8], bit_lens: &[Itf8]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut args = Vec::new();
+
+    write_itf8(&mut args, alphabet.len() as Itf8)?;
+
+    for &symbol in alphabet {
+        write_itf8(&mut args, symbol)?;
+    }
+
+    write_itf8(&mut args, bit_lens.len() as Itf8)?;
+
+    for &len in bit_lens {
+        write_itf8(&mut args, len)?;
+    }
+
+    // TODO: convert from encoding
+    write_itf8(writer, 3)?;
+    write_args(writer, &args)?;
+
+    Ok(())
+}
+
+fn write_beta_encoding<W>(writer: &mut W, offset: Itf8, len: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut args = Vec::new();
+    write_itf8(&mut args, offset)?;
+    write_itf8(&mut args, len)?;
+
+    // TODO: convert from encoding
+    write_itf8(writer, 6)?;
+    write_args(writer, &args)?;
+
+    Ok(())
+}
+
+fn write_golomb_encoding<W>(writer: &mut W, offset: Itf8, m: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut args = Vec::new();
+    write_itf8(&mut args, offset)?;
+    write_itf8(&mut args, m)?;
+
+    // TODO: convert from encoding
+    write_itf8(writer, 2)?;
+    write_args(writer, &args)?;
+
+    Ok(())
+}
+
+fn write_subexp_encoding<W>(writer: &mut W, offset: Itf8, k: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut args = Vec::new();
+    write_itf8(&mut args, offset)?;
+    write_itf8(&mut args, k)?;
+
+    // TODO: convert from encoding
+    write_itf8(writer, 7)?;
+    write_args(writer, &args)?;
+
+    Ok(())
+}
+
+fn write_golomb_rice_encoding<W>(writer: &mut W, offset: Itf8, log2_m: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut args = Vec::new();
+    write_itf8(&mut args, offset)?;
+    write_itf8(&mut args, log2_m)?;
+
+    // TODO: convert from encoding
+    write_itf8(writer, 8)?;
+    write_args(writer, &args)?;
+
+    Ok(())
+}
+
+fn write_gamma_encoding<W>(writer: &mut W, offset: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut args = Vec::new();
+    write_itf8(&mut args, offset)?;
+
+    // TODO: convert from encoding
+    write_itf8(writer, 9)?;
+    write_args(writer, &args)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_encoding() -> io::Result<()> {
+        fn t(encoding: &Encoding, expected: &[u8]) -> io::Result<()> {
+            let mut buf = Vec::new();
+            write_encoding(&mut buf, encoding)?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        t(
+            &Encoding::Huffman(vec![65, 66], vec![1, 1]),
+            &[3, 6, 2, 65, 66, 2, 1, 1],
+        )?;
+        t(&Encoding::Beta(1, 3), &[6, 2, 1, 3])?;
+        t(&Encoding::Golomb(1, 5), &[2, 2, 1, 5])?;
+        t(&Encoding::Subexp(0, 2), &[7, 2, 0, 2])?;
+        t(&Encoding::GolombRice(1, 3), &[8, 2, 1, 3])?;
+        t(&Encoding::Gamma(1), &[9, 1, 1])?;
+
+        Ok(())
+    }
+}
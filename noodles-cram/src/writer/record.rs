@@ -2,6 +2,7 @@ mod tag;
 
 use std::{
     collections::HashMap,
+    convert::TryFrom,
     error, fmt,
     io::{self, Write},
 };
@@ -15,9 +16,11 @@ use crate::{
         compression_header::{data_series_encoding_map::DataSeries, Encoding},
         CompressionHeader, ReferenceSequenceId,
     },
+    gamma, golomb,
+    huffman::CanonicalHuffmanEncoder,
     num::{write_itf8, Itf8},
     record::{self, feature, Feature, Flags, NextMateFlags},
-    BitWriter, Record,
+    subexp, BitWriter, Record,
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -832,7 +835,7 @@ where
 
 fn encode_byte<W, X>(
     encoding: &Encoding,
-    _core_data_writer: &mut BitWriter<W>,
+    core_data_writer: &mut BitWriter<W>,
     external_data_writers: &mut HashMap<Itf8, X>,
     value: u8,
 ) -> io::Result<()>
@@ -853,13 +856,33 @@ where
 
             writer.write_u8(value)
         }
-        _ => todo!("encode_byte: {:?}", encoding),
+        Encoding::Huffman(alphabet, bit_lens) => {
+            let encoder = CanonicalHuffmanEncoder::new(alphabet, bit_lens);
+            encoder.write(core_data_writer, Itf8::from(value))
+        }
+        Encoding::Beta(offset, len) => {
+            encode_beta(core_data_writer, *offset, *len, Itf8::from(value))
+        }
+        Encoding::Golomb(offset, m) => {
+            golomb::write(core_data_writer, *offset, *m, Itf8::from(value))
+        }
+        Encoding::Subexp(offset, k) => {
+            subexp::write(core_data_writer, *offset, *k, Itf8::from(value))
+        }
+        Encoding::GolombRice(offset, log2_m) => {
+            golomb::write_rice(core_data_writer, *offset, *log2_m, Itf8::from(value))
+        }
+        Encoding::Gamma(offset) => gamma::write(core_data_writer, *offset, Itf8::from(value)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid byte encoding: {:?}", encoding),
+        )),
     }
 }
 
 fn encode_itf8<W, X>(
     encoding: &Encoding,
-    _core_data_writer: &mut BitWriter<W>,
+    core_data_writer: &mut BitWriter<W>,
     external_data_writers: &mut HashMap<Itf8, X>,
     value: Itf8,
 ) -> io::Result<()>
@@ -880,10 +903,58 @@ where
 
             write_itf8(writer, value)
         }
-        _ => todo!("encode_itf8: {:?}", encoding),
+        Encoding::Huffman(alphabet, bit_lens) => {
+            let encoder = CanonicalHuffmanEncoder::new(alphabet, bit_lens);
+            encoder.write(core_data_writer, value)
+        }
+        Encoding::Beta(offset, len) => encode_beta(core_data_writer, *offset, *len, value),
+        Encoding::Golomb(offset, m) => golomb::write(core_data_writer, *offset, *m, value),
+        Encoding::Subexp(offset, k) => subexp::write(core_data_writer, *offset, *k, value),
+        Encoding::GolombRice(offset, log2_m) => {
+            golomb::write_rice(core_data_writer, *offset, *log2_m, value)
+        }
+        Encoding::Gamma(offset) => gamma::write(core_data_writer, *offset, value),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid integer encoding: {:?}", encoding),
+        )),
     }
 }
 
+fn encode_beta<W>(
+    core_data_writer: &mut BitWriter<W>,
+    offset: Itf8,
+    len: Itf8,
+    value: Itf8,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let n = golomb::offset_value(offset, value)?;
+
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|&len| len < 32)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid beta length: {}", len),
+            )
+        })?;
+
+    if n >> len != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "value cannot be encoded in {} bits with offset {}: {}",
+                len, offset, value
+            ),
+        ));
+    }
+
+    core_data_writer.write_u32(n, len)
+}
+
 fn encode_byte_array<W, X>(
     encoding: &Encoding,
     core_data_writer: &mut BitWriter<W>,
@@ -933,6 +1004,66 @@ where
 
             Ok(())
         }
-        _ => todo!("encode_byte_array: {:?}", encoding),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid byte array encoding: {:?}", encoding),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_beta() -> io::Result<()> {
+        let mut writer = BitWriter::new(Vec::new());
+        encode_beta(&mut writer, 1, 3, 6)?;
+        encode_beta(&mut writer, 1, 3, -1)?;
+        assert_eq!(writer.finish()?, [0b11100000]);
+
+        let mut writer = BitWriter::new(Vec::new());
+
+        assert!(matches!(
+            encode_beta(&mut writer, 1, 3, 7),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            encode_beta(&mut writer, 0, 3, -1),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            encode_beta(&mut writer, 0, -1, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_invalid_encodings() {
+        let mut core_data_writer = BitWriter::new(Vec::new());
+        let mut external_data_writers: HashMap<Itf8, Vec<u8>> = HashMap::new();
+
+        let encoding = Encoding::ByteArrayStop(0x00, 1);
+
+        assert!(matches!(
+            encode_byte(&encoding, &mut core_data_writer, &mut external_data_writers, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            encode_itf8(&encoding, &mut core_data_writer, &mut external_data_writers, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let encoding = Encoding::Gamma(1);
+
+        assert!(matches!(
+            encode_byte_array(&encoding, &mut core_data_writer, &mut external_data_writers, b"ACGT"),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
     }
 }
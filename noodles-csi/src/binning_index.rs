@@ -0,0 +1,28 @@
+use std::io;
+
+use super::index::reference_sequence::bin::Chunk;
+
+/// A binning index.
+///
+/// A binning index groups records into hierarchical bins and is used to find the physical
+/// positions of records that may overlap a given region. This is implemented by, e.g., BAI, tabix,
+/// and CSI indices.
+///
+/// Reference sequences are addressed by position. Not all indices store reference sequence names,
+/// e.g., a BAI; in this case, names are resolved using the header of the associated file.
+pub trait BinningIndex {
+    /// Returns a list of optimized chunks that may contain records in the given region.
+    ///
+    /// `start` and `end` are 1-based, inclusive. The returned chunks are sorted and
+    /// non-overlapping.
+    fn query(&self, reference_sequence_id: usize, start: u64, end: u64) -> io::Result<Vec<Chunk>>;
+}
+
+/// A binning index that stores the names of its reference sequences.
+///
+/// This is implemented by, e.g., tabix and CSI indices, which list reference sequence names in
+/// their headers.
+pub trait NamedBinningIndex: BinningIndex {
+    /// Returns the position of the reference sequence with the given name.
+    fn reference_sequence_id(&self, reference_sequence_name: &str) -> Option<usize>;
+}
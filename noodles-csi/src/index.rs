@@ -0,0 +1,351 @@
+//! Coordinate-sorted index (CSI) and fields.
+
+pub mod builder;
+mod indexer;
+pub mod reference_sequence;
+
+pub use self::{builder::Builder, indexer::Indexer, reference_sequence::ReferenceSequence};
+
+use std::{
+    convert::TryFrom,
+    io::{self, Read},
+    str,
+};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::{BinningIndex, NamedBinningIndex};
+
+use self::reference_sequence::bin::Chunk;
+
+// The size of the tabix-style header at the start of auxiliary data, i.e., format, col_seq,
+// col_beg, col_end, meta, and skip.
+const AUX_HEADER_LEN: usize = 24;
+
+const NUL: u8 = b'\x00';
+
+/// A coordinate-sorted index (CSI).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Index {
+    min_shift: u32,
+    depth: u32,
+    aux: Vec<u8>,
+    reference_sequences: Vec<ReferenceSequence>,
+    unmapped_read_count: Option<u64>,
+}
+
+impl Index {
+    /// Returns a builder to create an index from each of its fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let builder = csi::Index::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns an indexer to create an index from records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let indexer = csi::Index::indexer();
+    /// ```
+    pub fn indexer() -> Indexer {
+        Indexer::default()
+    }
+
+    /// Returns the number of bits for the minimum interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_min_shift(16).build();
+    /// assert_eq!(index.min_shift(), 16);
+    /// ```
+    pub fn min_shift(&self) -> u32 {
+        self.min_shift
+    }
+
+    /// Returns the depth of the binning index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_depth(7).build();
+    /// assert_eq!(index.depth(), 7);
+    /// ```
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns the auxiliary data.
+    ///
+    /// For tabix-like indices, e.g., of bgzipped VCFs, this is the tabix header followed by the
+    /// list of reference sequence names. Otherwise, it is typically empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_aux(b"ndls".to_vec()).build();
+    /// assert_eq!(index.aux(), b"ndls");
+    /// ```
+    pub fn aux(&self) -> &[u8] {
+        &self.aux
+    }
+
+    /// Returns a list of reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, index::ReferenceSequence};
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(Vec::new(), None)])
+    ///     .build();
+    ///
+    /// assert_eq!(index.reference_sequences().len(), 1);
+    /// ```
+    pub fn reference_sequences(&self) -> &[ReferenceSequence] {
+        &self.reference_sequences
+    }
+
+    /// Returns the number of unmapped reads in the associated file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_unmapped_read_count(21).build();
+    /// assert_eq!(index.unmapped_read_count(), Some(21));
+    /// ```
+    pub fn unmapped_read_count(&self) -> Option<u64> {
+        self.unmapped_read_count
+    }
+
+    /// Parses the reference sequence names stored in the tabix-like auxiliary data.
+    ///
+    /// This returns an empty list if there is no auxiliary data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi as csi;
+    ///
+    /// let index = csi::Index::default();
+    /// assert!(index.reference_sequence_names()?.is_empty());
+    ///
+    /// let mut aux = vec![0; 24];
+    /// aux.extend_from_slice(&8i32.to_le_bytes());
+    /// aux.extend_from_slice(b"sq0\x00sq1\x00");
+    /// let index = csi::Index::builder().set_aux(aux).build();
+    /// assert_eq!(index.reference_sequence_names()?, [String::from("sq0"), String::from("sq1")]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn reference_sequence_names(&self) -> io::Result<Vec<String>> {
+        if self.aux.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut reader = self.aux.get(AUX_HEADER_LEN..).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "invalid auxiliary data header")
+        })?;
+
+        let l_nm = reader.read_i32::<LittleEndian>().and_then(|n| {
+            usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })?;
+
+        let mut names = vec![0; l_nm];
+        reader.read_exact(&mut names)?;
+
+        parse_names(&names)
+    }
+
+    /// Returns a list of optimized chunks that may contain records in the given region.
+    ///
+    /// `start` and `end` are 1-based, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi::{self as csi, index::ReferenceSequence};
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(Vec::new(), None)])
+    ///     .build();
+    ///
+    /// let chunks = index.query(0, 8, 13)?;
+    /// assert!(chunks.is_empty());
+    ///
+    /// assert!(index.query(1, 8, 13).is_err());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn query(
+        &self,
+        reference_sequence_id: usize,
+        start: u64,
+        end: u64,
+    ) -> io::Result<Vec<Chunk>> {
+        let reference_sequence = self
+            .reference_sequences()
+            .get(reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "could not find reference in index: {} >= {}",
+                        reference_sequence_id,
+                        self.reference_sequences().len()
+                    ),
+                )
+            })?;
+
+        let chunks: Vec<_> = reference_sequence
+            .query(self.min_shift, self.depth, start, end)?
+            .iter()
+            .flat_map(|bin| bin.chunks())
+            .copied()
+            .collect();
+
+        let min_offset = reference_sequence.min_offset(self.min_shift, self.depth, start);
+
+        Ok(crate::optimize_chunks(&chunks, min_offset))
+    }
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Builder::default().build()
+    }
+}
+
+impl BinningIndex for Index {
+    fn query(&self, reference_sequence_id: usize, start: u64, end: u64) -> io::Result<Vec<Chunk>> {
+        Index::query(self, reference_sequence_id, start, end)
+    }
+}
+
+impl NamedBinningIndex for Index {
+    /// Returns the position of the reference sequence with the given name.
+    ///
+    /// This returns `None` if the auxiliary data does not list reference sequence names.
+    fn reference_sequence_id(&self, reference_sequence_name: &str) -> Option<usize> {
+        self.reference_sequence_names()
+            .ok()?
+            .iter()
+            .position(|name| name == reference_sequence_name)
+    }
+}
+
+fn parse_names(buf: &[u8]) -> io::Result<Vec<String>> {
+    buf.split(|&b| b == NUL)
+        .take_while(|raw_name| !raw_name.is_empty())
+        .map(|raw_name| {
+            str::from_utf8(raw_name)
+                .map(|s| s.into())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::{reference_sequence::Bin, *};
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let index = Index::builder()
+            .set_reference_sequences(vec![ReferenceSequence::new(
+                vec![
+                    Bin::new(
+                        0,
+                        bgzf::VirtualPosition::from(2),
+                        vec![Chunk::new(
+                            bgzf::VirtualPosition::from(5),
+                            bgzf::VirtualPosition::from(13),
+                        )],
+                    ),
+                    Bin::new(
+                        4681,
+                        bgzf::VirtualPosition::from(3),
+                        vec![Chunk::new(
+                            bgzf::VirtualPosition::from(2),
+                            bgzf::VirtualPosition::from(8),
+                        )],
+                    ),
+                    Bin::new(
+                        4682,
+                        bgzf::VirtualPosition::from(21),
+                        vec![Chunk::new(
+                            bgzf::VirtualPosition::from(21),
+                            bgzf::VirtualPosition::from(34),
+                        )],
+                    ),
+                ],
+                None,
+            )])
+            .build();
+
+        let actual = index.query(0, 8, 13)?;
+        let expected = [Chunk::new(
+            bgzf::VirtualPosition::from(2),
+            bgzf::VirtualPosition::from(13),
+        )];
+        assert_eq!(actual, expected);
+
+        assert!(index.query(1, 8, 13).is_err());
+
+        assert!(matches!(
+            index.query(0, 0, 13),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            index.query(0, 13, 8),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reference_sequence_id() {
+        let mut aux = vec![0; AUX_HEADER_LEN];
+        aux.extend_from_slice(&8i32.to_le_bytes());
+        aux.extend_from_slice(b"sq0\x00sq1\x00");
+
+        let index = Index::builder().set_aux(aux).build();
+
+        assert_eq!(index.reference_sequence_id("sq0"), Some(0));
+        assert_eq!(index.reference_sequence_id("sq1"), Some(1));
+        assert_eq!(index.reference_sequence_id("sq2"), None);
+
+        let index = Index::default();
+        assert_eq!(index.reference_sequence_id("sq0"), None);
+    }
+
+    #[test]
+    fn test_parse_names() -> io::Result<()> {
+        let data = b"noodles\x00csi\x00";
+        let actual = parse_names(&data[..])?;
+        let expected = vec![String::from("noodles"), String::from("csi")];
+        assert_eq!(actual, expected);
+
+        let data = b"";
+        assert!(parse_names(&data[..])?.is_empty());
+
+        Ok(())
+    }
+}
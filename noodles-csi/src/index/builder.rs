@@ -0,0 +1,123 @@
+//! CSI builder.
+
+use super::{Index, ReferenceSequence};
+
+// The BAI-compatible binning scheme, i.e., 16 kbp minimum intervals and 6 levels.
+pub(crate) const DEFAULT_MIN_SHIFT: u32 = 14;
+pub(crate) const DEFAULT_DEPTH: u32 = 5;
+
+/// A CSI builder.
+#[derive(Debug)]
+pub struct Builder {
+    min_shift: u32,
+    depth: u32,
+    aux: Vec<u8>,
+    reference_sequences: Vec<ReferenceSequence>,
+    unmapped_read_count: Option<u64>,
+}
+
+impl Builder {
+    /// Sets the number of bits for the minimum interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_min_shift(16).build();
+    /// assert_eq!(index.min_shift(), 16);
+    /// ```
+    pub fn set_min_shift(mut self, min_shift: u32) -> Self {
+        self.min_shift = min_shift;
+        self
+    }
+
+    /// Sets the depth of the binning index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_depth(7).build();
+    /// assert_eq!(index.depth(), 7);
+    /// ```
+    pub fn set_depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets auxiliary data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_aux(b"ndls".to_vec()).build();
+    /// assert_eq!(index.aux(), b"ndls");
+    /// ```
+    pub fn set_aux(mut self, aux: Vec<u8>) -> Self {
+        self.aux = aux;
+        self
+    }
+
+    /// Sets reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, index::ReferenceSequence};
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(Vec::new(), None)])
+    ///     .build();
+    ///
+    /// assert_eq!(index.reference_sequences().len(), 1);
+    /// ```
+    pub fn set_reference_sequences(mut self, reference_sequences: Vec<ReferenceSequence>) -> Self {
+        self.reference_sequences = reference_sequences;
+        self
+    }
+
+    /// Sets an unmapped read count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().set_unmapped_read_count(21).build();
+    /// assert_eq!(index.unmapped_read_count(), Some(21));
+    /// ```
+    pub fn set_unmapped_read_count(mut self, unmapped_read_count: u64) -> Self {
+        self.unmapped_read_count = Some(unmapped_read_count);
+        self
+    }
+
+    /// Builds a CSI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::builder().build();
+    /// ```
+    pub fn build(self) -> Index {
+        Index {
+            min_shift: self.min_shift,
+            depth: self.depth,
+            aux: self.aux,
+            reference_sequences: self.reference_sequences,
+            unmapped_read_count: self.unmapped_read_count,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            min_shift: DEFAULT_MIN_SHIFT,
+            depth: DEFAULT_DEPTH,
+            aux: Vec::new(),
+            reference_sequences: Vec::new(),
+            unmapped_read_count: None,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use super::{
+    builder::{DEFAULT_DEPTH, DEFAULT_MIN_SHIFT},
+    reference_sequence::{self, bin::Chunk},
+    Index,
+};
+
+/// A CSI indexer.
+///
+/// Records are expected to be added in coordinate-sorted order, i.e., grouped by reference
+/// sequence and sorted by start position.
+#[derive(Debug)]
+pub struct Indexer {
+    min_shift: u32,
+    depth: u32,
+    aux: Vec<u8>,
+    reference_sequence_builders: Vec<reference_sequence::Builder>,
+    unplaced_unmapped_record_count: u64,
+}
+
+impl Indexer {
+    /// Sets the number of bits for the minimum interval.
+    ///
+    /// The default is 14, i.e., 16 kbp minimum intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let mut indexer = csi::Index::indexer();
+    /// indexer.set_min_shift(16);
+    /// ```
+    pub fn set_min_shift(&mut self, min_shift: u32) {
+        self.min_shift = min_shift;
+    }
+
+    /// Sets the depth of the binning index.
+    ///
+    /// The default is 5. The maximum reference sequence length that can be indexed is
+    /// 2^(`min_shift` + 3 * `depth`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let mut indexer = csi::Index::indexer();
+    /// indexer.set_depth(7);
+    /// ```
+    pub fn set_depth(&mut self, depth: u32) {
+        self.depth = depth;
+    }
+
+    /// Sets auxiliary data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let mut indexer = csi::Index::indexer();
+    /// indexer.set_aux(Vec::new());
+    /// ```
+    pub fn set_aux(&mut self, aux: Vec<u8>) {
+        self.aux = aux;
+    }
+
+    /// Adds a record.
+    ///
+    /// `start` and `end` are 1-based, inclusive. `chunk` is the range of virtual positions of
+    /// the record in the associated file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+    ///
+    /// let mut indexer = csi::Index::indexer();
+    ///
+    /// indexer.add_record(
+    ///     0,
+    ///     8,
+    ///     13,
+    ///     true,
+    ///     Chunk::new(bgzf::VirtualPosition::from(0), bgzf::VirtualPosition::from(21)),
+    /// );
+    /// ```
+    pub fn add_record(
+        &mut self,
+        reference_sequence_id: usize,
+        start: u64,
+        end: u64,
+        is_mapped: bool,
+        chunk: Chunk,
+    ) {
+        let (min_shift, depth) = (self.min_shift, self.depth);
+
+        if reference_sequence_id >= self.reference_sequence_builders.len() {
+            self.reference_sequence_builders
+                .resize_with(reference_sequence_id + 1, || {
+                    reference_sequence::Builder::new(min_shift, depth)
+                });
+        }
+
+        self.reference_sequence_builders[reference_sequence_id]
+            .add_record(start, end, is_mapped, chunk);
+    }
+
+    /// Adds a record that is not placed on a reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let mut indexer = csi::Index::indexer();
+    /// indexer.add_unplaced_unmapped_record();
+    /// ```
+    pub fn add_unplaced_unmapped_record(&mut self) {
+        self.unplaced_unmapped_record_count += 1;
+    }
+
+    /// Builds a CSI.
+    ///
+    /// `reference_sequence_count` is the number of reference sequences in the associated file.
+    /// Reference sequences with no records are included as empty reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let indexer = csi::Index::indexer();
+    /// let index = indexer.build(1);
+    /// assert_eq!(index.reference_sequences().len(), 1);
+    /// ```
+    pub fn build(self, reference_sequence_count: usize) -> Index {
+        let mut reference_sequences: Vec<_> = self
+            .reference_sequence_builders
+            .into_iter()
+            .map(|b| b.build())
+            .collect();
+
+        if reference_sequences.len() < reference_sequence_count {
+            reference_sequences.resize_with(reference_sequence_count, Default::default);
+        }
+
+        Index::builder()
+            .set_min_shift(self.min_shift)
+            .set_depth(self.depth)
+            .set_aux(self.aux)
+            .set_reference_sequences(reference_sequences)
+            .set_unmapped_read_count(self.unplaced_unmapped_record_count)
+            .build()
+    }
+}
+
+impl Default for Indexer {
+    fn default() -> Self {
+        Self {
+            min_shift: DEFAULT_MIN_SHIFT,
+            depth: DEFAULT_DEPTH,
+            aux: Vec::new(),
+            reference_sequence_builders: Vec::new(),
+            unplaced_unmapped_record_count: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let mut indexer = Indexer::default();
+        indexer.set_min_shift(12);
+        indexer.set_depth(7);
+
+        indexer.add_record(
+            1,
+            8,
+            13,
+            true,
+            Chunk::new(
+                bgzf::VirtualPosition::from(0),
+                bgzf::VirtualPosition::from(21),
+            ),
+        );
+
+        indexer.add_unplaced_unmapped_record();
+
+        let index = indexer.build(3);
+
+        assert_eq!(index.min_shift(), 12);
+        assert_eq!(index.depth(), 7);
+        assert_eq!(index.reference_sequences().len(), 3);
+        assert!(index.reference_sequences()[0].bins().is_empty());
+        assert_eq!(index.reference_sequences()[1].bins().len(), 1);
+        assert_eq!(index.reference_sequences()[1].bins()[0].id(), 299593);
+        assert!(index.reference_sequences()[2].bins().is_empty());
+        assert_eq!(index.unmapped_read_count(), Some(1));
+    }
+}
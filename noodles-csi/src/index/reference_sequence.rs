@@ -0,0 +1,286 @@
+//! CSI reference sequence and fields.
+
+pub mod bin;
+mod builder;
+pub mod metadata;
+
+pub(crate) use self::builder::Builder;
+
+pub use self::{bin::Bin, metadata::Metadata};
+
+use std::io;
+
+use noodles_bgzf as bgzf;
+
+/// A CSI reference sequence.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReferenceSequence {
+    bins: Vec<Bin>,
+    metadata: Option<Metadata>,
+}
+
+impl ReferenceSequence {
+    /// Creates a CSI reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), None);
+    /// ```
+    pub fn new(bins: Vec<Bin>, metadata: Option<Metadata>) -> Self {
+        Self { bins, metadata }
+    }
+
+    /// Returns the list of bins in the reference sequence.
+    ///
+    /// This list does not include the metadata pseudo-bin. Use [`metadata`] instead.
+    ///
+    /// [`metadata`]: #method.metadata
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), None);
+    /// assert!(reference_sequence.bins().is_empty());
+    /// ```
+    pub fn bins(&self) -> &[Bin] {
+        &self.bins
+    }
+
+    /// Returns metadata for this reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::{reference_sequence::Metadata, ReferenceSequence};
+    ///
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), None);
+    /// assert!(reference_sequence.metadata().is_none());
+    ///
+    /// let reference_sequence = ReferenceSequence::new(
+    ///     Vec::new(),
+    ///     Some(Metadata::new(
+    ///         bgzf::VirtualPosition::from(610),
+    ///         bgzf::VirtualPosition::from(1597),
+    ///         55,
+    ///         0,
+    ///     )),
+    /// );
+    /// assert!(reference_sequence.metadata().is_some());
+    /// ```
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Returns a list of bins in this reference sequence that intersect the given range.
+    ///
+    /// `min_shift` and `depth` are the binning parameters of the index. `start` and `end` are
+    /// 1-based, inclusive. `end` is clamped to the maximum position addressable by the binning
+    /// scheme (2^(`min_shift` + 3 * `depth`)).
+    ///
+    /// This returns an error if `start` is 0 or `start` > `end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), None);
+    /// let query_bins = reference_sequence.query(14, 5, 8, 13)?;
+    /// assert!(query_bins.is_empty());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn query(&self, min_shift: u32, depth: u32, start: u64, end: u64) -> io::Result<Vec<&Bin>> {
+        if start == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid start position: expected >= 1, got 0",
+            ));
+        }
+
+        let end = end.min(bin::max_position(min_shift, depth));
+
+        if start > end {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid interval: expected start <= end, got {} > {}",
+                    start, end
+                ),
+            ));
+        }
+
+        let ranges = bin::region_to_bin_ranges(min_shift, depth, start - 1, end);
+
+        let query_bins = self
+            .bins()
+            .iter()
+            .filter(|b| {
+                let level = bin::level(b.id()) as usize;
+
+                ranges
+                    .get(level)
+                    .map(|&(first, last)| first <= b.id() && b.id() <= last)
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        Ok(query_bins)
+    }
+
+    /// Finds the minimum start offset for a given start position.
+    ///
+    /// This is the offset of the smallest bin that contains the start position, or, if it does not
+    /// exist, the offset of the nearest bin to its left or one of its ancestors.
+    ///
+    /// `start` is 1-based. It is clamped to the range of positions addressable by the binning
+    /// scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), None);
+    /// assert_eq!(reference_sequence.min_offset(14, 5, 13), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn min_offset(&self, min_shift: u32, depth: u32, start: u64) -> bgzf::VirtualPosition {
+        let find_bin = |id| self.bins().iter().find(|b| b.id() == id);
+
+        let max_position = bin::max_position(min_shift, depth);
+        let start = start.saturating_sub(1).min(max_position - 1);
+        let mut id = bin::first_id(depth) + (start >> min_shift) as u32;
+
+        while id > 0 {
+            if let Some(b) = find_bin(id) {
+                return b.loffset();
+            }
+
+            let first_sibling_id = (bin::parent_id(id) << 3) + 1;
+
+            if id > first_sibling_id {
+                id -= 1;
+            } else {
+                id = bin::parent_id(id);
+            }
+        }
+
+        find_bin(0).map(|b| b.loffset()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let reference_sequence = ReferenceSequence::new(
+            vec![
+                Bin::new(0, bgzf::VirtualPosition::default(), Vec::new()),
+                Bin::new(2, bgzf::VirtualPosition::default(), Vec::new()),
+                Bin::new(4681, bgzf::VirtualPosition::default(), Vec::new()),
+                Bin::new(4682, bgzf::VirtualPosition::default(), Vec::new()),
+            ],
+            None,
+        );
+
+        let actual: Vec<_> = reference_sequence
+            .query(14, 5, 8, 13)?
+            .iter()
+            .map(|b| b.id())
+            .collect();
+
+        assert_eq!(actual, [0, 4681]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_large_end() -> io::Result<()> {
+        let reference_sequence = ReferenceSequence::new(
+            vec![
+                Bin::new(0, bgzf::VirtualPosition::default(), Vec::new()),
+                Bin::new(8, bgzf::VirtualPosition::default(), Vec::new()),
+                Bin::new(37448, bgzf::VirtualPosition::default(), Vec::new()),
+            ],
+            None,
+        );
+
+        let actual: Vec<_> = reference_sequence
+            .query(14, 5, 1, u64::MAX)?
+            .iter()
+            .map(|b| b.id())
+            .collect();
+
+        assert_eq!(actual, [0, 8, 37448]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_invalid_interval() {
+        let reference_sequence = ReferenceSequence::default();
+
+        assert!(matches!(
+            reference_sequence.query(14, 5, 0, 13),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            reference_sequence.query(14, 5, 13, 8),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_min_offset() {
+        let reference_sequence = ReferenceSequence::new(
+            vec![
+                Bin::new(0, bgzf::VirtualPosition::from(2), Vec::new()),
+                Bin::new(4681, bgzf::VirtualPosition::from(5), Vec::new()),
+                Bin::new(4683, bgzf::VirtualPosition::from(13), Vec::new()),
+            ],
+            None,
+        );
+
+        // bin 4681
+        assert_eq!(
+            reference_sequence.min_offset(14, 5, 8),
+            bgzf::VirtualPosition::from(5)
+        );
+
+        // bin 4682 (missing) -> bin 4681
+        assert_eq!(
+            reference_sequence.min_offset(14, 5, 16385),
+            bgzf::VirtualPosition::from(5)
+        );
+
+        // bin 4683
+        assert_eq!(
+            reference_sequence.min_offset(14, 5, 32769),
+            bgzf::VirtualPosition::from(13)
+        );
+
+        // bin 4689 (missing) -> ... -> bin 585 (missing) -> ... -> bin 0
+        assert_eq!(
+            reference_sequence.min_offset(14, 5, 131073),
+            bgzf::VirtualPosition::from(2)
+        );
+
+        // start = 0 -> bin 4681
+        assert_eq!(
+            reference_sequence.min_offset(14, 5, 0),
+            bgzf::VirtualPosition::from(5)
+        );
+
+        // bin 37448 (missing) -> ... -> bin 0
+        assert_eq!(
+            reference_sequence.min_offset(14, 5, u64::MAX),
+            bgzf::VirtualPosition::from(2)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! CSI reference sequence bin and fields.
+
+mod chunk;
+
+pub use self::chunk::Chunk;
+
+use noodles_bgzf as bgzf;
+
+/// A CSI reference sequence bin.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bin {
+    id: u32,
+    loffset: bgzf::VirtualPosition,
+    chunks: Vec<Chunk>,
+}
+
+impl Bin {
+    /// Creates a new bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::Bin;
+    /// let bin = Bin::new(10946, bgzf::VirtualPosition::default(), Vec::new());
+    /// ```
+    pub fn new(id: u32, loffset: bgzf::VirtualPosition, chunks: Vec<Chunk>) -> Self {
+        Self {
+            id,
+            loffset,
+            chunks,
+        }
+    }
+
+    /// Returns the bin ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::Bin;
+    /// let bin = Bin::new(10946, bgzf::VirtualPosition::default(), Vec::new());
+    /// assert_eq!(bin.id(), 10946);
+    /// ```
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the virtual position of the first record that overlaps this bin.
+    ///
+    /// This replaces the linear index used in BAI and tabix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::Bin;
+    /// let bin = Bin::new(10946, bgzf::VirtualPosition::from(8), Vec::new());
+    /// assert_eq!(bin.loffset(), bgzf::VirtualPosition::from(8));
+    /// ```
+    pub fn loffset(&self) -> bgzf::VirtualPosition {
+        self.loffset
+    }
+
+    /// Returns the list of chunks in the bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::Bin;
+    /// let bin = Bin::new(10946, bgzf::VirtualPosition::default(), Vec::new());
+    /// assert!(bin.chunks().is_empty());
+    /// ```
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+}
+
+// § 3.2 "Calculating bins" (2020-07-19)
+
+/// The maximum depth of a binning scheme.
+///
+/// This is the largest depth in which all bin IDs, including the metadata pseudo-bin ID, fit in a
+/// `u32`.
+pub(crate) const MAX_DEPTH: u32 = 10;
+
+/// Returns the ID of the first bin in the given level.
+pub(crate) fn first_id(level: u32) -> u32 {
+    (((1u64 << (level * 3)) - 1) / 7) as u32
+}
+
+/// Returns the ID of the parent of the given bin.
+pub(crate) fn parent_id(id: u32) -> u32 {
+    (id - 1) >> 3
+}
+
+/// Returns the ID of the metadata pseudo-bin, i.e., one more than the maximum bin ID.
+pub(crate) fn metadata_id(depth: u32) -> u32 {
+    first_id(depth + 1) + 1
+}
+
+/// Returns the maximum position (exclusive) addressable by the given binning scheme, i.e.,
+/// 2^(`min_shift` + 3 * `depth`).
+pub(crate) fn max_position(min_shift: u32, depth: u32) -> u64 {
+    let width = u64::from(min_shift) + 3 * u64::from(depth);
+
+    if width < 64 {
+        1 << width
+    } else {
+        u64::MAX
+    }
+}
+
+/// Returns the level of the given bin.
+pub(crate) fn level(id: u32) -> u32 {
+    let mut level = 0;
+
+    while first_id(level + 1) <= id {
+        level += 1;
+    }
+
+    level
+}
+
+// 0-based, [start, end)
+pub(crate) fn region_to_bin(min_shift: u32, depth: u32, start: u64, mut end: u64) -> u32 {
+    end -= 1;
+
+    let mut shift = min_shift;
+
+    for level in (1..=depth).rev() {
+        if start >> shift == end >> shift {
+            return first_id(level) + (start >> shift) as u32;
+        }
+
+        shift += 3;
+    }
+
+    0
+}
+
+// 0-based, [start, end)
+//
+// This returns the inclusive range of bin IDs that overlap the region for each level, starting at
+// level 0.
+pub(crate) fn region_to_bin_ranges(
+    min_shift: u32,
+    depth: u32,
+    start: u64,
+    mut end: u64,
+) -> Vec<(u32, u32)> {
+    end -= 1;
+
+    (0..=depth)
+        .map(|level| {
+            let shift = min_shift + 3 * (depth - level);
+            let first = first_id(level);
+            (
+                first + (start >> shift) as u32,
+                first + (end >> shift) as u32,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    #[test]
+    fn test_max_depth() {
+        let id = ((1u64 << ((MAX_DEPTH + 2) * 3)) - 1) / 7 + 1;
+        assert!(u32::try_from(id).is_err());
+        assert_eq!(metadata_id(MAX_DEPTH), 1227133514);
+    }
+
+    #[test]
+    fn test_first_id() {
+        assert_eq!(first_id(0), 0);
+        assert_eq!(first_id(1), 1);
+        assert_eq!(first_id(2), 9);
+        assert_eq!(first_id(5), 4681);
+    }
+
+    #[test]
+    fn test_parent_id() {
+        assert_eq!(parent_id(1), 0);
+        assert_eq!(parent_id(8), 0);
+        assert_eq!(parent_id(9), 1);
+        assert_eq!(parent_id(4681), 585);
+    }
+
+    #[test]
+    fn test_metadata_id() {
+        assert_eq!(metadata_id(5), 37450);
+    }
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(0), 0);
+        assert_eq!(level(8), 1);
+        assert_eq!(level(9), 2);
+        assert_eq!(level(4680), 4);
+        assert_eq!(level(4681), 5);
+    }
+
+    #[test]
+    fn test_region_to_bin() {
+        // [8, 13]
+        assert_eq!(region_to_bin(14, 5, 7, 13), 4681);
+        // [63245986, 63245986]
+        assert_eq!(region_to_bin(14, 5, 63245985, 63255986), 8541);
+        // [1, 2^30]
+        assert_eq!(region_to_bin(14, 5, 0, 1 << 30), 0);
+        // [2^32 + 1, 2^32 + 1], which requires a deeper index
+        assert_eq!(
+            region_to_bin(14, 7, 1 << 32, (1 << 32) + 1),
+            first_id(7) + (1 << 18)
+        );
+    }
+
+    #[test]
+    fn test_region_to_bin_ranges() {
+        // [8, 13]
+        assert_eq!(
+            region_to_bin_ranges(14, 5, 7, 13),
+            [(0, 0), (1, 1), (9, 9), (73, 73), (585, 585), (4681, 4681)]
+        );
+
+        // [16384, 16385]
+        assert_eq!(
+            region_to_bin_ranges(14, 5, 16383, 16385),
+            [(0, 0), (1, 1), (9, 9), (73, 73), (585, 585), (4681, 4682)]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use noodles_bgzf as bgzf;
 
-/// A tabix index reference sequence bin chunk.
+/// A CSI reference sequence bin chunk.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Chunk {
     start: bgzf::VirtualPosition,
@@ -14,7 +14,7 @@ impl Chunk {
     ///
     /// ```
     /// use noodles_bgzf as bgzf;
-    /// use noodles_tabix::index::reference_sequence::bin::Chunk;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
     /// let chunk = Chunk::new(bgzf::VirtualPosition::from(8), bgzf::VirtualPosition::from(13));
     /// ```
     pub fn new(start: bgzf::VirtualPosition, end: bgzf::VirtualPosition) -> Self {
@@ -27,7 +27,7 @@ impl Chunk {
     ///
     /// ```
     /// use noodles_bgzf as bgzf;
-    /// use noodles_tabix::index::reference_sequence::bin::Chunk;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
     /// let chunk = Chunk::new(bgzf::VirtualPosition::from(8), bgzf::VirtualPosition::from(13));
     /// assert_eq!(chunk.start(), bgzf::VirtualPosition::from(8));
     /// ```
@@ -41,7 +41,7 @@ impl Chunk {
     ///
     /// ```
     /// use noodles_bgzf as bgzf;
-    /// use noodles_tabix::index::reference_sequence::bin::Chunk;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
     /// let chunk = Chunk::new(bgzf::VirtualPosition::from(8), bgzf::VirtualPosition::from(13));
     /// assert_eq!(chunk.end(), bgzf::VirtualPosition::from(13));
     /// ```
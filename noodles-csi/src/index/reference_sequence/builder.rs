@@ -0,0 +1,249 @@
+use std::{cmp, collections::HashMap};
+
+use noodles_bgzf as bgzf;
+
+use super::{
+    bin::{self, Chunk},
+    Bin, Metadata, ReferenceSequence,
+};
+
+#[derive(Debug)]
+pub struct Builder {
+    min_shift: u32,
+    depth: u32,
+    bins: HashMap<u32, Vec<Chunk>>,
+    // The virtual position of the first record that overlaps each (2^min_shift)-sized window.
+    windows: Vec<Option<bgzf::VirtualPosition>>,
+    start_position: bgzf::VirtualPosition,
+    end_position: bgzf::VirtualPosition,
+    mapped_record_count: u64,
+    unmapped_record_count: u64,
+}
+
+impl Builder {
+    pub fn new(min_shift: u32, depth: u32) -> Self {
+        Self {
+            min_shift,
+            depth,
+            bins: HashMap::new(),
+            windows: Vec::new(),
+            start_position: bgzf::VirtualPosition::max(),
+            end_position: bgzf::VirtualPosition::default(),
+            mapped_record_count: 0,
+            unmapped_record_count: 0,
+        }
+    }
+
+    // `start` and `end` are 1-based, inclusive.
+    pub fn add_record(&mut self, start: u64, end: u64, is_mapped: bool, chunk: Chunk) -> &mut Self {
+        self.update_bins(start, end, chunk);
+        self.update_windows(start, end, chunk);
+        self.update_metadata(is_mapped, chunk);
+        self
+    }
+
+    pub fn build(self) -> ReferenceSequence {
+        if self.bins.is_empty() {
+            return ReferenceSequence::default();
+        }
+
+        let depth = self.depth;
+        let windows = fill_windows(self.windows);
+
+        let mut bins: Vec<_> = self
+            .bins
+            .into_iter()
+            .map(|(id, chunks)| {
+                let loffset = bin_start_window(depth, id)
+                    .and_then(|i| windows.get(i).copied())
+                    .unwrap_or_default();
+
+                Bin::new(id, loffset, chunks)
+            })
+            .collect();
+
+        bins.sort_unstable_by_key(|b| b.id());
+
+        let metadata = Metadata::new(
+            self.start_position,
+            self.end_position,
+            self.mapped_record_count,
+            self.unmapped_record_count,
+        );
+
+        ReferenceSequence::new(bins, Some(metadata))
+    }
+
+    fn update_bins(&mut self, start: u64, end: u64, chunk: Chunk) {
+        let id = bin::region_to_bin(self.min_shift, self.depth, start - 1, end);
+        let chunks = self.bins.entry(id).or_default();
+
+        if let Some(last_chunk) = chunks.last_mut() {
+            if chunk.start() <= last_chunk.end() {
+                *last_chunk = Chunk::new(last_chunk.start(), chunk.end());
+                return;
+            }
+        }
+
+        chunks.push(chunk);
+    }
+
+    fn update_windows(&mut self, start: u64, end: u64, chunk: Chunk) {
+        let start_index = ((start - 1) >> self.min_shift) as usize;
+        let end_index = ((end - 1) >> self.min_shift) as usize;
+
+        if end_index >= self.windows.len() {
+            self.windows.resize(end_index + 1, None);
+        }
+
+        for window in &mut self.windows[start_index..=end_index] {
+            if window.is_none() {
+                *window = Some(chunk.start());
+            }
+        }
+    }
+
+    fn update_metadata(&mut self, is_mapped: bool, chunk: Chunk) {
+        if is_mapped {
+            self.mapped_record_count += 1;
+        } else {
+            self.unmapped_record_count += 1;
+        }
+
+        self.start_position = cmp::min(self.start_position, chunk.start());
+        self.end_position = cmp::max(self.end_position, chunk.end());
+    }
+}
+
+// Fills windows with no records with the position of the next window that has records.
+fn fill_windows(windows: Vec<Option<bgzf::VirtualPosition>>) -> Vec<bgzf::VirtualPosition> {
+    let mut filled_windows = vec![bgzf::VirtualPosition::default(); windows.len()];
+    let mut next = bgzf::VirtualPosition::default();
+
+    for (i, window) in windows.into_iter().enumerate().rev() {
+        if let Some(position) = window {
+            next = position;
+        }
+
+        filled_windows[i] = next;
+    }
+
+    filled_windows
+}
+
+// Returns the index of the first window covered by the given bin.
+fn bin_start_window(depth: u32, id: u32) -> Option<usize> {
+    let level = bin::level(id);
+
+    if level > depth {
+        return None;
+    }
+
+    let offset = u64::from(id - bin::first_id(level));
+    let shift = 3 * (depth - level);
+
+    Some((offset << shift) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let mut builder = Builder::new(14, 5);
+
+        builder.add_record(
+            2,
+            5,
+            true,
+            Chunk::new(
+                bgzf::VirtualPosition::from(55),
+                bgzf::VirtualPosition::from(89),
+            ),
+        );
+
+        builder.add_record(
+            16380,
+            16390,
+            true,
+            Chunk::new(
+                bgzf::VirtualPosition::from(89),
+                bgzf::VirtualPosition::from(144),
+            ),
+        );
+
+        builder.add_record(
+            32769,
+            32770,
+            false,
+            Chunk::new(
+                bgzf::VirtualPosition::from(144),
+                bgzf::VirtualPosition::from(233),
+            ),
+        );
+
+        let actual = builder.build();
+
+        let expected = ReferenceSequence::new(
+            vec![
+                Bin::new(
+                    585,
+                    bgzf::VirtualPosition::from(55),
+                    vec![Chunk::new(
+                        bgzf::VirtualPosition::from(89),
+                        bgzf::VirtualPosition::from(144),
+                    )],
+                ),
+                Bin::new(
+                    4681,
+                    bgzf::VirtualPosition::from(55),
+                    vec![Chunk::new(
+                        bgzf::VirtualPosition::from(55),
+                        bgzf::VirtualPosition::from(89),
+                    )],
+                ),
+                Bin::new(
+                    4683,
+                    bgzf::VirtualPosition::from(144),
+                    vec![Chunk::new(
+                        bgzf::VirtualPosition::from(144),
+                        bgzf::VirtualPosition::from(233),
+                    )],
+                ),
+            ],
+            Some(Metadata::new(
+                bgzf::VirtualPosition::from(55),
+                bgzf::VirtualPosition::from(233),
+                2,
+                1,
+            )),
+        );
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_build_with_no_bins() {
+        let reference_sequence = Builder::new(14, 5).build();
+        assert_eq!(reference_sequence, ReferenceSequence::default());
+    }
+
+    #[test]
+    fn test_fill_windows() {
+        let windows = vec![
+            Some(bgzf::VirtualPosition::from(8)),
+            None,
+            Some(bgzf::VirtualPosition::from(13)),
+        ];
+
+        assert_eq!(
+            fill_windows(windows),
+            [
+                bgzf::VirtualPosition::from(8),
+                bgzf::VirtualPosition::from(13),
+                bgzf::VirtualPosition::from(13),
+            ]
+        );
+    }
+}
@@ -0,0 +1,37 @@
+//! Indexed I/O.
+
+mod query;
+
+pub use self::query::Query;
+
+use std::io;
+
+use noodles::Region;
+
+/// Resolves a region to a reference sequence name and a 1-based, inclusive interval.
+///
+/// Only mapped regions can be resolved. This returns an error if the region is unmapped or
+/// represents all records.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles::Region;
+/// use noodles_csi::io::resolve_region;
+///
+/// let region = Region::mapped("sq0", 8, 13);
+/// assert_eq!(resolve_region(&region)?, ("sq0", 8, 13));
+///
+/// assert!(resolve_region(&Region::Unmapped).is_err());
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn resolve_region(region: &Region) -> io::Result<(&str, u64, u64)> {
+    match region {
+        Region::Mapped { name, start, end } => Ok((name, *start, *end)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "region is not mapped",
+        )),
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    io::{self, BufRead, Read, Seek},
+    vec,
+};
+
+use noodles_bgzf as bgzf;
+
+use crate::index::reference_sequence::bin::Chunk;
+
+enum State {
+    Seek,
+    Read(bgzf::VirtualPosition),
+    Done,
+}
+
+/// A reader over the data of a list of chunks in a bgzipped file.
+///
+/// This seeks to the start of each chunk and reads until the end of the chunk. The chunks are
+/// typically the result of a [`BinningIndex::query`], and the read data, a sequence of records
+/// that may intersect the queried region.
+///
+/// [`BinningIndex::query`]: ../trait.BinningIndex.html#tymethod.query
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, BufRead};
+/// use noodles_bgzf as bgzf;
+/// use noodles_csi::{io::Query, index::reference_sequence::bin::Chunk};
+///
+/// let mut reader = bgzf::Reader::new(io::Cursor::new(Vec::new()));
+///
+/// let chunks = vec![Chunk::new(
+///     bgzf::VirtualPosition::from(0),
+///     bgzf::VirtualPosition::from(0),
+/// )];
+///
+/// let mut query = Query::new(&mut reader, chunks);
+/// assert!(query.fill_buf()?.is_empty());
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: &'a mut bgzf::Reader<R>,
+    chunks: vec::IntoIter<Chunk>,
+    state: State,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    /// Creates a reader over the data of the given chunks.
+    pub fn new(reader: &'a mut bgzf::Reader<R>, chunks: Vec<Chunk>) -> Self {
+        Self {
+            reader,
+            chunks: chunks.into_iter(),
+            state: State::Seek,
+        }
+    }
+}
+
+impl<'a, R> Read for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut src = self.fill_buf()?;
+        let amt = src.read(buf)?;
+        self.consume(amt);
+        Ok(amt)
+    }
+}
+
+impl<'a, R> BufRead for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        loop {
+            match self.state {
+                State::Seek => {
+                    self.state = match self.chunks.next() {
+                        Some(chunk) => {
+                            self.reader.seek(chunk.start())?;
+                            State::Read(chunk.end())
+                        }
+                        None => State::Done,
+                    };
+                }
+                State::Read(chunk_end) => {
+                    let is_eof = self.reader.fill_buf()?.is_empty();
+                    let position = self.reader.virtual_position();
+
+                    if is_eof || position >= chunk_end {
+                        self.state = State::Seek;
+                    } else {
+                        let buf = self.reader.fill_buf()?;
+
+                        // The chunk can end within the current block.
+                        let len = if position.compressed() == chunk_end.compressed() {
+                            let n = chunk_end.uncompressed() - position.uncompressed();
+                            buf.len().min(usize::from(n))
+                        } else {
+                            buf.len()
+                        };
+
+                        return Ok(&buf[..len]);
+                    }
+                }
+                State::Done => return Ok(&[]),
+            }
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.reader.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::TryFrom,
+        io::{Cursor, Write},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_read() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(b"noodles\ncsi\nquery\n")?;
+        let data = writer.finish()?;
+
+        let virtual_position = |pos: u16| bgzf::VirtualPosition::try_from((0, pos));
+
+        let chunks = vec![
+            Chunk::new(virtual_position(0)?, virtual_position(8)?),
+            Chunk::new(virtual_position(12)?, virtual_position(18)?),
+        ];
+
+        let mut reader = bgzf::Reader::new(Cursor::new(data));
+        let mut query = Query::new(&mut reader, chunks);
+
+        let mut buf = String::new();
+        query.read_to_string(&mut buf)?;
+        assert_eq!(buf, "noodles\nquery\n");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    convert::TryFrom,
+    io::{self, Read},
+};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use noodles_bgzf as bgzf;
+
+use super::{
+    index::{
+        reference_sequence::{
+            bin::{self, Chunk},
+            Bin, Metadata,
+        },
+        ReferenceSequence,
+    },
+    Index, MAGIC_NUMBER,
+};
+
+/// A CSI reader.
+///
+/// Consider using [`csi::read`] to read the entire index at once.
+///
+/// [`csi::read`]: fn.read.html
+pub struct Reader<R> {
+    inner: bgzf::Reader<R>,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a CSI reader.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_csi as csi;
+    /// let reader = File::open("sample.bcf.csi").map(csi::Reader::new)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn new(reader: R) -> Self {
+        Self {
+            inner: bgzf::Reader::new(reader),
+        }
+    }
+
+    /// Reads a CSI.
+    ///
+    /// The position of the stream is expected to be at the beginning.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_csi as csi;
+    /// let mut reader = File::open("sample.bcf.csi").map(csi::Reader::new)?;
+    /// let index = reader.read_index()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_index(&mut self) -> io::Result<Index> {
+        read_magic(&mut self.inner)?;
+
+        let min_shift = read_i32_as_u32(&mut self.inner)?;
+        let depth = read_i32_as_u32(&mut self.inner)?;
+        validate_binning_scheme(min_shift, depth)?;
+
+        let aux = read_aux(&mut self.inner)?;
+        let reference_sequences = read_reference_sequences(&mut self.inner, depth)?;
+        let n_no_coor = self.inner.read_u64::<LittleEndian>().ok();
+
+        let mut builder = Index::builder()
+            .set_min_shift(min_shift)
+            .set_depth(depth)
+            .set_aux(aux)
+            .set_reference_sequences(reference_sequences);
+
+        if let Some(unmapped_read_count) = n_no_coor {
+            builder = builder.set_unmapped_read_count(unmapped_read_count);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+fn read_magic<R>(reader: &mut R) -> io::Result<()>
+where
+    R: Read,
+{
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+
+    if magic == MAGIC_NUMBER {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid CSI header",
+        ))
+    }
+}
+
+fn read_i32_as_u32<R>(reader: &mut R) -> io::Result<u32>
+where
+    R: Read,
+{
+    reader
+        .read_i32::<LittleEndian>()
+        .and_then(|n| u32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+// Positions are 64-bit, so the width of the binning scheme (`min_shift + 3 * depth` bits) must be
+// less than 64.
+fn validate_binning_scheme(min_shift: u32, depth: u32) -> io::Result<()> {
+    if depth > bin::MAX_DEPTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid depth: expected <= {}, got {}",
+                bin::MAX_DEPTH,
+                depth
+            ),
+        ));
+    }
+
+    let width = u64::from(min_shift) + 3 * u64::from(depth);
+
+    if width >= 64 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid min_shift and depth: expected min_shift + 3 * depth < 64, got {} + 3 * {}",
+                min_shift, depth
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn read_aux<R>(reader: &mut R) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let l_aux = read_i32_as_u32(reader)?;
+    let mut aux = vec![0; l_aux as usize];
+    reader.read_exact(&mut aux)?;
+    Ok(aux)
+}
+
+fn read_reference_sequences<R>(reader: &mut R, depth: u32) -> io::Result<Vec<ReferenceSequence>>
+where
+    R: Read,
+{
+    let n_ref = read_i32_as_u32(reader)?;
+    let mut reference_sequences = Vec::with_capacity(n_ref as usize);
+
+    for _ in 0..n_ref {
+        let (bins, metadata) = read_bins(reader, depth)?;
+        reference_sequences.push(ReferenceSequence::new(bins, metadata));
+    }
+
+    Ok(reference_sequences)
+}
+
+fn read_bins<R>(reader: &mut R, depth: u32) -> io::Result<(Vec<Bin>, Option<Metadata>)>
+where
+    R: Read,
+{
+    let n_bin = read_i32_as_u32(reader)?;
+
+    let mut bins = Vec::with_capacity(n_bin as usize);
+    let mut metadata = None;
+
+    let metadata_id = bin::metadata_id(depth);
+
+    for _ in 0..n_bin {
+        let id = reader.read_u32::<LittleEndian>()?;
+
+        let loffset = reader
+            .read_u64::<LittleEndian>()
+            .map(bgzf::VirtualPosition::from)?;
+
+        let chunks = read_chunks(reader)?;
+
+        if id == metadata_id {
+            metadata = parse_metadata(&chunks).map(Some)?;
+        } else {
+            bins.push(Bin::new(id, loffset, chunks));
+        }
+    }
+
+    Ok((bins, metadata))
+}
+
+fn read_chunks<R>(reader: &mut R) -> io::Result<Vec<Chunk>>
+where
+    R: Read,
+{
+    let n_chunk = read_i32_as_u32(reader)?;
+    let mut chunks = Vec::with_capacity(n_chunk as usize);
+
+    for _ in 0..n_chunk {
+        let chunk_beg = reader
+            .read_u64::<LittleEndian>()
+            .map(bgzf::VirtualPosition::from)?;
+
+        let chunk_end = reader
+            .read_u64::<LittleEndian>()
+            .map(bgzf::VirtualPosition::from)?;
+
+        chunks.push(Chunk::new(chunk_beg, chunk_end));
+    }
+
+    Ok(chunks)
+}
+
+fn parse_metadata(chunks: &[Chunk]) -> io::Result<Metadata> {
+    match chunks {
+        [positions_chunk, counts_chunk] => Ok(Metadata::new(
+            positions_chunk.start(),
+            positions_chunk.end(),
+            u64::from(counts_chunk.start()),
+            u64::from(counts_chunk.end()),
+        )),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid metadata pseudo-bin chunk count: expected 2, got {}",
+                chunks.len()
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_index_with_invalid_magic_number() {
+        let data = [];
+        let mut reader = Reader::new(&data[..]);
+        assert!(reader.read_index().is_err());
+
+        let data = b"CSI";
+        let mut reader = Reader::new(&data[..]);
+        assert!(reader.read_index().is_err());
+
+        let data = b"MThd";
+        let mut reader = Reader::new(&data[..]);
+        assert!(reader.read_index().is_err());
+    }
+
+    #[test]
+    fn test_validate_binning_scheme() {
+        assert!(validate_binning_scheme(14, 5).is_ok());
+        assert!(validate_binning_scheme(33, 10).is_ok());
+
+        assert!(matches!(
+            validate_binning_scheme(14, 11),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            validate_binning_scheme(34, 10),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            validate_binning_scheme(u32::MAX, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_parse_metadata() -> io::Result<()> {
+        let chunks = [
+            Chunk::new(
+                bgzf::VirtualPosition::from(610),
+                bgzf::VirtualPosition::from(1597),
+            ),
+            Chunk::new(
+                bgzf::VirtualPosition::from(55),
+                bgzf::VirtualPosition::from(0),
+            ),
+        ];
+
+        let actual = parse_metadata(&chunks)?;
+        let expected = Metadata::new(
+            bgzf::VirtualPosition::from(610),
+            bgzf::VirtualPosition::from(1597),
+            55,
+            0,
+        );
+        assert_eq!(actual, expected);
+
+        assert!(parse_metadata(&chunks[..1]).is_err());
+
+        Ok(())
+    }
+}
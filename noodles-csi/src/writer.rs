@@ -0,0 +1,230 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use noodles_bgzf as bgzf;
+
+use super::{
+    index::{
+        reference_sequence::{
+            bin::{self, Chunk},
+            Bin, Metadata,
+        },
+        ReferenceSequence,
+    },
+    Index, MAGIC_NUMBER,
+};
+
+/// A CSI writer.
+///
+/// The output is BGZF-compressed.
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: bgzf::Writer<W>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a CSI writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let writer = csi::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: bgzf::Writer::new(inner),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// let writer = csi::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Attempts to finish the output stream.
+    ///
+    /// This is typically only manually called if the underlying stream is needed before the
+    /// writer is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi as csi;
+    /// let mut writer = csi::Writer::new(Vec::new());
+    /// writer.try_finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        self.inner.try_finish()
+    }
+
+    /// Writes a CSI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_csi as csi;
+    /// let index = csi::Index::default();
+    /// let mut writer = csi::Writer::new(Vec::new());
+    /// writer.write_index(&index)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_index(&mut self, index: &Index) -> io::Result<()> {
+        self.inner.write_all(MAGIC_NUMBER)?;
+
+        self.inner
+            .write_i32::<LittleEndian>(index.min_shift() as i32)?;
+        self.inner.write_i32::<LittleEndian>(index.depth() as i32)?;
+
+        let l_aux = index.aux().len() as i32;
+        self.inner.write_i32::<LittleEndian>(l_aux)?;
+        self.inner.write_all(index.aux())?;
+
+        let n_ref = index.reference_sequences().len() as i32;
+        self.inner.write_i32::<LittleEndian>(n_ref)?;
+
+        for reference_sequence in index.reference_sequences() {
+            write_reference_sequence(&mut self.inner, index.depth(), reference_sequence)?;
+        }
+
+        if let Some(n_no_coor) = index.unmapped_read_count() {
+            self.inner.write_u64::<LittleEndian>(n_no_coor)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_reference_sequence<W>(
+    writer: &mut W,
+    depth: u32,
+    reference_sequence: &ReferenceSequence,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut n_bin = reference_sequence.bins().len() as i32;
+
+    if reference_sequence.metadata().is_some() {
+        n_bin += 1;
+    }
+
+    writer.write_i32::<LittleEndian>(n_bin)?;
+
+    for bin in reference_sequence.bins() {
+        write_bin(writer, bin)?;
+    }
+
+    if let Some(metadata) = reference_sequence.metadata() {
+        let bin = metadata_to_bin(depth, metadata);
+        write_bin(writer, &bin)?;
+    }
+
+    Ok(())
+}
+
+fn write_bin<W>(writer: &mut W, bin: &Bin) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u32::<LittleEndian>(bin.id())?;
+    writer.write_u64::<LittleEndian>(u64::from(bin.loffset()))?;
+
+    let n_chunk = bin.chunks().len() as i32;
+    writer.write_i32::<LittleEndian>(n_chunk)?;
+
+    for chunk in bin.chunks() {
+        write_chunk(writer, chunk)?;
+    }
+
+    Ok(())
+}
+
+fn write_chunk<W>(writer: &mut W, chunk: &Chunk) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u64::<LittleEndian>(u64::from(chunk.start()))?;
+    writer.write_u64::<LittleEndian>(u64::from(chunk.end()))?;
+    Ok(())
+}
+
+fn metadata_to_bin(depth: u32, metadata: &Metadata) -> Bin {
+    let positions_chunk = Chunk::new(metadata.start_position(), metadata.end_position());
+
+    let counts_chunk = Chunk::new(
+        bgzf::VirtualPosition::from(metadata.mapped_record_count()),
+        bgzf::VirtualPosition::from(metadata.unmapped_record_count()),
+    );
+
+    Bin::new(
+        bin::metadata_id(depth),
+        bgzf::VirtualPosition::default(),
+        vec![positions_chunk, counts_chunk],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Reader;
+
+    use super::*;
+
+    #[test]
+    fn test_write_index() -> io::Result<()> {
+        let reference_sequences = vec![
+            ReferenceSequence::new(
+                vec![Bin::new(
+                    299593,
+                    bgzf::VirtualPosition::from(55),
+                    vec![Chunk::new(
+                        bgzf::VirtualPosition::from(55),
+                        bgzf::VirtualPosition::from(89),
+                    )],
+                )],
+                Some(Metadata::new(
+                    bgzf::VirtualPosition::from(55),
+                    bgzf::VirtualPosition::from(89),
+                    1,
+                    0,
+                )),
+            ),
+            ReferenceSequence::default(),
+        ];
+
+        let expected = Index::builder()
+            .set_min_shift(12)
+            .set_depth(7)
+            .set_aux(b"ndls".to_vec())
+            .set_reference_sequences(reference_sequences)
+            .set_unmapped_read_count(0)
+            .build();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_index(&expected)?;
+        writer.try_finish()?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        let actual = reader.read_index()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+//! Queries a bgzipped SAM file with a given region.
+//!
+//! The input SAM must have a tabix index in the same directory, e.g., `sample.sam.gz.tbi`.
+//!
+//! The result matches the output of `tabix <src> <region>`.
+
+use std::{env, fs::File, io, path::PathBuf};
+
+use noodles::Region;
+use noodles_bgzf as bgzf;
+use noodles_sam as sam;
+use noodles_tabix as tabix;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args();
+
+    let src = args.nth(1).map(PathBuf::from).expect("missing src");
+    let raw_region = args.next().expect("missing region");
+
+    let mut reader = File::open(&src)
+        .map(bgzf::Reader::new)
+        .map(sam::Reader::new)?;
+
+    let header: sam::Header = reader.read_header()?.parse()?;
+    let reference_sequences = header.reference_sequences();
+
+    let index = tabix::read(src.with_extension("gz.tbi"))?;
+
+    let region = Region::from_str_reference_sequences(&raw_region, reference_sequences)?;
+    let query = reader.query(&index, &region)?;
+
+    let stdout = io::stdout();
+    let handle = stdout.lock();
+    let mut writer = sam::Writer::new(handle);
+
+    for result in query {
+        let record = result?;
+        writer.write_record(&record)?;
+    }
+
+    Ok(())
+}
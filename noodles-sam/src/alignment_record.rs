@@ -0,0 +1,36 @@
+use std::io;
+
+use super::{header::ReferenceSequences, Record};
+
+/// An alignment record that can be decoded to a SAM record.
+///
+/// This allows records from other alignment formats, e.g., BAM, to be written by a
+/// [`sam::Writer`].
+///
+/// [`sam::Writer`]: struct.Writer.html
+pub trait AlignmentRecord {
+    /// Converts this record to a SAM record.
+    ///
+    /// The reference sequences are used to resolve reference sequence names from their IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam::{self as sam, header::ReferenceSequences, AlignmentRecord};
+    ///
+    /// let reference_sequences = ReferenceSequences::default();
+    /// let record = sam::Record::default();
+    ///
+    /// let sam_record = record.try_into_sam_record(&reference_sequences)?;
+    /// assert_eq!(sam_record, record);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    fn try_into_sam_record(&self, reference_sequences: &ReferenceSequences) -> io::Result<Record>;
+}
+
+impl AlignmentRecord for Record {
+    fn try_into_sam_record(&self, _: &ReferenceSequences) -> io::Result<Record> {
+        Ok(self.clone())
+    }
+}
@@ -319,6 +319,12 @@ impl ReferenceSequence {
     }
 }
 
+impl noodles::region::ReferenceSequence for ReferenceSequence {
+    fn len(&self) -> u64 {
+        self.len as u64
+    }
+}
+
 impl fmt::Display for ReferenceSequence {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", record::Kind::ReferenceSequence)?;
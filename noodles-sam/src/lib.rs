@@ -31,9 +31,13 @@
 //! # Ok::<(), io::Error>(())
 //! ```
 
+mod alignment_record;
 pub mod header;
 pub mod reader;
 pub mod record;
 mod writer;
 
-pub use self::{header::Header, reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    alignment_record::AlignmentRecord, header::Header, reader::Reader, record::Record,
+    writer::Writer,
+};
@@ -1,12 +1,15 @@
 //! SAM reader and iterators.
 
+mod query;
 mod records;
 
-pub use self::records::Records;
+pub use self::{query::Query, records::Records};
 
 use std::io::{self, BufRead, BufReader, Read, Seek};
 
+use noodles::Region;
 use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, NamedBinningIndex};
 
 const HEADER_PREFIX: u8 = b'@';
 const NEWLINE: u8 = b'\n';
@@ -194,10 +197,31 @@ where
     }
 }
 
-impl<R> Reader<BufReader<bgzf::Reader<R>>>
+impl<R> Reader<bgzf::Reader<R>>
 where
     R: Read + Seek,
 {
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_sam as sam;
+    ///
+    /// let data = io::Cursor::new(Vec::new());
+    /// let reader = sam::Reader::new(bgzf::Reader::new(data));
+    /// let virtual_position = reader.virtual_position();
+    ///
+    /// assert_eq!(virtual_position.compressed(), 0);
+    /// assert_eq!(virtual_position.uncompressed(), 0);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+
     /// Seeks the underlying BGZF stream to the given virtual position.
     ///
     /// Virtual positions typically come from an associated index.
@@ -206,8 +230,93 @@ where
     ///
     /// ```no_run
     /// # use std::{fs::File, io};
-    /// use std::io::BufReader;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_sam as sam;
     ///
+    /// let mut reader = File::open("sample.sam.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(sam::Reader::new)?;
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::from(102334155);
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// The index can be any binning index, e.g., a tabix index (TBI) or coordinate-sorted index
+    /// (CSI). The reference sequence of the region is resolved using the reference sequence names
+    /// in the index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles::Region;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_sam as sam;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let mut reader = File::open("sample.sam.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(sam::Reader::new)?;
+    ///
+    /// let index = tabix::read("sample.sam.gz.tbi")?;
+    /// let region = Region::mapped("sq0", 17711, 28657);
+    /// let query = reader.query(&index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<I>(&mut self, index: &I, region: &Region) -> io::Result<Query<'_, R>>
+    where
+        I: NamedBinningIndex + ?Sized,
+    {
+        let (reference_sequence_name, start, end) = csi::io::resolve_region(region)?;
+
+        let i = index
+            .reference_sequence_id(reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in index: {:?}",
+                        region
+                    ),
+                )
+            })?;
+
+        let chunks = index.query(i, start, end)?;
+
+        Ok(Query::new(
+            &mut self.inner,
+            chunks,
+            reference_sequence_name.into(),
+            start,
+            end,
+        ))
+    }
+}
+
+impl<R> Reader<BufReader<bgzf::Reader<R>>>
+where
+    R: Read + Seek,
+{
+    /// Seeks the underlying BGZF stream to the given virtual position.
+    ///
+    /// Data buffered by the [`BufReader`] is discarded. Prefer using a BGZF reader directly, which
+    /// is already buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io::{self, BufReader}};
     /// use noodles_bgzf as bgzf;
     /// use noodles_sam as sam;
     ///
@@ -221,12 +330,16 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        let len = self.inner.buffer().len();
+        self.inner.consume(len);
         self.inner.get_mut().seek(pos)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use noodles_tabix as tabix;
+
     use super::*;
 
     #[test]
@@ -245,4 +358,106 @@ mod tests {
         assert_eq!(header, "@HD\tVN1.6\n");
         Ok(())
     }
+
+    #[test]
+    fn test_seek_with_buf_reader() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{convert::TryFrom, io::Write};
+
+        let header = "@HD\tVN:1.6\n";
+        let records = "r0\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\nr1\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\n";
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(header.as_bytes())?;
+        writer.write_all(records.as_bytes())?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(BufReader::new(bgzf::Reader::new(io::Cursor::new(data))));
+
+        let virtual_position = bgzf::VirtualPosition::try_from((0, header.len() as u16))?;
+        reader.seek(virtual_position)?;
+
+        let mut buf = String::new();
+        reader.read_record(&mut buf)?;
+        assert!(buf.starts_with("r0\t"));
+
+        buf.clear();
+        reader.read_record(&mut buf)?;
+        assert!(buf.starts_with("r1\t"));
+
+        reader.seek(virtual_position)?;
+
+        buf.clear();
+        reader.read_record(&mut buf)?;
+        assert!(buf.starts_with("r0\t"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{convert::TryFrom, io::Write};
+
+        use noodles_tabix::index::reference_sequence::{bin::Chunk, Bin, ReferenceSequence};
+
+        let header = "@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:55\n@SQ\tSN:sq1\tLN:34\n";
+        let records = "r0\t0\tsq0\t8\t255\t4M\t*\t0\t0\tACGT\tNDLS
+r1\t0\tsq0\t34\t255\t4M\t*\t0\t0\tACGT\tNDLS
+r2\t0\tsq1\t8\t255\t4M\t*\t0\t0\tACGT\tNDLS
+";
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(header.as_bytes())?;
+        writer.write_all(records.as_bytes())?;
+        let data = writer.finish()?;
+
+        let virtual_position = |pos: usize| bgzf::VirtualPosition::try_from((0, pos as u16));
+        let sq0_start = header.len();
+        let sq1_start = sq0_start + records.find("r2").unwrap();
+        let sq1_end = header.len() + records.len();
+
+        let index = tabix::Index::builder()
+            .set_reference_sequence_names(vec![String::from("sq0"), String::from("sq1")])
+            .set_reference_sequences(vec![
+                ReferenceSequence::new(
+                    vec![Bin::new(
+                        4681,
+                        vec![Chunk::new(
+                            virtual_position(sq0_start)?,
+                            virtual_position(sq1_start)?,
+                        )],
+                    )],
+                    Vec::new(),
+                    None,
+                ),
+                ReferenceSequence::new(
+                    vec![Bin::new(
+                        4681,
+                        vec![Chunk::new(
+                            virtual_position(sq1_start)?,
+                            virtual_position(sq1_end)?,
+                        )],
+                    )],
+                    Vec::new(),
+                    None,
+                ),
+            ])
+            .build();
+
+        let mut reader = Reader::new(bgzf::Reader::new(io::Cursor::new(data)));
+
+        let region = Region::mapped("sq0", 1, 13);
+        let records: Vec<_> = reader.query(&index, &region)?.collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].read_name().map(|name| name.as_str()), Some("r0"));
+
+        let region = Region::mapped("sq1", 1, 34);
+        let records: Vec<_> = reader.query(&index, &region)?.collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].read_name().map(|name| name.as_str()), Some("r2"));
+
+        let region = Region::mapped("sq2", 1, 8);
+        assert!(reader.query(&index, &region).is_err());
+
+        Ok(())
+    }
 }
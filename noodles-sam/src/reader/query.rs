@@ -0,0 +1,122 @@
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+
+use crate::Record;
+
+use super::Reader;
+
+/// An iterator over records of a SAM reader that intersect a given region.
+///
+/// This is created by calling [`sam::Reader::query`].
+///
+/// [`sam::Reader::query`]: struct.Reader.html#method.query
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: Reader<csi::io::Query<'a, R>>,
+    reference_sequence_name: String,
+    start: u64,
+    end: u64,
+    line_buf: String,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(crate) fn new(
+        reader: &'a mut bgzf::Reader<R>,
+        chunks: Vec<Chunk>,
+        reference_sequence_name: String,
+        start: u64,
+        end: u64,
+    ) -> Self {
+        Self {
+            reader: Reader::new(csi::io::Query::new(reader, chunks)),
+            reference_sequence_name,
+            start,
+            end,
+            line_buf: String::new(),
+        }
+    }
+
+    fn read_record(&mut self) -> Option<io::Result<Record>> {
+        self.line_buf.clear();
+
+        match self.reader.read_record(&mut self.line_buf) {
+            Ok(0) => None,
+            Ok(_) => Some(
+                self.line_buf
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            ),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<'a, R> Iterator for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.read_record()? {
+                Ok(record) => {
+                    if intersects(&record, &self.reference_sequence_name, self.start, self.end) {
+                        return Some(Ok(record));
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+fn intersects(record: &Record, reference_sequence_name: &str, start: u64, end: u64) -> bool {
+    let record_reference_sequence_name = match record.reference_sequence_name() {
+        Some(name) => name,
+        None => return false,
+    };
+
+    let record_start = match record.position() {
+        Some(position) => i32::from(position) as u64,
+        None => return false,
+    };
+
+    // Records with no reference span, e.g., ones with an empty CIGAR, cover a single position.
+    let record_reference_len = u64::from(record.cigar().reference_len()).max(1);
+    let record_end = record_start + record_reference_len - 1;
+
+    record_reference_sequence_name.as_str() == reference_sequence_name
+        && in_interval(record_start, record_end, start, end)
+}
+
+fn in_interval(a_start: u64, a_end: u64, b_start: u64, b_end: u64) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersects() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "r0\t0\tsq0\t8\t255\t4M\t*\t0\t0\tACGT\tNDLS".parse()?;
+
+        assert!(intersects(&record, "sq0", 1, 8));
+        assert!(intersects(&record, "sq0", 11, 13));
+        assert!(!intersects(&record, "sq0", 12, 13));
+        assert!(!intersects(&record, "sq1", 8, 11));
+
+        let record: Record = "r1\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*".parse()?;
+        assert!(!intersects(&record, "sq0", 1, 8));
+
+        Ok(())
+    }
+}
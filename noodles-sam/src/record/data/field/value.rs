@@ -18,6 +18,11 @@ pub enum Value {
     Char(char),
     /// A SAM data field 32-bit integer (`i`).
     Int32(i32),
+    /// A SAM data field 32-bit unsigned integer (`i`).
+    ///
+    /// The SAM integer type (`i`) has a range of [-2^31, 2^32). This holds values that do not fit
+    /// in a 32-bit integer.
+    UInt32(u32),
     /// A SAM data field single-precision floating-point (`f`).
     Float(f32),
     /// A SAM data field string (`Z`).
@@ -52,7 +57,7 @@ impl Value {
     pub fn ty(&self) -> Type {
         match *self {
             Self::Char(_) => Type::Char,
-            Self::Int32(_) => Type::Int32,
+            Self::Int32(_) | Self::UInt32(_) => Type::Int32,
             Self::Float(_) => Type::Float,
             Self::String(_) => Type::String,
             Self::Hex(_) => Type::Hex,
@@ -148,6 +153,35 @@ impl Value {
         self.as_int32().is_some()
     }
 
+    /// Returns the value as a 32-bit unsigned integer if it is a 32-bit unsigned integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::Value;
+    /// assert_eq!(Value::UInt32(0).as_uint32(), Some(0));
+    /// assert_eq!(Value::Int32(0).as_uint32(), None);
+    /// ```
+    pub fn as_uint32(&self) -> Option<u32> {
+        match *self {
+            Self::UInt32(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the value is a 32-bit unsigned integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::Value;
+    /// assert!(Value::UInt32(0).is_uint32());
+    /// assert!(!Value::Int32(0).is_uint32());
+    /// ```
+    pub fn is_uint32(&self) -> bool {
+        self.as_uint32().is_some()
+    }
+
     /// Returns the value as a single-precision floating-point if it is a single-precision
     /// float-point.
     ///
@@ -449,6 +483,7 @@ impl fmt::Display for Value {
         match self {
             Self::Char(c) => write!(f, "{}", c),
             Self::Int32(n) => write!(f, "{}", n),
+            Self::UInt32(n) => write!(f, "{}", n),
             Self::Float(n) => write!(f, "{}", n),
             Self::String(s) => f.write_str(s),
             Self::Hex(s) => f.write_str(s),
@@ -572,7 +607,7 @@ impl FromStr for Value {
 
         match ty {
             Type::Char => parse_char(value).map(Value::Char),
-            Type::Int32 => parse_i32(value).map(Value::Int32),
+            Type::Int32 => parse_int(value),
             Type::Float => parse_f32(value).map(Value::Float),
             Type::String => Ok(Value::String(value.into())),
             Type::Hex => Ok(Value::Hex(value.into())),
@@ -609,6 +644,13 @@ fn parse_u32(s: &str) -> Result<u32, ParseError> {
     s.parse().map_err(ParseError::InvalidIntValue)
 }
 
+// Values in [2^31, 2^32) are parsed as 32-bit unsigned integers.
+fn parse_int(s: &str) -> Result<Value, ParseError> {
+    parse_i32(s)
+        .map(Value::Int32)
+        .or_else(|e| parse_u32(s).map(Value::UInt32).map_err(|_| e))
+}
+
 fn parse_f32(s: &str) -> Result<f32, ParseError> {
     s.parse().map_err(ParseError::InvalidFloatValue)
 }
@@ -667,6 +709,7 @@ mod tests {
     fn test_ty() {
         assert_eq!(Value::Char('n').ty(), Type::Char);
         assert_eq!(Value::Int32(0).ty(), Type::Int32);
+        assert_eq!(Value::UInt32(0).ty(), Type::Int32);
         assert_eq!(Value::Float(0.0).ty(), Type::Float);
         assert_eq!(Value::String(String::from("noodles")).ty(), Type::String);
         assert_eq!(Value::Hex(String::from("cafe")).ty(), Type::Hex);
@@ -683,6 +726,7 @@ mod tests {
     fn test_subtype() {
         assert_eq!(Value::Char('n').subtype(), None);
         assert_eq!(Value::Int32(0).subtype(), None);
+        assert_eq!(Value::UInt32(0).subtype(), None);
         assert_eq!(Value::Float(0.0).subtype(), None);
         assert_eq!(Value::String(String::from("noodles")).subtype(), None);
         assert_eq!(Value::Hex(String::from("cafe")).subtype(), None);
@@ -699,6 +743,7 @@ mod tests {
     fn test_fmt() {
         assert_eq!(Value::Char('n').to_string(), "n");
         assert_eq!(Value::Int32(13).to_string(), "13");
+        assert_eq!(Value::UInt32(4294967295).to_string(), "4294967295");
         assert_eq!(Value::Float(3.14).to_string(), "3.14");
 
         assert_eq!(
@@ -719,4 +764,19 @@ mod tests {
             "f,2.71,3.14"
         );
     }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("i:13".parse(), Ok(Value::Int32(13)));
+        assert_eq!("i:-2147483648".parse(), Ok(Value::Int32(i32::MIN)));
+        assert_eq!("i:4294967295".parse(), Ok(Value::UInt32(u32::MAX)));
+        assert!(matches!(
+            "i:4294967296".parse::<Value>(),
+            Err(ParseError::InvalidIntValue(_))
+        ));
+        assert!(matches!(
+            "i:-2147483649".parse::<Value>(),
+            Err(ParseError::InvalidIntValue(_))
+        ));
+    }
 }
@@ -1,6 +1,6 @@
 use std::io::{self, Write};
 
-use super::{record, Header, Record};
+use super::{header::ReferenceSequences, record, AlignmentRecord, Header, Record};
 
 /// A SAM writer.
 ///
@@ -152,6 +152,39 @@ where
             writeln!(self.inner, "\t{}", record.data())
         }
     }
+
+    /// Writes an alignment record as a SAM record.
+    ///
+    /// The record is decoded to a SAM record using the given reference sequences, which are
+    /// typically the reference sequences in the SAM header. This is used to write records read
+    /// from other alignment formats, e.g., BAM.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = sam::Writer::new(Vec::new());
+    ///
+    /// let header = sam::Header::default();
+    /// let record = sam::Record::default();
+    /// writer.write_alignment_record(header.reference_sequences(), &record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"*\t4\t*\t0\t255\t*\t*\t0\t0\t*\t*\n");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_alignment_record<R>(
+        &mut self,
+        reference_sequences: &ReferenceSequences,
+        record: &R,
+    ) -> io::Result<()>
+    where
+        R: AlignmentRecord,
+    {
+        let record = record.try_into_sam_record(reference_sequences)?;
+        self.write_record(&record)
+    }
 }
 
 #[cfg(test)]
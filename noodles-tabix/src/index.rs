@@ -9,6 +9,12 @@ pub use self::{
     builder::Builder, header::Header, indexer::Indexer, reference_sequence::ReferenceSequence,
 };
 
+use std::io;
+
+use noodles_csi::{self as csi, BinningIndex, NamedBinningIndex};
+
+use self::reference_sequence::bin::Chunk;
+
 /// A tabix index.
 #[derive(Debug)]
 pub struct Index {
@@ -74,7 +80,7 @@ impl Index {
     /// ```
     /// use noodles_tabix::{self as tabix, index::ReferenceSequence};
     ///
-    /// let reference_sequences = vec![ReferenceSequence::new(Vec::new(), Vec::new())];
+    /// let reference_sequences = vec![ReferenceSequence::new(Vec::new(), Vec::new(), None)];
     ///
     /// let index = tabix::Index::builder()
     ///     .set_reference_sequences(reference_sequences)
@@ -102,6 +108,86 @@ impl Index {
     pub fn unmapped_read_count(&self) -> Option<u64> {
         self.unmapped_read_count
     }
+
+    /// Returns a list of optimized chunks that may contain records in the given region.
+    ///
+    /// The reference sequence is found by name in the list of [`reference_sequence_names`]. `start`
+    /// and `end` are 1-based, inclusive.
+    ///
+    /// The returned chunks are sorted and non-overlapping (see [`optimize_chunks`]).
+    ///
+    /// [`reference_sequence_names`]: #method.reference_sequence_names
+    /// [`optimize_chunks`]: ../fn.optimize_chunks.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_tabix::{self as tabix, index::ReferenceSequence};
+    ///
+    /// let index = tabix::Index::builder()
+    ///     .set_reference_sequence_names(vec![String::from("sq0")])
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(Vec::new(), Vec::new(), None)])
+    ///     .build();
+    ///
+    /// let chunks = index.query_by_name("sq0", 8, 13)?;
+    /// assert!(chunks.is_empty());
+    ///
+    /// assert!(index.query_by_name("sq1", 8, 13).is_err());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn query_by_name(
+        &self,
+        reference_sequence_name: &str,
+        start: u64,
+        end: u64,
+    ) -> io::Result<Vec<Chunk>> {
+        let i = self
+            .reference_sequence_id(reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "reference sequence name does not exist in index: {}",
+                        reference_sequence_name
+                    ),
+                )
+            })?;
+
+        self.query_by_id(i, start, end)
+    }
+
+    fn query_by_id(
+        &self,
+        reference_sequence_id: usize,
+        start: u64,
+        end: u64,
+    ) -> io::Result<Vec<Chunk>> {
+        let reference_sequence = self
+            .reference_sequences()
+            .get(reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "could not find reference in index: {} >= {}",
+                        reference_sequence_id,
+                        self.reference_sequences().len()
+                    ),
+                )
+            })?;
+
+        let chunks: Vec<_> = reference_sequence
+            .query(start, end)?
+            .iter()
+            .flat_map(|bin| bin.chunks())
+            .copied()
+            .collect();
+
+        let min_offset = reference_sequence.min_offset(start);
+
+        Ok(csi::optimize_chunks(&chunks, min_offset))
+    }
 }
 
 impl Default for Index {
@@ -109,3 +195,69 @@ impl Default for Index {
         Builder::default().build()
     }
 }
+
+impl NamedBinningIndex for Index {
+    fn reference_sequence_id(&self, reference_sequence_name: &str) -> Option<usize> {
+        self.reference_sequence_names()
+            .iter()
+            .position(|name| name == reference_sequence_name)
+    }
+}
+
+impl BinningIndex for Index {
+    fn query(&self, reference_sequence_id: usize, start: u64, end: u64) -> io::Result<Vec<Chunk>> {
+        self.query_by_id(reference_sequence_id, start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::{reference_sequence::Bin, *};
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let index = Index::builder()
+            .set_reference_sequence_names(vec![String::from("sq0")])
+            .set_reference_sequences(vec![ReferenceSequence::new(
+                vec![
+                    Bin::new(
+                        0,
+                        vec![Chunk::new(
+                            bgzf::VirtualPosition::from(5),
+                            bgzf::VirtualPosition::from(13),
+                        )],
+                    ),
+                    Bin::new(
+                        4681,
+                        vec![Chunk::new(
+                            bgzf::VirtualPosition::from(2),
+                            bgzf::VirtualPosition::from(8),
+                        )],
+                    ),
+                    Bin::new(
+                        4682,
+                        vec![Chunk::new(
+                            bgzf::VirtualPosition::from(21),
+                            bgzf::VirtualPosition::from(34),
+                        )],
+                    ),
+                ],
+                vec![bgzf::VirtualPosition::from(3)],
+                None,
+            )])
+            .build();
+
+        let actual = index.query_by_name("sq0", 8, 13)?;
+        let expected = [Chunk::new(
+            bgzf::VirtualPosition::from(2),
+            bgzf::VirtualPosition::from(13),
+        )];
+        assert_eq!(actual, expected);
+
+        assert!(index.query_by_name("sq1", 8, 13).is_err());
+
+        Ok(())
+    }
+}
@@ -2,18 +2,28 @@
 
 pub mod bin;
 mod builder;
+pub mod metadata;
 
-pub use self::bin::Bin;
+pub use self::{bin::Bin, metadata::Metadata};
 
 pub(crate) use self::builder::Builder;
 
+use std::io;
+
+use bit_vec::BitVec;
 use noodles_bgzf as bgzf;
 
+const WINDOW_SIZE: u64 = 16384;
+
+// The maximum position (exclusive) addressable by the tabix binning scheme.
+const MAX_POSITION: u64 = 1 << 29;
+
 /// A tabix index reference sequence.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct ReferenceSequence {
     bins: Vec<Bin>,
     intervals: Vec<bgzf::VirtualPosition>,
+    metadata: Option<Metadata>,
 }
 
 impl ReferenceSequence {
@@ -27,19 +37,31 @@ impl ReferenceSequence {
     ///
     /// ```
     /// use noodles_tabix::index::ReferenceSequence;
-    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new());
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new(), None);
     /// ```
-    pub fn new(bins: Vec<Bin>, intervals: Vec<bgzf::VirtualPosition>) -> Self {
-        Self { bins, intervals }
+    pub fn new(
+        bins: Vec<Bin>,
+        intervals: Vec<bgzf::VirtualPosition>,
+        metadata: Option<Metadata>,
+    ) -> Self {
+        Self {
+            bins,
+            intervals,
+            metadata,
+        }
     }
 
     /// Returns the list of bins in the reference sequence.
     ///
+    /// This list does not include the metadata pseudo-bin (bin 37450). Use [`metadata`] instead.
+    ///
+    /// [`metadata`]: #method.metadata
+    ///
     /// # Examples
     ///
     /// ```
     /// use noodles_tabix::index::ReferenceSequence;
-    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new());
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new(), None);
     /// assert!(reference_sequence.bins().is_empty());
     /// ```
     pub fn bins(&self) -> &[Bin] {
@@ -52,10 +74,234 @@ impl ReferenceSequence {
     ///
     /// ```
     /// use noodles_tabix::index::ReferenceSequence;
-    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new());
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new(), None);
     /// assert!(reference_sequence.intervals().is_empty());
     /// ```
     pub fn intervals(&self) -> &[bgzf::VirtualPosition] {
         &self.intervals
     }
+
+    /// Returns metadata for this reference sequence.
+    ///
+    /// Metadata is parsed from the optional pseudo-bin 37450.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::VirtualPosition;
+    /// use noodles_tabix::index::{reference_sequence::Metadata, ReferenceSequence};
+    ///
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new(), None);
+    /// assert!(reference_sequence.metadata().is_none());
+    ///
+    /// let reference_sequence = ReferenceSequence::new(
+    ///     Vec::new(),
+    ///     Vec::new(),
+    ///     Some(Metadata::new(VirtualPosition::from(610), VirtualPosition::from(1597), 55, 0))
+    /// );
+    /// assert!(reference_sequence.metadata().is_some());
+    /// ```
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Returns a list of bins in this reference sequence that intersect the given range.
+    ///
+    /// `start` and `end` are 1-based, inclusive. `end` is clamped to the maximum position
+    /// addressable by the tabix binning scheme (2^29).
+    ///
+    /// This returns an error if `start` is 0 or `start` > `end`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_tabix::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new(), None);
+    /// let query_bins = reference_sequence.query(8, 13)?;
+    /// assert!(query_bins.is_empty());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn query(&self, start: u64, end: u64) -> io::Result<Vec<&Bin>> {
+        if start == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid start position: expected >= 1, got 0",
+            ));
+        }
+
+        let end = end.min(MAX_POSITION);
+
+        if start > end {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid interval: expected start <= end, got {} > {}",
+                    start, end
+                ),
+            ));
+        }
+
+        let region_bins = region_to_bins((start - 1) as usize, end as usize);
+
+        let query_bins = self
+            .bins()
+            .iter()
+            .filter(|b| region_bins.get(b.id() as usize).unwrap_or(false))
+            .collect();
+
+        Ok(query_bins)
+    }
+
+    /// Finds the minimum start offset in the linear index for a given start position.
+    ///
+    /// `start` is 1-based.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new(), None);
+    /// assert_eq!(reference_sequence.min_offset(13), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn min_offset(&self, start: u64) -> bgzf::VirtualPosition {
+        let i = (start.saturating_sub(1) / WINDOW_SIZE) as usize;
+        self.intervals.get(i).copied().unwrap_or_default()
+    }
+}
+
+// 0-based, [start, end)
+fn region_to_bins(start: usize, mut end: usize) -> BitVec {
+    end -= 1;
+
+    let mut bins = BitVec::from_elem(bin::MAX_ID, false);
+    bins.set(0, true);
+
+    for k in (1 + (start >> 26))..=(1 + (end >> 26)) {
+        bins.set(k, true);
+    }
+
+    for k in (9 + (start >> 23))..=(9 + (end >> 23)) {
+        bins.set(k, true);
+    }
+
+    for k in (73 + (start >> 20))..=(73 + (end >> 20)) {
+        bins.set(k, true);
+    }
+
+    for k in (585 + (start >> 17))..=(585 + (end >> 17)) {
+        bins.set(k, true);
+    }
+
+    for k in (4681 + (start >> 14))..=(4681 + (end >> 14)) {
+        bins.set(k, true);
+    }
+
+    bins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let reference_sequence = ReferenceSequence::new(
+            vec![
+                Bin::new(0, Vec::new()),
+                Bin::new(1, Vec::new()),
+                Bin::new(2, Vec::new()),
+                Bin::new(4681, Vec::new()),
+            ],
+            Vec::new(),
+            None,
+        );
+
+        let actual: Vec<_> = reference_sequence
+            .query(8, 13)?
+            .iter()
+            .map(|b| b.id())
+            .collect();
+        assert_eq!(actual, [0, 1, 4681]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_pseudo_bin_and_large_end() -> io::Result<()> {
+        let reference_sequence = ReferenceSequence::new(
+            vec![
+                Bin::new(0, Vec::new()),
+                Bin::new(8, Vec::new()),
+                Bin::new(metadata::MAGIC_NUMBER, Vec::new()),
+            ],
+            Vec::new(),
+            None,
+        );
+
+        let actual: Vec<_> = reference_sequence
+            .query(1, u64::MAX)?
+            .iter()
+            .map(|b| b.id())
+            .collect();
+        assert_eq!(actual, [0, 8]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_invalid_interval() {
+        let reference_sequence = ReferenceSequence::default();
+
+        assert!(matches!(
+            reference_sequence.query(0, 13),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            reference_sequence.query(13, 8),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_min_offset() {
+        let reference_sequence = ReferenceSequence::new(
+            Vec::new(),
+            vec![
+                bgzf::VirtualPosition::from(8),
+                bgzf::VirtualPosition::from(13),
+            ],
+            None,
+        );
+
+        assert_eq!(
+            reference_sequence.min_offset(0),
+            bgzf::VirtualPosition::from(8)
+        );
+        assert_eq!(
+            reference_sequence.min_offset(1),
+            bgzf::VirtualPosition::from(8)
+        );
+        assert_eq!(
+            reference_sequence.min_offset(16385),
+            bgzf::VirtualPosition::from(13)
+        );
+        assert_eq!(
+            reference_sequence.min_offset(32769),
+            bgzf::VirtualPosition::default()
+        );
+    }
+
+    #[test]
+    fn test_region_to_bins() {
+        // [8, 13]
+        let actual = region_to_bins(7, 13);
+        let mut expected = BitVec::from_elem(bin::MAX_ID, false);
+        for &k in &[0, 1, 9, 73, 585, 4681] {
+            expected.set(k, true);
+        }
+        assert_eq!(actual, expected);
+    }
 }
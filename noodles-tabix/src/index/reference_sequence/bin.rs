@@ -1,12 +1,14 @@
 //! Tabix index bin and fields.
 
 mod builder;
-mod chunk;
 
-pub use self::chunk::Chunk;
+pub use noodles_csi::index::reference_sequence::bin::Chunk;
 
 pub(crate) use self::builder::Builder;
 
+// § 5.3 C source code for computing bin number and overlapping bins (2020-04-30)
+pub(crate) const MAX_ID: usize = ((1 << 18) - 1) / 7 + 1;
+
 /// A tabix index reference sequence bin.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Bin {
@@ -29,7 +29,7 @@ impl Builder {
             .map(|(_, b)| b.build())
             .collect();
 
-        ReferenceSequence::new(bins, self.intervals)
+        ReferenceSequence::new(bins, self.intervals, None)
     }
 
     fn update_bins(&mut self, start: u32, end: u32, chunk: Chunk) {
@@ -31,6 +31,8 @@ mod writer;
 
 pub use self::{index::Index, reader::Reader, writer::Writer};
 
+pub use noodles_csi::{merge_chunks, optimize_chunks};
+
 use std::{fs::File, io, path::Path};
 
 static MAGIC_NUMBER: &[u8] = b"TBI\x01";
@@ -10,7 +10,7 @@ use noodles_bgzf as bgzf;
 use crate::index::{
     self,
     header::Format,
-    reference_sequence::{bin::Chunk, Bin},
+    reference_sequence::{self, bin::Chunk, Bin, Metadata},
     ReferenceSequence,
 };
 
@@ -174,28 +174,39 @@ where
     let mut references = Vec::with_capacity(len);
 
     for _ in 0..len {
-        let bins = read_bins(reader)?;
+        let (bins, metadata) = read_bins(reader)?;
         let intervals = read_intervals(reader)?;
-        references.push(ReferenceSequence::new(bins, intervals));
+        references.push(ReferenceSequence::new(bins, intervals, metadata));
     }
 
     Ok(references)
 }
 
-fn read_bins<R>(reader: &mut R) -> io::Result<Vec<Bin>>
+fn read_bins<R>(reader: &mut R) -> io::Result<(Vec<Bin>, Option<Metadata>)>
 where
     R: Read,
 {
+    use reference_sequence::metadata::TryFromBinError;
+
     let n_bin = reader.read_i32::<LittleEndian>()?;
+
     let mut bins = Vec::with_capacity(n_bin as usize);
+    let mut metadata = None;
 
     for _ in 0..n_bin {
-        let bin = reader.read_u32::<LittleEndian>()?;
+        let id = reader.read_u32::<LittleEndian>()?;
         let chunks = read_chunks(reader)?;
-        bins.push(Bin::new(bin, chunks));
+
+        let bin = Bin::new(id, chunks);
+
+        match Metadata::try_from(&bin) {
+            Ok(m) => metadata = Some(m),
+            Err(TryFromBinError::InvalidMagicNumber(_)) => bins.push(bin),
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
     }
 
-    Ok(bins)
+    Ok((bins, metadata))
 }
 
 fn read_chunks<R>(reader: &mut R) -> io::Result<Vec<Chunk>>
@@ -240,6 +251,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use byteorder::WriteBytesExt;
+
     use super::*;
 
     #[test]
@@ -257,6 +270,59 @@ mod tests {
         assert!(reader.read_index().is_err());
     }
 
+    #[test]
+    fn test_read_bins() -> io::Result<()> {
+        let mut writer = Vec::new();
+        // n_bin
+        writer.write_i32::<LittleEndian>(2)?;
+        // bin
+        writer.write_u32::<LittleEndian>(4681)?;
+        // n_chunk
+        writer.write_i32::<LittleEndian>(1)?;
+        // chunk_beg
+        writer.write_u64::<LittleEndian>(610)?;
+        // chunk_end
+        writer.write_u64::<LittleEndian>(1597)?;
+        // bin (pseudo-bin)
+        writer.write_u32::<LittleEndian>(37450)?;
+        // n_chunk
+        writer.write_i32::<LittleEndian>(2)?;
+        // ref_beg
+        writer.write_u64::<LittleEndian>(610)?;
+        // ref_end
+        writer.write_u64::<LittleEndian>(1597)?;
+        // n_mapped
+        writer.write_u64::<LittleEndian>(55)?;
+        // n_unmapped
+        writer.write_u64::<LittleEndian>(0)?;
+
+        let mut reader = &writer[..];
+        let (bins, metadata) = read_bins(&mut reader)?;
+
+        assert_eq!(
+            bins,
+            [Bin::new(
+                4681,
+                vec![Chunk::new(
+                    bgzf::VirtualPosition::from(610),
+                    bgzf::VirtualPosition::from(1597)
+                )]
+            )]
+        );
+
+        assert_eq!(
+            metadata,
+            Some(Metadata::new(
+                bgzf::VirtualPosition::from(610),
+                bgzf::VirtualPosition::from(1597),
+                55,
+                0
+            ))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_names() -> io::Result<()> {
         let data = b"noodles\x00tabix\x00";
@@ -131,13 +131,23 @@ pub fn write_reference_sequence<W>(writer: &mut W, reference: &ReferenceSequence
 where
     W: Write,
 {
-    let n_bin = reference.bins().len() as i32;
+    let mut n_bin = reference.bins().len() as i32;
+
+    if reference.metadata().is_some() {
+        n_bin += 1;
+    }
+
     writer.write_i32::<LittleEndian>(n_bin)?;
 
     for bin in reference.bins() {
         write_bin(writer, bin)?;
     }
 
+    if let Some(metadata) = reference.metadata() {
+        let bin = Bin::from(metadata.clone());
+        write_bin(writer, &bin)?;
+    }
+
     let n_intv = reference.intervals().len() as i32;
     writer.write_i32::<LittleEndian>(n_intv)?;
 
@@ -184,7 +194,7 @@ mod tests {
 
     use noodles_bgzf as bgzf;
 
-    use super::*;
+    use super::{index::reference_sequence::Metadata, *};
 
     #[test]
     fn test_write_index() -> io::Result<()> {
@@ -194,7 +204,13 @@ mod tests {
         )];
         let bins = vec![Bin::new(16385, chunks)];
         let intervals = vec![bgzf::VirtualPosition::from(337)];
-        let references = vec![ReferenceSequence::new(bins, intervals)];
+        let metadata = Metadata::new(
+            bgzf::VirtualPosition::from(509268599425),
+            bgzf::VirtualPosition::from(509268599570),
+            1,
+            0,
+        );
+        let references = vec![ReferenceSequence::new(bins, intervals, Some(metadata))];
 
         let index = Index::builder()
             .set_reference_sequence_names(vec![String::from("sq0"), String::from("sq1")])
@@ -226,7 +242,7 @@ mod tests {
         // names
         expected_writer.write_all(b"sq0\x00sq1\x00")?;
         // n_bin
-        expected_writer.write_u32::<LittleEndian>(1)?;
+        expected_writer.write_u32::<LittleEndian>(2)?;
         // bin
         expected_writer.write_u32::<LittleEndian>(16385)?;
         // n_chunk
@@ -235,6 +251,18 @@ mod tests {
         expected_writer.write_u64::<LittleEndian>(509268599425)?;
         // chunk_end
         expected_writer.write_u64::<LittleEndian>(509268599570)?;
+        // bin (pseudo-bin)
+        expected_writer.write_u32::<LittleEndian>(37450)?;
+        // n_chunk
+        expected_writer.write_u32::<LittleEndian>(2)?;
+        // ref_beg
+        expected_writer.write_u64::<LittleEndian>(509268599425)?;
+        // ref_end
+        expected_writer.write_u64::<LittleEndian>(509268599570)?;
+        // n_mapped
+        expected_writer.write_u64::<LittleEndian>(1)?;
+        // n_unmapped
+        expected_writer.write_u64::<LittleEndian>(0)?;
         // n_intv
         expected_writer.write_u32::<LittleEndian>(1)?;
         // ioffset
@@ -1,23 +1,82 @@
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+};
+
+use noodles_bam as bam;
+use noodles_sam as sam;
 use structopt::StructOpt;
-use std::{fs::File, io::BufReader};
+
 /// A tutorial on noodles bioinformatics I/O
+///
+/// Prints the records of a SAM or BAM file as SAM, similar to `samtools view`.
 #[derive(StructOpt)]
 struct Cli {
     /// Path to the file to read
-    #[structopt(
-    short="i", 
-    long = "--infile",
-    parse(from_os_str)
-)]
-    infile: std::path::PathBuf,
+    #[structopt(short = "i", long = "--infile", parse(from_os_str))]
+    infile: PathBuf,
+
+    /// Include the header in the output
+    #[structopt(long = "--with-header")]
+    with_header: bool,
+}
+
+fn is_bam<P>(src: P) -> bool
+where
+    P: AsRef<Path>,
+{
+    src.as_ref()
+        .extension()
+        .map(|ext| ext == "bam")
+        .unwrap_or_default()
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Cli::from_args();
-    let mut reader = File::open(&args.infile).map(BufReader::new).map(noodles_sam::Reader::new)?;
-    for result in reader.records() {
-        let record = result;
-        println!("{:?}", record);
- }
+
+    let stdout = io::stdout();
+    let mut writer = sam::Writer::new(stdout.lock());
+
+    if is_bam(&args.infile) {
+        let mut reader = File::open(&args.infile).map(bam::Reader::new)?;
+        let mut header: sam::Header = reader.read_header()?.parse()?;
+        let reference_sequences = reader.read_reference_sequences()?;
+
+        // The SAM header text in a BAM file may be empty.
+        if header.reference_sequences().is_empty() {
+            let mut builder = sam::Header::builder();
+
+            for reference_sequence in reference_sequences {
+                builder = builder.add_reference_sequence(reference_sequence);
+            }
+
+            header = builder.build();
+        }
+
+        if args.with_header {
+            writer.write_header(&header)?;
+        }
+
+        for result in reader.records() {
+            let record = result?;
+            writer.write_alignment_record(header.reference_sequences(), &record)?;
+        }
+    } else {
+        let mut reader = File::open(&args.infile)
+            .map(BufReader::new)
+            .map(sam::Reader::new)?;
+        let header: sam::Header = reader.read_header()?.parse()?;
+
+        if args.with_header {
+            writer.write_header(&header)?;
+        }
+
+        for result in reader.records() {
+            let record = result?;
+            writer.write_record(&record)?;
+        }
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
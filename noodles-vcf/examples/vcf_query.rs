@@ -0,0 +1,52 @@
+//! Queries a bgzipped VCF file with a given region.
+//!
+//! The input VCF must have an index in the same directory, either a tabix index (TBI), e.g.,
+//! `sample.vcf.gz.tbi`, or a coordinate-sorted index (CSI), e.g., `sample.vcf.gz.csi`. A CSI is
+//! preferred if both exist.
+//!
+//! The result matches the output of `bcftools view --no-header <src> <name>:<start>-<end>`.
+
+use std::{env, fs::File, io, path::PathBuf};
+
+use noodles::Region;
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, NamedBinningIndex};
+use noodles_tabix as tabix;
+use noodles_vcf as vcf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args();
+
+    let src = args.nth(1).map(PathBuf::from).expect("missing src");
+    let reference_sequence_name = args.next().expect("missing reference sequence name");
+    let start = args.next().expect("missing start").parse()?;
+    let end = args.next().expect("missing end").parse()?;
+
+    let mut reader = File::open(&src)
+        .map(bgzf::Reader::new)
+        .map(vcf::Reader::new)?;
+
+    reader.read_header()?;
+
+    let csi_src = src.with_extension("gz.csi");
+
+    let index: Box<dyn NamedBinningIndex> = if csi_src.exists() {
+        csi::read(csi_src).map(Box::new)?
+    } else {
+        tabix::read(src.with_extension("gz.tbi")).map(Box::new)?
+    };
+
+    let region = Region::mapped(reference_sequence_name, start, end);
+    let query = reader.query(index.as_ref(), &region)?;
+
+    let stdout = io::stdout();
+    let handle = stdout.lock();
+    let mut writer = vcf::Writer::new(handle);
+
+    for result in query {
+        let record = result?;
+        writer.write_record(&record)?;
+    }
+
+    Ok(())
+}
@@ -1,12 +1,15 @@
 //! VCF reader and iterators.
 
+mod query;
 mod records;
 
-pub use self::records::Records;
+pub use self::{query::Query, records::Records};
 
 use std::io::{self, BufRead, BufReader, Read, Seek};
 
+use noodles::Region;
 use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, NamedBinningIndex};
 
 const LINE_FEED: u8 = b'\n';
 const CARRIAGE_RETURN: char = '\r';
@@ -216,12 +219,128 @@ where
     }
 }
 
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let data = Vec::new();
+    /// let reader = vcf::Reader::new(bgzf::Reader::new(&data[..]));
+    /// let virtual_position = reader.virtual_position();
+    ///
+    /// assert_eq!(virtual_position.compressed(), 0);
+    /// assert_eq!(virtual_position.uncompressed(), 0);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
+
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Seeks the underlying BGZF stream to the given virtual position.
+    ///
+    /// Virtual positions typically come from an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(vcf::Reader::new)?;
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::from(102334155);
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// The index can be any binning index, e.g., a tabix index (TBI) or coordinate-sorted index
+    /// (CSI).
+    ///
+    /// The end position of a record is taken from its `END` information field, if present;
+    /// otherwise, it is the end of its reference bases.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles::Region;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(vcf::Reader::new)?;
+    ///
+    /// let index = tabix::read("sample.vcf.gz.tbi")?;
+    /// let region = Region::mapped("sq0", 8, 13);
+    /// let query = reader.query(&index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<I>(&mut self, index: &I, region: &Region) -> io::Result<Query<'_, R>>
+    where
+        I: NamedBinningIndex + ?Sized,
+    {
+        let (reference_sequence_name, start, end) = csi::io::resolve_region(region)?;
+
+        let i = index
+            .reference_sequence_id(reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in index: {:?}",
+                        region
+                    ),
+                )
+            })?;
+
+        let chunks = index.query(i, start, end)?;
+
+        Ok(Query::new(
+            &mut self.inner,
+            chunks,
+            reference_sequence_name.into(),
+            start,
+            end,
+        ))
+    }
+}
+
 impl<R> Reader<BufReader<bgzf::Reader<R>>>
 where
     R: Read,
 {
     /// Returns the current virtual position of the underlying BGZF reader.
     ///
+    /// This does not account for data buffered by the [`BufReader`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -248,7 +367,8 @@ where
 {
     /// Seeks the underlying BGZF stream to the given virtual position.
     ///
-    /// Virtual positions typically come from an associated index.
+    /// Data buffered by the [`BufReader`] is discarded. Prefer using a BGZF reader directly, which
+    /// is already buffered.
     ///
     /// # Examples
     ///
@@ -267,12 +387,16 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        let len = self.inner.buffer().len();
+        self.inner.consume(len);
         self.inner.get_mut().seek(pos)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use noodles_tabix as tabix;
+
     use super::*;
 
     static DATA: &[u8] = b"\
@@ -352,4 +476,153 @@ sq0\t13\r\n\
 
         Ok(())
     }
+
+    #[test]
+    fn test_seek_with_buf_reader() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{convert::TryFrom, io::Write};
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(DATA)?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(BufReader::new(bgzf::Reader::new(io::Cursor::new(data))));
+        let header = reader.read_header()?;
+
+        let mut buf = String::new();
+        reader.read_record(&mut buf)?;
+        assert_eq!(buf, "sq0\t8");
+
+        buf.clear();
+        reader.read_record(&mut buf)?;
+        assert_eq!(buf, "sq0\t13");
+
+        let virtual_position = bgzf::VirtualPosition::try_from((0, header.len() as u16))?;
+        reader.seek(virtual_position)?;
+
+        buf.clear();
+        reader.read_record(&mut buf)?;
+        assert_eq!(buf, "sq0\t8");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{convert::TryFrom, io::Write};
+
+        use noodles_tabix::index::reference_sequence::{bin::Chunk, Bin, ReferenceSequence};
+
+        let header = "##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n";
+        let records = "sq0\t1\t.\tA\t<DEL>\t.\tPASS\tEND=8
+sq0\t13\t.\tACGT\t.\t.\tPASS\t.
+sq1\t8\t.\tA\t.\t.\tPASS\t.
+";
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(header.as_bytes())?;
+        writer.write_all(records.as_bytes())?;
+        let data = writer.finish()?;
+
+        let virtual_position = |pos: usize| bgzf::VirtualPosition::try_from((0, pos as u16));
+        let sq0_start = header.len();
+        let sq1_start = sq0_start + records.find("sq1").unwrap();
+        let sq1_end = header.len() + records.len();
+
+        let build_reference_sequence = |start, end| -> Result<_, Box<dyn std::error::Error>> {
+            Ok(ReferenceSequence::new(
+                vec![Bin::new(
+                    4681,
+                    vec![Chunk::new(virtual_position(start)?, virtual_position(end)?)],
+                )],
+                Vec::new(),
+                None,
+            ))
+        };
+
+        let index = tabix::Index::builder()
+            .set_reference_sequence_names(vec![String::from("sq0"), String::from("sq1")])
+            .set_reference_sequences(vec![
+                build_reference_sequence(sq0_start, sq1_start)?,
+                build_reference_sequence(sq1_start, sq1_end)?,
+            ])
+            .build();
+
+        let mut reader = Reader::new(bgzf::Reader::new(io::Cursor::new(data)));
+
+        let region = Region::mapped("sq0", 5, 8);
+        let records: Vec<_> = reader.query(&index, &region)?.collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].position(), 1);
+
+        let region = Region::mapped("sq0", 8, 16);
+        let records: Vec<_> = reader.query(&index, &region)?.collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 2);
+
+        let region = Region::mapped("sq1", 1, 7);
+        let records: Vec<_> = reader.query(&index, &region)?.collect::<Result<_, _>>()?;
+        assert!(records.is_empty());
+
+        let region = Region::mapped("sq2", 1, 8);
+        assert!(reader.query(&index, &region).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_csi() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{convert::TryFrom, io::Write};
+
+        use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+
+        let header = "##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n";
+        let records = "sq0\t8\t.\tA\t.\t.\tPASS\t.
+sq0\t1073741825\t.\tA\t.\t.\tPASS\t.
+";
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(header.as_bytes())?;
+        writer.write_all(records.as_bytes())?;
+        let data = writer.finish()?;
+
+        let virtual_position = |pos: usize| bgzf::VirtualPosition::try_from((0, pos as u16));
+        let r0_start = header.len();
+        let r1_start = r0_start + records.find('\n').unwrap() + 1;
+        let r1_end = header.len() + records.len();
+
+        // The second record is past 2^30, which requires a deeper index than BAI and tabix.
+        let mut aux = vec![0; 24];
+        aux.extend_from_slice(&4i32.to_le_bytes());
+        aux.extend_from_slice(b"sq0\x00");
+
+        let mut indexer = csi::Index::indexer();
+        indexer.set_depth(6);
+        indexer.set_aux(aux);
+        indexer.add_record(
+            0,
+            8,
+            8,
+            true,
+            Chunk::new(virtual_position(r0_start)?, virtual_position(r1_start)?),
+        );
+        indexer.add_record(
+            0,
+            1073741825,
+            1073741825,
+            true,
+            Chunk::new(virtual_position(r1_start)?, virtual_position(r1_end)?),
+        );
+        let index = indexer.build(1);
+
+        let mut reader = Reader::new(bgzf::Reader::new(io::Cursor::new(data)));
+
+        let region = Region::mapped("sq0", 1073741825, 1073741832);
+        let records: Vec<_> = reader.query(&index, &region)?.collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].position(), 1073741825);
+
+        let region = Region::mapped("sq1", 1, 8);
+        assert!(reader.query(&index, &region).is_err());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,189 @@
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Seek},
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+
+use crate::{
+    record::{info, Chromosome},
+    Record,
+};
+
+use super::Reader;
+
+/// An iterator over records of a VCF reader that intersect a given region.
+///
+/// This is created by calling [`vcf::Reader::query`].
+///
+/// [`vcf::Reader::query`]: struct.Reader.html#method.query
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: Reader<csi::io::Query<'a, R>>,
+    reference_sequence_name: String,
+    start: u64,
+    end: u64,
+    line_buf: String,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(crate) fn new(
+        reader: &'a mut bgzf::Reader<R>,
+        chunks: Vec<Chunk>,
+        reference_sequence_name: String,
+        start: u64,
+        end: u64,
+    ) -> Self {
+        Self {
+            reader: Reader::new(csi::io::Query::new(reader, chunks)),
+            reference_sequence_name,
+            start,
+            end,
+            line_buf: String::new(),
+        }
+    }
+
+    fn read_record(&mut self) -> Option<io::Result<Record>> {
+        self.line_buf.clear();
+
+        match self.reader.read_record(&mut self.line_buf) {
+            Ok(0) => None,
+            Ok(_) => Some(
+                self.line_buf
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            ),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl<'a, R> Iterator for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.read_record()? {
+                Ok(record) => {
+                    match intersects(&record, &self.reference_sequence_name, self.start, self.end) {
+                        Ok(true) => return Some(Ok(record)),
+                        Ok(false) => {}
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+fn intersects(
+    record: &Record,
+    reference_sequence_name: &str,
+    start: u64,
+    end: u64,
+) -> io::Result<bool> {
+    if !chromosome_eq(record.chromosome(), reference_sequence_name) {
+        return Ok(false);
+    }
+
+    let record_start = record.position() as u64;
+    let record_end = end_position(record)?;
+
+    Ok(in_interval(record_start, record_end, start, end))
+}
+
+// Compares a chromosome to a reference sequence name, where symbols are written as `<symbol>`.
+fn chromosome_eq(chromosome: &Chromosome, reference_sequence_name: &str) -> bool {
+    match chromosome {
+        Chromosome::Name(name) => name == reference_sequence_name,
+        Chromosome::Symbol(symbol) => reference_sequence_name
+            .strip_prefix('<')
+            .and_then(|s| s.strip_suffix('>'))
+            .map(|s| s == symbol)
+            .unwrap_or(false),
+    }
+}
+
+// Returns the end position of the record.
+//
+// This is the value of the `END` information field if present; otherwise, the end is derived from
+// the length of the reference bases.
+fn end_position(record: &Record) -> io::Result<u64> {
+    let end = record
+        .info()
+        .iter()
+        .find(|field| *field.key() == info::field::Key::EndPosition)
+        .and_then(|field| match field.value() {
+            info::field::Value::Integer(n) => Some(*n),
+            _ => None,
+        });
+
+    match end {
+        Some(n) => u64::try_from(n).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid END position: {}", n),
+            )
+        }),
+        None => {
+            let start = record.position() as u64;
+            let len = record.reference_bases().len().max(1) as u64;
+            Ok(start + len - 1)
+        }
+    }
+}
+
+fn in_interval(a_start: u64, a_end: u64, b_start: u64, b_end: u64) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersects() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\t8\t.\tACGT\t.\t.\tPASS\t.".parse()?;
+
+        assert!(intersects(&record, "sq0", 1, 8)?);
+        assert!(intersects(&record, "sq0", 11, 13)?);
+        assert!(!intersects(&record, "sq0", 12, 13)?);
+        assert!(!intersects(&record, "sq1", 8, 11)?);
+
+        let record: Record = "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tEND=13".parse()?;
+
+        assert!(intersects(&record, "sq0", 13, 21)?);
+        assert!(!intersects(&record, "sq0", 14, 21)?);
+
+        let record: Record = "<sq0>\t8\t.\tACGT\t.\t.\tPASS\t.".parse()?;
+
+        assert!(intersects(&record, "<sq0>", 1, 8)?);
+        assert!(!intersects(&record, "sq0", 1, 8)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersects_with_negative_end_position() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tEND=-1".parse()?;
+
+        assert!(matches!(
+            intersects(&record, "sq0", 1, 8),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(!intersects(&record, "sq1", 1, 8)?);
+
+        Ok(())
+    }
+}
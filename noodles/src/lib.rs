@@ -1,3 +1,3 @@
-mod region;
+pub mod region;
 
 pub use self::region::Region;
@@ -1,6 +1,8 @@
+//! Genomic region and reference sequence resolution.
+
 use std::{error, fmt, num};
 
-use noodles_sam::header::ReferenceSequences;
+use indexmap::IndexMap;
 
 // Position coordinates are 1-based.
 const MIN_POSITION: u64 = 1;
@@ -8,6 +10,20 @@ const MIN_POSITION: u64 = 1;
 static UNMAPPED_NAME: &str = "*";
 static ALL_NAME: &str = ".";
 
+/// A reference sequence a region can be resolved against.
+///
+/// This is typically implemented by the reference sequence type of a format's header, e.g.,
+/// `noodles_sam::header::ReferenceSequence`.
+pub trait ReferenceSequence {
+    /// Returns the length of the reference sequence.
+    fn len(&self) -> u64;
+
+    /// Returns whether the reference sequence has a length of 0.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// A genomic region.
 ///
 /// Genomic regions can either be mapped to a reference sequence, unmapped (*), or an inclusion of
@@ -30,10 +46,13 @@ impl Region {
     ///
     /// If no start position is given, the minimum position of 1 is used. If no end position is
     /// given, the entire span of the reference sequence, i.e., its length, is used.
-    pub fn from_str_reference_sequences(
+    pub fn from_str_reference_sequences<V>(
         s: &str,
-        reference_sequences: &ReferenceSequences,
-    ) -> Result<Self, ParseError> {
+        reference_sequences: &IndexMap<String, V>,
+    ) -> Result<Self, ParseError>
+    where
+        V: ReferenceSequence,
+    {
         if s.is_empty() {
             return Err(ParseError::Empty);
         } else if s == UNMAPPED_NAME {
@@ -52,7 +71,7 @@ impl Region {
                     if reference_sequences.contains_key(s) {
                         return Err(ParseError::Ambiguous);
                     } else {
-                        let resolved_end = end.unwrap_or(reference_sequence.len() as u64);
+                        let resolved_end = end.unwrap_or_else(|| reference_sequence.len());
                         return Ok(Region::mapped(prefix, start, resolved_end));
                     }
                 }
@@ -60,7 +79,7 @@ impl Region {
         }
 
         if let Some(reference_sequence) = reference_sequences.get(s) {
-            let end = reference_sequence.len() as u64;
+            let end = reference_sequence.len();
             Ok(Region::mapped(s, MIN_POSITION, end))
         } else {
             Err(ParseError::Invalid)
@@ -172,21 +191,26 @@ fn parse_interval(s: &str) -> Result<(u64, Option<u64>), ParseError> {
 
 #[cfg(test)]
 mod tests {
-    use noodles_sam::header::ReferenceSequence;
-
     use super::*;
 
+    struct Length(u64);
+
+    impl ReferenceSequence for Length {
+        fn len(&self) -> u64 {
+            self.0
+        }
+    }
+
     #[test]
     fn test_from_str_reference_sequences() {
-        let reference_sequences: ReferenceSequences = vec![
-            ReferenceSequence::new(String::from("sq0"), 8),
-            ReferenceSequence::new(String::from("sq1:"), 13),
-            ReferenceSequence::new(String::from("sq2:5"), 21),
-            ReferenceSequence::new(String::from("sq3"), 34),
-            ReferenceSequence::new(String::from("sq3:5-8"), 55),
+        let reference_sequences: IndexMap<String, Length> = vec![
+            (String::from("sq0"), Length(8)),
+            (String::from("sq1:"), Length(13)),
+            (String::from("sq2:5"), Length(21)),
+            (String::from("sq3"), Length(34)),
+            (String::from("sq3:5-8"), Length(55)),
         ]
         .into_iter()
-        .map(|rs| (rs.name().into(), rs))
         .collect();
 
         assert_eq!(